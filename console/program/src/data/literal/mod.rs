@@ -121,6 +121,7 @@ impl<N: Network> Literal<N> {
             "type": "Literal",
             "vtype": j_vtype,
             "value": j_value,
+            "str": format!("{}", self),
         })
     }
 }
@@ -13,7 +13,6 @@
 // limitations under the License.
 
 use serde_json::json;
-use std::collections::HashMap;
 
 mod entry_type;
 pub use entry_type::EntryType;
@@ -43,17 +42,20 @@ pub struct RecordType<N: Network> {
 
 /// ** Vanguard JSON serialization helper ** ///
 impl<N: Network> RecordType<N> {
+    /// Returns the record type as JSON. The entries of `"data"` are in declaration order, which is the order in
+    /// which the entries of a record of this type are encoded, encrypted, and committed to.
     pub fn to_json(&self) -> serde_json::Value {
-        let mut j_entries: HashMap<String, serde_json::Value> = HashMap::new();
-        for (key, val) in &self.entries {
-            j_entries.insert(key.to_key(), val.to_json());
-        }
+        let j_entries =
+            self.entries.iter().map(|(key, val)| (key.to_key(), val.to_json())).collect::<serde_json::Map<_, _>>();
 
+        // The header holds the built-in fields of the record, and the data holds the user-defined entries.
         json!({
             "type": "RecordType",
             "name": self.name.to_json(),
-            "owner": self.owner.to_json(),
-            "entries": j_entries,
+            "header": {
+                "owner": { "type": "address", "visibility": self.owner.to_json() },
+            },
+            "data": j_entries,
         })
     }
 }
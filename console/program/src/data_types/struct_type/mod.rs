@@ -13,7 +13,6 @@
 // limitations under the License.
 
 use serde_json::json;
-use std::collections::HashMap;
 
 mod bytes;
 mod parse;
@@ -35,7 +34,8 @@ pub struct StructType<N: Network> {
 /// ** Vanguard JSON serialization helper ** ///
 impl<N: Network> StructType<N> {
     pub fn to_json(&self) -> serde_json::Value {
-        let mut j_members: HashMap<String, serde_json::Value> = HashMap::new();
+        // The members are in declaration order, as they are laid out.
+        let mut j_members: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.members {
             j_members.insert(key.to_key(), val.to_json());
         }
@@ -56,7 +56,11 @@ impl<N: Network> ValueType<N> {
             Self::Public(plaintext_type) => plaintext_type.to_json(),
             Self::Private(plaintext_type) => plaintext_type.to_json(),
             Self::Record(identifier) => identifier.to_json(),
-            Self::ExternalRecord(locator) => locator.to_json(),
+            // An external record is a qualified reference to a record in an imported program.
+            Self::ExternalRecord(locator) => json!({
+                "program": locator.program_id().to_string(),
+                "record": locator.resource().to_string(),
+            }),
             Self::Future(locator) => locator.to_json(),
         };
 
@@ -39,7 +39,7 @@ pub struct ClosureCore<N: Network, Instruction: InstructionTrait<N>> {
     /// Input assignments are ensured to match the ordering of the input statements.
     inputs: IndexSet<Input<N>>,
     /// The instructions, in order of execution.
-    instructions: Vec<Instruction>,
+    pub(crate) instructions: Vec<Instruction>,
     /// The output statements, in order of the desired output.
     outputs: IndexSet<Output<N>>,
 }
@@ -53,8 +53,12 @@ impl<N: Network, Instruction: InstructionTrait<N>> ClosureCore<N, Instruction> {
         }
 
         let mut j_instructions = Vec::new();
-        for val in &self.instructions {
-            j_instructions.push(val.to_json());
+        for (index, val) in self.instructions.iter().enumerate() {
+            // Tag each instruction with its position in the closure, i.e. `transfer_public#3`.
+            let mut j_instruction = val.to_json();
+            j_instruction["index"] = json!(index);
+            j_instruction["uid"] = json!(format!("{}#{index}", self.name));
+            j_instructions.push(j_instruction);
         }
 
         let mut j_outputs = Vec::new();
@@ -65,6 +69,8 @@ impl<N: Network, Instruction: InstructionTrait<N>> ClosureCore<N, Instruction> {
         json!({
             "type": "ClosureCore",
             "name": self.name.to_json(),
+            // A closure can only be called from within its program.
+            "is_entrypoint": false,
             "inputs": j_inputs,
             "instructions": j_instructions,
             "outputs": j_outputs,
@@ -26,7 +26,7 @@ use console::{
     program::{FinalizeType, Identifier, Register},
 };
 
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use std::collections::HashMap;
 
 #[derive(Clone, PartialEq, Eq)]
@@ -37,7 +37,7 @@ pub struct FinalizeCore<N: Network, Command: CommandTrait<N>> {
     /// Input assignments are ensured to match the ordering of the input statements.
     inputs: IndexSet<Input<N>>,
     /// The commands, in order of execution.
-    commands: Vec<Command>,
+    pub(crate) commands: Vec<Command>,
     /// The number of write commands.
     num_writes: u16,
     /// A mapping from `Position`s to their index in `commands`.
@@ -57,8 +57,11 @@ impl<N: Network, Command: CommandTrait<N>> FinalizeCore<N, Command> {
             j_commands.push(val.to_json());
         }
 
-        let mut j_positions: HashMap<String, serde_json::Value> = HashMap::new();
-        for (key, val) in &self.positions {
+        // The positions are in the order of their commands, so that the output is reproducible.
+        let mut positions = self.positions.iter().collect::<Vec<_>>();
+        positions.sort_by_key(|(_, index)| **index);
+        let mut j_positions: IndexMap<String, serde_json::Value> = IndexMap::new();
+        for (key, val) in positions {
             j_positions.insert(key.to_key(), json!(val));
         }
 
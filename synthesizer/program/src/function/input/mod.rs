@@ -39,10 +39,24 @@ impl<N: Network> Input<N> {
             "type": "Input",
             "register": self.register.to_json(),
             "value_type": self.value_type.to_json(),
+            "visibility": self.visibility(),
             "str": format!("{}", self),
         })
     }
 
+    /// Returns the visibility of the input, i.e. `"public"`, which determines how a caller encodes it.
+    fn visibility(&self) -> &'static str {
+        match self.value_type {
+            ValueType::Constant(_) => "constant",
+            ValueType::Public(_) => "public",
+            ValueType::Private(_) => "private",
+            ValueType::Record(_) => "record",
+            ValueType::ExternalRecord(_) => "external_record",
+            // A future is not a valid input type, but is handled for completeness.
+            ValueType::Future(_) => "future",
+        }
+    }
+
     /// Returns the input register.
     #[inline]
     pub const fn register(&self) -> &Register<N> {
@@ -42,11 +42,11 @@ pub struct FunctionCore<N: Network, Instruction: InstructionTrait<N>, Command: C
     /// Input assignments are ensured to match the ordering of the input statements.
     inputs: IndexSet<Input<N>>,
     /// The instructions, in order of execution.
-    instructions: Vec<Instruction>,
+    pub(crate) instructions: Vec<Instruction>,
     /// The output statements, in order of the desired output.
     outputs: IndexSet<Output<N>>,
     /// The optional finalize logic.
-    finalize_logic: Option<FinalizeCore<N, Command>>,
+    pub(crate) finalize_logic: Option<FinalizeCore<N, Command>>,
 }
 
 /// ** Vanguard JSON serialization helper ** ///
@@ -58,8 +58,12 @@ impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> Fun
         }
 
         let mut j_instructions = Vec::new();
-        for val in &self.instructions {
-            j_instructions.push(val.to_json());
+        for (index, val) in self.instructions.iter().enumerate() {
+            // Tag each instruction with its position in the function, i.e. `transfer_public#3`.
+            let mut j_instruction = val.to_json();
+            j_instruction["index"] = json!(index);
+            j_instruction["uid"] = json!(format!("{}#{index}", self.name));
+            j_instructions.push(j_instruction);
         }
 
         let mut j_outputs = Vec::new();
@@ -68,13 +72,20 @@ impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> Fun
         }
 
         let j_finalize_logic = match &self.finalize_logic {
-            Some(v) => v.to_json(),
+            Some(v) => {
+                // Link the finalize logic back to this function, for consumers that process it on its own.
+                let mut j_finalize = v.to_json();
+                j_finalize["function"] = self.name.to_json();
+                j_finalize
+            }
             None => serde_json::Value::Null,
         };
 
         json!({
             "type": "FunctionCore",
             "name": self.name.to_json(),
+            // Every function can be invoked as a transition.
+            "is_entrypoint": true,
             "inputs": j_inputs,
             "instructions": j_instructions,
             "outputs": j_outputs,
@@ -17,7 +17,6 @@
 #![warn(clippy::cast_possible_truncation)]
 
 use serde_json::json;
-use std::collections::HashMap;
 
 pub type Program<N> = crate::ProgramCore<N, Instruction<N>, Command<N>>;
 pub type Function<N> = crate::FunctionCore<N, Instruction<N>, Command<N>>;
@@ -147,44 +146,45 @@ pub struct ProgramCore<N: Network, Instruction: InstructionTrait<N>, Command: Co
 /// ** Vanguard JSON serialization helper ** ///
 impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> ProgramCore<N, Instruction, Command> {
     pub fn to_json(&self) -> serde_json::Value {
+        // Each section is collected in declaration order, so that the output is reproducible.
         // collect imports
-        let mut j_imports: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_imports: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.imports {
             j_imports.insert(key.to_key(), val.to_json());
         }
 
         // collect identifiers
-        let mut j_identifiers: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_identifiers: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.identifiers {
             j_identifiers.insert(key.to_key(), val.to_json());
         }
 
         // collect mappings
-        let mut j_mappings: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_mappings: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.mappings {
             j_mappings.insert(key.to_key(), val.to_json());
         }
 
         // collect structs
-        let mut j_structs: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_structs: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.structs {
             j_structs.insert(key.to_key(), val.to_json());
         }
 
         // collect records
-        let mut j_records: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_records: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.records {
             j_records.insert(key.to_key(), val.to_json());
         }
 
         // collect closures
-        let mut j_closures: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_closures: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.closures {
             j_closures.insert(key.to_key(), val.to_json());
         }
 
         // collect functions
-        let mut j_functions: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_functions: IndexMap<String, serde_json::Value> = IndexMap::new();
         for (key, val) in &self.functions {
             j_functions.insert(key.to_key(), val.to_json());
         }
@@ -203,6 +203,30 @@ impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> Pro
     }
 }
 
+/// ** Vanguard desugaring helper ** ///
+impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> ProgramCore<N, Instruction, Command> {
+    /// Returns the program with each instruction of its closures and functions, and each command of their finalize
+    /// logic, replaced by the given functions of it, in place, so that each finalize position still refers to the
+    /// same command. The functions must keep the kind of each command, i.e. a write.
+    pub fn map_instructions(
+        &self,
+        mut instruction: impl FnMut(&Instruction) -> Result<Instruction>,
+        mut command: impl FnMut(&Command) -> Result<Command>,
+    ) -> Result<Self> {
+        let mut program = self.clone();
+        let closures = program.closures.values_mut().map(|closure| &mut closure.instructions);
+        let functions = program.functions.values_mut().map(|function| &mut function.instructions);
+        for each in closures.chain(functions).flatten() {
+            *each = instruction(each)?;
+        }
+        let finalizes = program.functions.values_mut().filter_map(|function| function.finalize_logic.as_mut());
+        for each in finalizes.flat_map(|finalize| &mut finalize.commands) {
+            *each = command(each)?;
+        }
+        Ok(program)
+    }
+}
+
 impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> ProgramCore<N, Instruction, Command> {
     /// Initializes an empty program.
     #[inline]
@@ -42,7 +42,9 @@ impl<N: Network> Contains<N> {
     pub fn to_json(&self) -> serde_json::Value {
         json!({
             "type": "Contains",
+            "access": "read",
             "mapping": self.mapping.to_json(),
+            "mapping_name": self.mapping.to_string(),
             "key": self.key.to_json(),
             "destination": self.destination.to_json(),
         })
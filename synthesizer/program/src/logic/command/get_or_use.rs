@@ -49,7 +49,9 @@ impl<N: Network> GetOrUse<N> {
     pub fn to_json(&self) -> serde_json::Value {
         json!({
             "type": "GetOrUse",
+            "access": "read",
             "mapping": self.mapping.to_json(),
+            "mapping_name": self.mapping.to_string(),
             "key": self.key.to_json(),
             "default": self.default.to_json(),
             "destination": self.destination.to_json(),
@@ -37,7 +37,9 @@ impl<N: Network> Remove<N> {
     pub fn to_json(&self) -> serde_json::Value {
         json!({
             "type": "Remove",
+            "access": "write",
             "mapping": self.mapping.to_json(),
+            "mapping_name": self.mapping.to_string(),
             "key": self.key.to_json(),
         })
     }
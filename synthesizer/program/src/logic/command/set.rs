@@ -42,7 +42,9 @@ impl<N: Network> Set<N> {
     pub fn to_json(&self) -> serde_json::Value {
         json!({
             "type": "Set",
+            "access": "write",
             "mapping": self.mapping.to_json(),
+            "mapping_name": self.mapping.to_string(),
             "key": self.key.to_json(),
             "value": self.value.to_json(),
         })
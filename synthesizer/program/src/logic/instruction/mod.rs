@@ -532,10 +532,20 @@ impl<N: Network> InstructionTrait<N> for Instruction<N> {
             Instruction::Xor(instruction) => instruction.to_json(),
         };
 
+        // The registers written by the instruction, i.e. `null`, `"r2"`, or `["r2", "r3"]`.
+        let mut j_destinations: Vec<_> =
+            self.destinations().iter().map(|register| json!(register.to_string())).collect();
+        let j_destination = match j_destinations.len() {
+            0 => serde_json::Value::Null,
+            1 => j_destinations.remove(0),
+            _ => serde_json::Value::Array(j_destinations),
+        };
+
         json!({
             "type": "Instruction",
             "vtype": j_vtype,
             "value": j_value,
+            "destination": j_destination,
             "str": format!("{}", self),
         })
     }
@@ -557,6 +567,13 @@ impl<N: Network> Instruction<N> {
         instruction!(self, |instruction| instruction.operands())
     }
 
+    /// ** Vanguard desugaring helper ** ///
+    /// Returns the instruction with the given operands in place of its own, which must be as many.
+    #[inline]
+    pub fn with_operands(&self, operands: Vec<Operand<N>>) -> Result<Self> {
+        instruction!(self, |instruction| Ok(instruction.with_operands(operands)?.into()))
+    }
+
     /// Evaluates the instruction.
     #[inline]
     pub fn evaluate(
@@ -77,11 +77,31 @@ impl<N: Network> Operand<N> {
             Self::BlockHeight => serde_json::Value::Null,
         };
 
-        json!({
+        let j_kind = match self {
+            // Prints the literal, i.e. 10field.private
+            Self::Literal(_) => "literal",
+            // Prints the register, i.e. r0
+            Self::Register(Register::Locator(_)) => "register",
+            // Prints the register access, i.e. r0.owner
+            Self::Register(Register::Access(..)) => "accessor",
+            // Prints the program ID, i.e. howard.aleo
+            Self::ProgramID(_) => "identifier",
+            // Prints the built-in reference to the execution context, i.e. self.caller
+            Self::Signer | Self::Caller | Self::BlockHeight => "builtin",
+        };
+
+        let mut json = json!({
             "type": "Operand",
+            "kind": j_kind,
             "vtype": j_vtype,
             "value": j_value,
-        })
+            "str": self.to_string(),
+        });
+        // Name each built-in, i.e. self.caller, as it is an implicit input of the function rather than a value.
+        if j_kind == "builtin" {
+            json["name"] = json!(self.to_string());
+        }
+        json
     }
 }
 
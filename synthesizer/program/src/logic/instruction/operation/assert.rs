@@ -49,13 +49,17 @@ impl<N: Network, const VARIANT: u8> AssertInstruction<N, VARIANT> {
             j_operands.push(val.to_json());
         }
 
+        // An assertion has no destination, but has the same shape as the other instructions.
         json!({
             "type": "AssertInstruction",
             "operands": j_operands,
+            "destination": serde_json::Value::Null,
         })
     }
 }
 
+crate::with_operands!([N: Network, const VARIANT: u8] AssertInstruction<N, VARIANT> {});
+
 impl<N: Network, const VARIANT: u8> AssertInstruction<N, VARIANT> {
     /// Initializes a new `assert` instruction.
     #[inline]
@@ -58,6 +58,8 @@ impl<N: Network> Async<N> {
     }
 }
 
+crate::with_operands!([N: Network] Async<N> { function_name, destination });
+
 impl<N: Network> Async<N> {
     /// Returns the opcode.
     #[inline]
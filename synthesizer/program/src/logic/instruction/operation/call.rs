@@ -165,6 +165,8 @@ impl<N: Network> Call<N> {
     }
 }
 
+crate::with_operands!([N: Network] Call<N> { operator, destinations });
+
 impl<N: Network> Call<N> {
     /// Returns the opcode.
     #[inline]
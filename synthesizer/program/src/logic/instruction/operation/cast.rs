@@ -75,7 +75,7 @@ impl<N: Network> CastType<N> {
 
         let j_value = match self {
             Self::GroupXCoordinate => json!("group.x"),
-            Self::GroupYCoordinate => json!("group.x"),
+            Self::GroupYCoordinate => json!("group.y"),
             CastType::Plaintext(plaintext_type) => plaintext_type.to_json(),
             CastType::Record(identifier) => identifier.to_json(),
             CastType::ExternalRecord(locator) => locator.to_json(),
@@ -210,10 +210,14 @@ impl<N: Network, const VARIANT: u8> CastOperation<N, VARIANT> {
             "operands": j_operands,
             "destination": self.destination.to_json(),
             "cast_type": self.cast_type.to_json(),
+            // The target type as written in the program, i.e. `token.record`.
+            "cast_to": self.cast_type.to_string(),
         })
     }
 }
 
+crate::with_operands!([N: Network, const VARIANT: u8] CastOperation<N, VARIANT> { destination, cast_type });
+
 impl<N: Network, const VARIANT: u8> CastOperation<N, VARIANT> {
     /// Returns the opcode.
     #[inline]
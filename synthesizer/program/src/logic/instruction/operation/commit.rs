@@ -80,6 +80,8 @@ impl<N: Network, const VARIANT: u8> CommitInstruction<N, VARIANT> {
     }
 }
 
+crate::with_operands!([N: Network, const VARIANT: u8] CommitInstruction<N, VARIANT> { destination, destination_type });
+
 impl<N: Network, const VARIANT: u8> CommitInstruction<N, VARIANT> {
     /// Initializes a new `commit` instruction.
     #[inline]
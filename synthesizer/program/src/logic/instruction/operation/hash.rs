@@ -144,6 +144,8 @@ impl<N: Network, const VARIANT: u8> HashInstruction<N, VARIANT> {
     }
 }
 
+crate::with_operands!([N: Network, const VARIANT: u8] HashInstruction<N, VARIANT> { destination, destination_type });
+
 impl<N: Network, const VARIANT: u8> HashInstruction<N, VARIANT> {
     /// Initializes a new `hash` instruction.
     #[inline]
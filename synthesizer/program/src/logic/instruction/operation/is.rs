@@ -60,6 +60,8 @@ impl<N: Network, const VARIANT: u8> IsInstruction<N, VARIANT> {
     }
 }
 
+crate::with_operands!([N: Network, const VARIANT: u8] IsInstruction<N, VARIANT> { destination });
+
 impl<N: Network, const VARIANT: u8> IsInstruction<N, VARIANT> {
     /// Initializes a new `is` instruction.
     #[inline]
@@ -62,6 +62,22 @@ impl<N: Network, O: Operation<N, Literal<N>, LiteralType, NUM_OPERANDS>, const N
     }
 }
 
+/// ** Vanguard desugaring helper ** ///
+impl<N: Network, O: Operation<N, Literal<N>, LiteralType, NUM_OPERANDS>, const NUM_OPERANDS: usize>
+    Literals<N, O, NUM_OPERANDS>
+{
+    /// Initializes a new instruction with the given operands and destination, i.e. to write `gt a b` as `lt b a`.
+    pub fn new(operands: Vec<Operand<N>>, destination: Register<N>) -> Result<Self> {
+        ensure!(operands.len() == NUM_OPERANDS, "Instruction '{}' must have {NUM_OPERANDS} operands", O::OPCODE);
+        Ok(Self { operands, destination, _phantom: PhantomData })
+    }
+}
+
+crate::with_operands!(
+    [N: Network, O: Operation<N, Literal<N>, LiteralType, NUM_OPERANDS>, const NUM_OPERANDS: usize]
+    Literals<N, O, NUM_OPERANDS> { destination, _phantom }
+);
+
 impl<N: Network, O: Operation<N, Literal<N>, LiteralType, NUM_OPERANDS>, const NUM_OPERANDS: usize>
     Literals<N, O, NUM_OPERANDS>
 {
@@ -964,3 +964,26 @@ mod tests {
         };
     }
 }
+
+/// Implements `with_operands` for the given instruction, returning it with the given operands in place of its own,
+/// and a copy of each of its other given fields. It is invoked in the module of each instruction, as it sets the
+/// private fields of the instruction.
+///
+/// # Examples
+/// ```ignore
+/// with_operands!([N: Network] Call<N> { operator, destinations });
+/// ```
+#[macro_export]
+macro_rules! with_operands {
+    ([$($generics:tt)*] $instruction:ty { $($field:ident),* }) => {
+        /// ** Vanguard desugaring helper ** ///
+        impl<$($generics)*> $instruction {
+            /// Returns the instruction with the given operands in place of its own, which must be as many.
+            pub fn with_operands(&self, operands: Vec<Operand<N>>) -> Result<Self> {
+                let expected = self.operands.len();
+                ensure!(operands.len() == expected, "Expected {expected} operands, found {}", operands.len());
+                Ok(Self { operands, $($field: self.$field.clone()),* })
+            }
+        }
+    };
+}
@@ -51,6 +51,8 @@ impl<N: Network> SignVerify<N> {
     }
 }
 
+crate::with_operands!([N: Network] SignVerify<N> { destination });
+
 impl<N: Network> SignVerify<N> {
     /// Initializes a new `sign.verify` instruction.
     #[inline]
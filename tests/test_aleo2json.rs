@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    aleo2json::{parse_program, to_string_fallback},
+    console::network::Testnet3,
+};
+
+use serde_json::{json, Value};
+use std::{fs, path::Path, process::Command};
+
+type CurrentNetwork = Testnet3;
+
+/// Converts the given program into JSON, or into the error it fails with.
+fn convert(source: &str) -> Value {
+    match parse_program::<CurrentNetwork>(source) {
+        Ok(program) => program.to_json(),
+        Err(error) => json!({ "error": error.to_string(), "offset": error.offset() }),
+    }
+}
+
+/// Runs each program in `./tests/aleo2json/programs`, and compares its JSON against the corresponding
+/// file in `./tests/aleo2json/expectations`. Set `REWRITE_EXPECTATIONS=1` to regenerate the expectations.
+#[test]
+fn test_aleo2json_golden_files() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("aleo2json");
+    let rewrite = std::env::var("REWRITE_EXPECTATIONS").is_ok();
+
+    let mut paths = fs::read_dir(directory.join("programs"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "aleo"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert!(!paths.is_empty(), "No programs found in '{}'", directory.display());
+
+    for path in paths {
+        let actual = convert(&fs::read_to_string(&path).unwrap());
+        let expectation = directory.join("expectations").join(path.file_stem().unwrap()).with_extension("json");
+
+        if rewrite {
+            fs::write(&expectation, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&expectation).unwrap_or_else(|_| {
+            panic!("Missing '{}', run with REWRITE_EXPECTATIONS=1 to create it", expectation.display())
+        });
+        // The comparison does not depend on the order of the keys.
+        let expected: Value = serde_json::from_str(&expected).unwrap();
+        assert_eq!(expected, actual, "The JSON of '{}' does not match its expectation", path.display());
+    }
+}
+
+/// Checks that the JSON writer of the `no-std-serialize` feature writes the JSON of each program in
+/// `./tests/aleo2json/programs` byte for byte as `serde_json` does, indented or not.
+#[test]
+fn test_aleo2json_fallback_writer() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("aleo2json").join("programs");
+    for entry in fs::read_dir(&directory).unwrap() {
+        let path = entry.unwrap().path();
+        let json = convert(&fs::read_to_string(&path).unwrap());
+        assert_eq!(to_string_fallback(&json, false), serde_json::to_string(&json).unwrap(), "{}", path.display());
+        assert_eq!(to_string_fallback(&json, true), serde_json::to_string_pretty(&json).unwrap(), "{}", path.display());
+    }
+}
+
+/// Checks that the numbers in the output (i.e. the statistics, the layouts, and the sizes) do not depend on the
+/// locale of the host, by converting each valid program in `./tests/aleo2json/programs` under the `C` locale,
+/// and under a locale with thousands separators and a decimal comma. The test is skipped on a host without that
+/// locale, as the program would silently fall back to the `C` locale.
+#[test]
+fn test_aleo2json_output_ignores_locale() {
+    const LOCALE: &str = "de_DE.UTF-8";
+    // The installed locales are listed as `de_DE.utf8`, with the name of the encoding normalized.
+    let normalize = |locale: &str| locale.to_lowercase().replace('-', "");
+    let locales = Command::new("locale").arg("-a").output();
+    let locales = locales.map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+    if !locales.is_ok_and(|locales| locales.lines().any(|locale| normalize(locale) == normalize(LOCALE))) {
+        eprintln!("Skipping the test, as the locale '{LOCALE}' is not installed");
+        return;
+    }
+
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("aleo2json").join("programs");
+    let convert = |path: &Path, locale: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_aleo2json"))
+            .arg(path)
+            .args(["--stats", "--per-function", "--with-layout", "--with-byte-sizes", "--with-cost", "--pretty"])
+            .env("LC_ALL", locale)
+            .env("LC_NUMERIC", locale)
+            .env("LANG", locale)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "Failed to convert '{}'", path.display());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    for entry in fs::read_dir(&directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path.file_stem().unwrap().to_string_lossy().starts_with("invalid") {
+            continue;
+        }
+        let expected = convert(&path, "C");
+        assert_eq!(convert(&path, LOCALE), expected, "The output of '{}' depends on the locale", path.display());
+        // Each number is plain digits, so that the output parses back as it is.
+        let json: Value = serde_json::from_str(&expected).unwrap();
+        assert!(json["stats"].is_object());
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{parse_program, ConvertOptions, NetworkName},
+    console::network::{Network, Testnet3},
+};
+use snarkvm_synthesizer_program::Program;
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+/// Returns the interface of the given program, without the bodies of its functions, i.e.
+/// `{ "id": "token.aleo", "imports": [...], "functions": { "mint": { "inputs": ["u64.public"], "outputs": [...],
+/// "finalize": true } }, "mappings": { ... }, "structs": { ... }, "records": { ... } }`.
+/// Closures are left out, as they cannot be called from another program.
+pub fn program_abi<N: Network>(program: &Program<N>) -> Value {
+    let functions = program.functions().iter().map(|(name, function)| {
+        let inputs = function.inputs().iter().map(|input| input.value_type().to_string()).collect::<Vec<_>>();
+        let outputs = function.outputs().iter().map(|output| output.value_type().to_string()).collect::<Vec<_>>();
+        let abi = json!({ "inputs": inputs, "outputs": outputs, "finalize": function.finalize_logic().is_some() });
+        (name.to_string(), abi)
+    });
+    let mappings = program.mappings().iter().map(|(name, mapping)| {
+        let key = mapping.key().plaintext_type().to_string();
+        (name.to_string(), json!({ "key": key, "value": mapping.value().plaintext_type().to_string() }))
+    });
+    let structs = program.structs().iter().map(|(name, struct_)| {
+        let members = struct_.members().iter().map(|(name, member)| (name.to_string(), json!(member.to_string())));
+        (name.to_string(), Value::Object(members.collect()))
+    });
+    let records = program.records().iter().map(|(name, record)| {
+        let owner = match record.owner().is_public() {
+            true => "address.public",
+            false => "address.private",
+        };
+        let entries = record.entries().iter().map(|(name, entry)| (name.to_string(), json!(entry.to_string())));
+        let entries = std::iter::once(("owner".to_string(), json!(owner))).chain(entries);
+        (name.to_string(), Value::Object(entries.collect()))
+    });
+    json!({
+        "id": program.id().to_string(),
+        "imports": program.imports().keys().map(|program_id| program_id.to_string()).collect::<Vec<_>>(),
+        "functions": functions.collect::<Map<_, _>>(),
+        "mappings": mappings.collect::<Map<_, _>>(),
+        "structs": structs.collect::<Map<_, _>>(),
+        "records": records.collect::<Map<_, _>>(),
+    })
+}
+
+/// Parses the given string into a program, and returns its interface, for the network of the given options.
+pub fn convert_abi(string: &str, options: &ConvertOptions) -> Result<Value> {
+    match options.network {
+        NetworkName::Testnet3 => Ok(program_abi(&parse_program::<Testnet3>(string)?)),
+    }
+}
+
+/// Inserts the given program interface into the given index, by program ID, i.e. `{ "token.aleo": { ... } }`.
+/// Returns a warning if another interface of the same ID is already in the index, which is then kept.
+/// An identical interface is a duplicate, not a conflict, and is silently skipped.
+pub fn insert_into_index(index: &mut Map<String, Value>, abi: Value) -> Option<String> {
+    let id = abi["id"].as_str().unwrap_or_default().to_string();
+    match index.get(&id) {
+        Some(existing) if *existing == abi => None,
+        Some(_) => Some(format!("warning: the interface of '{id}' conflicts with a previous one, which is kept")),
+        None => {
+            index.insert(id, abi);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = r"
+import credits.aleo;
+program token.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+record coin:
+    owner as address.private;
+    amount as u64.private;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function mint:
+    input r0 as u64.public;
+    input r1 as point.private;
+    async mint r0 into r2;
+    output r2 as token.aleo/mint.future;
+
+finalize mint:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+
+function burn:
+    input r0 as coin.record;
+    output r0 as coin.record;
+";
+
+    #[test]
+    fn test_program_abi() {
+        let abi = convert_abi(PROGRAM, &ConvertOptions::new()).unwrap();
+        assert_eq!(
+            abi,
+            json!({
+                "id": "token.aleo",
+                "imports": ["credits.aleo"],
+                "functions": {
+                    "mint": {
+                        "inputs": ["u64.public", "point.private"],
+                        "outputs": ["token.aleo/mint.future"],
+                        "finalize": true,
+                    },
+                    "burn": { "inputs": ["coin.record"], "outputs": ["coin.record"], "finalize": false },
+                },
+                "mappings": { "balances": { "key": "address", "value": "u64" } },
+                "structs": { "point": { "x": "field", "y": "field" } },
+                "records": { "coin": { "owner": "address.private", "amount": "u64.private" } },
+            })
+        );
+    }
+
+    #[test]
+    fn test_insert_into_index() {
+        let abi = convert_abi(PROGRAM, &ConvertOptions::new()).unwrap();
+        let mut index = Map::new();
+        assert_eq!(insert_into_index(&mut index, abi.clone()), None);
+        assert_eq!(insert_into_index(&mut index, abi.clone()), None);
+
+        let changed = convert_abi(&PROGRAM.replace("output r0 as coin.record;", ""), &ConvertOptions::new()).unwrap();
+        assert!(insert_into_index(&mut index, changed).unwrap().contains("'token.aleo' conflicts"));
+        assert_eq!(index.len(), 1);
+        assert_eq!(index["token.aleo"], abi);
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{
+    network::Network,
+    program::{Access, Register},
+};
+
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+/// Returns the register and the access path of the given register access, i.e.
+/// `{ "register": "r0", "path": ["token", "amount"] }` for `r0.token.amount`. An array index is a number,
+/// i.e. `["balances", 1]` for `r0.balances[1u32]`.
+fn access_path<N: Network>(register: &str) -> Option<Value> {
+    let Ok(Register::<N>::Access(locator, accesses)) = Register::<N>::from_str(register) else {
+        return None;
+    };
+    let path = accesses
+        .iter()
+        .map(|access| match access {
+            Access::Member(identifier) => json!(identifier.to_string()),
+            Access::Index(index) => json!(**index),
+        })
+        .collect::<Vec<_>>();
+    Some(json!({ "register": format!("r{locator}"), "path": path }))
+}
+
+/// Adds the register and the access path of each accessor operand under `"access"`, i.e.
+/// `"access": { "register": "r0", "path": ["token", "amount"] }` for `r0.token.amount`.
+pub fn add_access_paths<N: Network>(json: &mut Value) {
+    if json["type"] == "Operand" && json["kind"] == "accessor" {
+        if let Some(path) = json["str"].as_str().and_then(access_path::<N>) {
+            json["access"] = path;
+        }
+        return;
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(add_access_paths::<N>),
+        Value::Array(array) => array.iter_mut().for_each(add_access_paths::<N>),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_access_paths() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program accessors.aleo;
+
+struct balance:
+    amount as u64;
+
+struct wallet:
+    balance as balance;
+    history as [u64; 4u32];
+
+function main:
+    input r0 as wallet.private;
+    input r1 as u64.private;
+    add r0.balance.amount r0.history[1u32] into r2;
+    add r1 r2 into r3;
+    output r3 as u64.private;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_access_paths::<CurrentNetwork>(&mut json);
+        let instructions = &json["functions"]["main"]["instructions"];
+        let operands = &instructions[0]["value"]["operands"];
+        assert_eq!(operands[0]["access"], json!({ "register": "r0", "path": ["balance", "amount"] }));
+        assert_eq!(operands[1]["access"], json!({ "register": "r0", "path": ["history", 1] }));
+        // A plain register has no access path.
+        assert!(instructions[1]["value"]["operands"][0].get("access").is_none());
+    }
+}
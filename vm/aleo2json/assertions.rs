@@ -0,0 +1,85 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::network::Network;
+use snarkvm_synthesizer_program::{Instruction, Program};
+
+use serde_json::{json, Value};
+
+/// Returns the assertions among the given instructions, i.e.
+/// `[{ "type": "Assertion", "index": 1, "opcode": "assert.eq", "operands": [...], "str": "assert.eq r1 r0;" }]`.
+fn assertions<N: Network>(instructions: &[Instruction<N>]) -> Vec<Value> {
+    let assertions = instructions.iter().enumerate().filter(|(_, instruction)| {
+        matches!(instruction, Instruction::AssertEq(..) | Instruction::AssertNeq(..))
+    });
+    assertions
+        .map(|(index, instruction)| {
+            json!({
+                "type": "Assertion",
+                "index": index,
+                "opcode": instruction.opcode().to_string(),
+                "operands": instruction.operands().iter().map(|operand| operand.to_json()).collect::<Vec<_>>(),
+                "str": instruction.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Adds the `assert.eq` and `assert.neq` instructions of each function and closure under `"assertions"`, in order,
+/// each with its index among the instructions, so that the invariants it enforces can be read at a glance.
+pub fn add_assertions<N: Network>(json: &mut Value, program: &Program<N>) {
+    let closures = program.closures().iter().map(|(name, closure)| ("closures", name, closure.instructions()));
+    let functions = program.functions().iter().map(|(name, function)| ("functions", name, function.instructions()));
+    for (section, name, instructions) in closures.chain(functions) {
+        if let Some(function) = json.get_mut(section).and_then(|section| section.get_mut(name.to_string())) {
+            function["assertions"] = Value::Array(assertions(instructions));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aleo2json::{convert_program, ConvertOptions};
+
+    #[test]
+    fn test_add_assertions() {
+        let source = r"
+program assertions.aleo;
+
+closure check:
+    input r0 as u8;
+    assert.neq r0 0u8;
+
+function main:
+    input r0 as u8.public;
+    input r1 as u8.public;
+    add r0 r1 into r2;
+    assert.eq r2 r1;
+    is.eq r0 r1 into r3;
+    assert.neq r0 1u8;
+";
+        let json = convert_program(source, &ConvertOptions::new()).unwrap();
+        let assertions = json["functions"]["main"]["assertions"].as_array().unwrap();
+        assert_eq!(assertions.len(), 2);
+        assert_eq!(assertions[0]["index"], 1);
+        assert_eq!(assertions[0]["opcode"], "assert.eq");
+        assert_eq!(assertions[0]["operands"][0]["str"], "r2");
+        assert_eq!(assertions[1]["index"], 3);
+        // An assertion is printed with a space before its semicolon.
+        assert_eq!(assertions[1]["str"], "assert.neq r0 1u8 ;");
+        // The index of an assertion is that of its instruction.
+        assert_eq!(json["functions"]["main"]["instructions"][3]["str"], assertions[1]["str"]);
+        assert_eq!(json["closures"]["check"]["assertions"][0]["opcode"], "assert.neq");
+    }
+}
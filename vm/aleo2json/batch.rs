@@ -0,0 +1,392 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{to_json_string, Failure};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant, SystemTime},
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// The minimum interval between two progress updates.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Returns the paths of the Aleo programs in the given directory, recursively, in sorted order.
+pub fn collect_programs(directory: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            paths.extend(collect_programs(&path)?);
+        } else if path.extension().is_some_and(|extension| extension == "aleo") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Returns the paths of the Aleo programs in the given directory changed in the given git revision range, i.e.
+/// `main..HEAD`, as listed by `git diff --name-only`, in sorted order. A program deleted in the range is not listed,
+/// as there is nothing left to convert. Fails if git is not available, or if the range is invalid.
+pub fn changed_programs(directory: &Path, range: &str) -> Result<Vec<PathBuf>> {
+    // A range is never an option of `git diff`.
+    if range.starts_with('-') {
+        return Err(Failure::usage(format!("Invalid git revision range '{range}'")).into());
+    }
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(["-c", "core.quotePath=off", "diff", "--name-only", "--relative", "--diff-filter=d", range, "--"])
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Err(error).context("Failed to run git, which '--git-diff' requires, as it was not found");
+        }
+        Err(error) => return Err(error).context("Failed to run git"),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = format!("Failed to diff the range '{range}' in '{}': {}", directory.display(), stderr.trim());
+        return Err(Failure::usage(message).into());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let paths = stdout.lines().filter(|line| line.ends_with(".aleo")).map(|line| directory.join(line));
+    let mut paths = paths.collect::<Vec<_>>();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Returns a sample of `size` of the given paths, chosen by an RNG seeded with `seed`, in sorted order.
+/// The same seed always yields the same sample of the same paths.
+pub fn sample_programs(paths: &[PathBuf], size: usize, seed: u64) -> Vec<PathBuf> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut sample = paths.choose_multiple(&mut rng, size).cloned().collect::<Vec<_>>();
+    sample.sort();
+    sample
+}
+
+/// Returns the path of the output file of the given program in the given directory, mirroring its path relative
+/// to the input directory, i.e. `out/nested/main.json` for `programs/nested/main.aleo`.
+pub fn output_path(directory: &Path, path: &Path, output_dir: &Path, extension: &str) -> PathBuf {
+    let relative = path.strip_prefix(directory).unwrap_or(path);
+    output_dir.join(relative).with_extension(extension)
+}
+
+/// The key to partition the programs of a directory by, with `--partition-by`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PartitionBy {
+    /// The namespace of the program ID, as given by `program_namespace`, i.e. `token` for `token_bridge.aleo`.
+    Namespace,
+}
+
+impl PartitionBy {
+    /// Returns the partition of the program at the given path, given its JSON, if it converted. The name of a program
+    /// is that of its ID, or else, if it failed to convert, the stem of its file name, i.e. `main` for `main.aleo`.
+    pub fn partition(&self, json: Option<&Value>, path: &Path) -> String {
+        match self {
+            Self::Namespace => {
+                // A wrapped document nests the program under `"data"`.
+                let id = json.and_then(|json| json.pointer("/id/name").or_else(|| json.pointer("/data/id/name")));
+                let name = match id.and_then(Value::as_str) {
+                    Some(name) => name.to_string(),
+                    None => path.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+                };
+                program_namespace(&name).to_string()
+            }
+        }
+    }
+}
+
+/// Returns the namespace of the given program name, i.e. its prefix before the first underscore: `token` for
+/// `token_bridge`, and `credits` for `credits`, which has none.
+pub fn program_namespace(name: &str) -> &str {
+    name.split('_').next().unwrap_or(name)
+}
+
+/// The programs of a directory, each listed once with the paths of its copies, with `--dedup`. Two programs are
+/// copies if they have the same normalized `"program_hash"`, as given by `--normalize-before-hash`.
+#[derive(Debug, Default)]
+pub struct Catalog {
+    /// The JSON of each unique program, and the paths of its copies, in order of the first path.
+    programs: Vec<(Value, Vec<String>)>,
+    /// The position of each unique program in `programs`, by hash.
+    positions: HashMap<String, usize>,
+}
+
+impl Catalog {
+    /// Adds the given JSON of the program at the given path, returning whether it is the first copy of the program.
+    /// A program without a hash (i.e. of another kind) is its only copy.
+    pub fn insert(&mut self, path: String, json: Value) -> bool {
+        // A wrapped document nests the program under `"data"`.
+        let hash = json.pointer("/program_hash").or_else(|| json.pointer("/data/program_hash")).and_then(Value::as_str);
+        match hash.and_then(|hash| self.positions.get(hash)) {
+            Some(position) => {
+                self.programs[*position].1.push(path);
+                false
+            }
+            None => {
+                if let Some(hash) = hash {
+                    self.positions.insert(hash.to_string(), self.programs.len());
+                }
+                self.programs.push((json, vec![path]));
+                true
+            }
+        }
+    }
+
+    /// Returns the number of unique programs.
+    pub fn len(&self) -> usize {
+        self.programs.len()
+    }
+
+    /// Returns whether the catalog has no program.
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+
+    /// Returns the catalog as JSON, i.e. `[{ "files": ["a/token.aleo", "b/token.aleo"], "program": { ... } }]`.
+    pub fn to_json(&self) -> Value {
+        let programs = self.programs.iter().map(|(json, files)| json!({ "files": files, "program": json }));
+        Value::Array(programs.collect())
+    }
+}
+
+/// Returns the ratio of the given number of programs to the given number of unique programs, i.e. `4.0` if each
+/// program has four copies, or `null` if there is no program.
+pub fn dedup_ratio(total: usize, unique: usize) -> Value {
+    match unique {
+        0 => Value::Null,
+        unique => json!(total as f64 / unique as f64),
+    }
+}
+
+/// Parses the given RFC 3339 timestamp, i.e. `2023-10-15T12:00:00Z`.
+pub fn parse_rfc3339(string: &str) -> Result<SystemTime> {
+    Ok(OffsetDateTime::parse(string, &Rfc3339)?.into())
+}
+
+/// Keeps only the given paths modified after the given time, returning the number of paths skipped.
+/// A path whose modification time is unavailable is kept, so that its conversion reports the error.
+pub fn retain_modified_since(paths: &mut Vec<PathBuf>, since: SystemTime) -> usize {
+    let total = paths.len();
+    paths.retain(|path| {
+        std::fs::metadata(path).and_then(|metadata| metadata.modified()).map_or(true, |modified| modified > since)
+    });
+    total - paths.len()
+}
+
+/// Reports the progress of a batch job to stderr, one JSON object per line,
+/// i.e. `{"done":1,"total":2,"current":"main.aleo"}`.
+pub struct Progress {
+    /// The total number of files.
+    total: usize,
+    /// The time of the last update, if any.
+    last_update: Option<Instant>,
+}
+
+impl Progress {
+    /// Initializes the progress reporter for the given number of files.
+    pub const fn new(total: usize) -> Self {
+        Self { total, last_update: None }
+    }
+
+    /// Returns the progress line for the given number of files done, if it is due.
+    /// Updates are throttled, except for the first and the last one.
+    pub fn line(&mut self, done: usize, current: &Path) -> Option<String> {
+        let now = Instant::now();
+        let is_due = match self.last_update {
+            Some(last_update) => now.duration_since(last_update) >= PROGRESS_INTERVAL,
+            None => true,
+        };
+        if !is_due && done < self.total {
+            return None;
+        }
+        self.last_update = Some(now);
+        let progress = json!({ "done": done, "total": self.total, "current": current.display().to_string() });
+        Some(to_json_string(&progress, false))
+    }
+
+    /// Prints the progress line for the given number of files done to stderr, if it is due.
+    pub fn update(&mut self, done: usize, current: &Path) {
+        if let Some(line) = self.line(done, current) {
+            eprintln!("{line}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::ExitStatus;
+
+    #[test]
+    fn test_collect_programs() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::create_dir(directory.path().join("nested")).unwrap();
+        std::fs::write(directory.path().join("b.aleo"), "").unwrap();
+        std::fs::write(directory.path().join("a.aleo"), "").unwrap();
+        std::fs::write(directory.path().join("notes.txt"), "").unwrap();
+        std::fs::write(directory.path().join("nested").join("c.aleo"), "").unwrap();
+
+        let paths = collect_programs(directory.path()).unwrap();
+        let paths = paths.iter().map(|path| path.strip_prefix(directory.path()).unwrap()).collect::<Vec<_>>();
+        assert_eq!(paths, [Path::new("a.aleo"), Path::new("b.aleo"), Path::new("nested/c.aleo")]);
+    }
+
+    #[test]
+    fn test_changed_programs() {
+        // The test requires git, which may not be installed.
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let directory = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(directory.path())
+                .args(["-c", "user.name=aleo2json", "-c", "user.email=aleo2json@example.com"])
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "Failed to run 'git {}'", args.join(" "));
+        };
+        git(&["init", "--quiet"]);
+        std::fs::write(directory.path().join("a.aleo"), "a").unwrap();
+        std::fs::write(directory.path().join("deleted.aleo"), "").unwrap();
+        std::fs::write(directory.path().join("notes.txt"), "").unwrap();
+        git(&["add", "--all"]);
+        git(&["commit", "--quiet", "--message", "first"]);
+        std::fs::create_dir(directory.path().join("nested")).unwrap();
+        std::fs::write(directory.path().join("a.aleo"), "b").unwrap();
+        std::fs::write(directory.path().join("nested").join("c.aleo"), "").unwrap();
+        std::fs::write(directory.path().join("notes.txt"), "changed").unwrap();
+        std::fs::remove_file(directory.path().join("deleted.aleo")).unwrap();
+        git(&["add", "--all"]);
+        git(&["commit", "--quiet", "--message", "second"]);
+
+        // Only the programs changed in the range are listed, without the deleted one.
+        let paths = changed_programs(directory.path(), "HEAD~1..HEAD").unwrap();
+        let paths = paths.iter().map(|path| path.strip_prefix(directory.path()).unwrap()).collect::<Vec<_>>();
+        assert_eq!(paths, [Path::new("a.aleo"), Path::new("nested/c.aleo")]);
+        assert!(changed_programs(directory.path(), "HEAD..HEAD").unwrap().is_empty());
+
+        // An invalid range is an invalid argument.
+        for range in ["missing..HEAD", "--output=notes.txt"] {
+            let error = changed_programs(directory.path(), range).unwrap_err();
+            assert_eq!(ExitStatus::of(&error), ExitStatus::Usage);
+        }
+    }
+
+    #[test]
+    fn test_sample_programs() {
+        let paths = (0..20).map(|index| PathBuf::from(format!("{index:02}.aleo"))).collect::<Vec<_>>();
+
+        let sample = sample_programs(&paths, 5, 7);
+        assert_eq!(sample.len(), 5);
+        assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(sample.iter().all(|path| paths.contains(path)));
+        // The same seed yields the same sample, and a different seed (almost surely) does not.
+        assert_eq!(sample_programs(&paths, 5, 7), sample);
+        assert_ne!(sample_programs(&paths, 5, 8), sample);
+        // A sample larger than the corpus is the entire corpus.
+        assert_eq!(sample_programs(&paths, 50, 7), paths);
+    }
+
+    #[test]
+    fn test_output_path() {
+        let (directory, output_dir) = (Path::new("programs"), Path::new("out"));
+        let path = output_path(directory, Path::new("programs/nested/main.aleo"), output_dir, "json");
+        assert_eq!(path, Path::new("out/nested/main.json"));
+        let path = output_path(directory, Path::new("programs/main.aleo"), output_dir, "json.gz");
+        assert_eq!(path, Path::new("out/main.json.gz"));
+    }
+
+    #[test]
+    fn test_partition_by_namespace() {
+        assert_eq!(program_namespace("token_bridge"), "token");
+        assert_eq!(program_namespace("token_bridge_v2"), "token");
+        assert_eq!(program_namespace("credits"), "credits");
+
+        let partition = |json: Option<&Value>, path: &str| PartitionBy::Namespace.partition(json, Path::new(path));
+        let json = json!({ "id": { "type": "ProgramID", "name": "token_bridge", "network": "aleo" } });
+        assert_eq!(partition(Some(&json), "programs/bridge.aleo"), "token");
+        assert_eq!(partition(Some(&json!({ "data": json })), "programs/bridge.aleo"), "token");
+        // A program that failed to convert is partitioned by its file name.
+        assert_eq!(partition(None, "programs/swap_pool.aleo"), "swap");
+    }
+
+    #[test]
+    fn test_catalog() {
+        let program = |hash: &str| json!({ "id": { "name": "token" }, "program_hash": hash });
+        let mut catalog = Catalog::default();
+        assert!(catalog.insert("a/token.aleo".to_string(), program("1")));
+        assert!(catalog.insert("b/other.aleo".to_string(), program("2")));
+        assert!(!catalog.insert("c/token.aleo".to_string(), program("1")));
+        // A wrapped document is a copy of the same program.
+        assert!(!catalog.insert("d/token.aleo".to_string(), json!({ "data": program("1") })));
+        // A program without a hash is its only copy.
+        assert!(catalog.insert("e/value.aleo".to_string(), json!({ "type": "Value" })));
+        assert!(catalog.insert("f/value.aleo".to_string(), json!({ "type": "Value" })));
+
+        assert_eq!(catalog.len(), 4);
+        let json = catalog.to_json();
+        assert_eq!(json[0]["files"], json!(["a/token.aleo", "c/token.aleo", "d/token.aleo"]));
+        assert_eq!(json[0]["program"], program("1"));
+        assert_eq!(json[1]["files"], json!(["b/other.aleo"]));
+
+        assert_eq!(dedup_ratio(6, 4), json!(1.5));
+        assert_eq!(dedup_ratio(0, 0), Value::Null);
+    }
+
+    #[test]
+    fn test_retain_modified_since() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("main.aleo");
+        std::fs::write(&path, "").unwrap();
+        let missing = directory.path().join("missing.aleo");
+
+        let mut paths = vec![path.clone(), missing.clone()];
+        assert_eq!(retain_modified_since(&mut paths, SystemTime::UNIX_EPOCH), 0);
+        assert_eq!(paths.len(), 2);
+
+        // A file older than the given time is skipped, unlike a file that cannot be read.
+        let since = parse_rfc3339("2999-01-01T00:00:00+01:00").unwrap();
+        assert_eq!(retain_modified_since(&mut paths, since), 1);
+        assert_eq!(paths, [missing]);
+
+        assert!(parse_rfc3339("2023-10-15").is_err());
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut progress = Progress::new(3);
+        // The first update is always reported.
+        assert_eq!(progress.line(1, Path::new("a.aleo")).unwrap(), r#"{"done":1,"total":3,"current":"a.aleo"}"#);
+        // Updates in quick succession are throttled.
+        assert!(progress.line(2, Path::new("b.aleo")).is_none());
+        // The last update is always reported.
+        assert_eq!(progress.line(3, Path::new("c.aleo")).unwrap(), r#"{"done":3,"total":3,"current":"c.aleo"}"#);
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{aleo2json::hashes::sha256_hex, console::network::Network};
+use snarkvm_synthesizer_program::Program;
+
+use serde_json::{json, Value};
+use similar::TextDiff;
+
+/// Returns a unified diff from the given source to the canonical form of its program,
+/// or `None` if the source is already in canonical form.
+pub fn canonical_diff<N: Network>(path: &str, source: &str, program: &Program<N>) -> Option<String> {
+    let canonical = program.to_string();
+    // Trailing whitespace at the end of the file is not significant.
+    if source.trim_end() == canonical.trim_end() {
+        return None;
+    }
+    let diff = TextDiff::from_lines(source, &canonical)
+        .unified_diff()
+        .header(path, &format!("{path} (canonical)"))
+        .to_string();
+    Some(diff)
+}
+
+/// Bundles the canonical form of the given program with its JSON, and the SHA-256 digest of the canonical form,
+/// i.e. `{ "source": "program main.aleo;...", "json": { ... }, "hash": "sha256:..." }`.
+pub fn canonical_bundle<N: Network>(program: &Program<N>, json: Value) -> Value {
+    let source = program.to_string();
+    let hash = format!("sha256:{}", sha256_hex(&source));
+    json!({ "source": source, "json": json, "hash": hash })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_canonical_diff() {
+        let source = "program main.aleo;\n\nfunction main:\n    input r0 as u64.public;\n    add   r0 r0 into r1;\n";
+        let program = parse_program::<CurrentNetwork>(source).unwrap();
+
+        // The canonical form has no diff.
+        let canonical = program.to_string();
+        assert_eq!(canonical_diff("main.aleo", &canonical, &program), None);
+
+        let diff = canonical_diff("main.aleo", source, &program).unwrap();
+        assert!(diff.starts_with("--- main.aleo\n+++ main.aleo (canonical)\n"));
+        assert!(diff.contains("-    add   r0 r0 into r1;\n"));
+        assert!(diff.contains("+    add r0 r0 into r1;\n"));
+    }
+
+    #[test]
+    fn test_canonical_bundle() {
+        let source = "program main.aleo;\n\nfunction main:\n    input r0 as u64.public;\n    add   r0 r0 into r1;\n";
+        let program = parse_program::<CurrentNetwork>(source).unwrap();
+        let bundle = canonical_bundle(&program, program.to_json());
+        assert_eq!(bundle["source"], program.to_string());
+        assert_eq!(bundle["json"], program.to_json());
+
+        // The hash is over the canonical source, so it verifies against the source in the bundle.
+        let canonical = bundle["source"].as_str().unwrap();
+        assert_eq!(bundle["hash"], format!("sha256:{}", sha256_hex(canonical)));
+        let reparsed = parse_program::<CurrentNetwork>(canonical).unwrap();
+        assert_eq!(canonical_bundle(&reparsed, reparsed.to_json()), bundle);
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::network::Network;
+use snarkvm_synthesizer_program::Program;
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Returns the field names of each struct and record declared in the given program, in declaration order,
+/// keyed by the target of a cast to it, i.e. `point` or `token.record`. A record starts with its `owner`.
+fn cast_targets<N: Network>(program: &Program<N>) -> HashMap<String, Vec<String>> {
+    let structs = program.structs().iter().map(|(name, struct_)| {
+        (name.to_string(), struct_.members().keys().map(|member| member.to_string()).collect())
+    });
+    let records = program.records().iter().map(|(name, record)| {
+        let entries = record.entries().keys().map(|entry| entry.to_string());
+        (format!("{name}.record"), std::iter::once("owner".to_string()).chain(entries).collect())
+    });
+    structs.chain(records).collect()
+}
+
+/// Pairs each operand of a cast to a struct or record with the field it initializes under `"fields"`,
+/// i.e. `"fields": [{ "field": "amount", "operand": { ... } }]`. The operands are given in field order.
+/// Casts to other types, including external records, are left untouched.
+pub fn add_cast_fields<N: Network>(json: &mut Value, program: &Program<N>) {
+    add_fields(json, &cast_targets(program));
+}
+
+/// Adds the fields to each cast in the given JSON, whose target is among the given targets.
+fn add_fields(json: &mut Value, targets: &HashMap<String, Vec<String>>) {
+    if json["type"] == "CastOperation" {
+        let fields = json["cast_to"].as_str().and_then(|cast_to| targets.get(cast_to));
+        if let (Some(fields), Some(operands)) = (fields, json["operands"].as_array()) {
+            let fields = fields
+                .iter()
+                .zip(operands)
+                .map(|(field, operand)| json!({ "field": field, "operand": operand }))
+                .collect::<Vec<_>>();
+            json["fields"] = Value::Array(fields);
+        }
+        return;
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(|value| add_fields(value, targets)),
+        Value::Array(array) => array.iter_mut().for_each(|value| add_fields(value, targets)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_cast_fields() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+
+program fields.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function main:
+    input r0 as u64.private;
+    input r1 as credits.aleo/credits.record;
+    cast 1field 2field into r2 as point;
+    cast self.caller r0 into r3 as token.record;
+    cast r1.owner r0 into r4 as credits.aleo/credits.record;
+    cast r0 into r5 as u64;
+    output r3 as token.record;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_cast_fields(&mut json, &program);
+        let instructions = &json["functions"]["main"]["instructions"];
+        let fields = |index: usize| {
+            let fields = instructions[index]["value"]["fields"].as_array().unwrap();
+            fields.iter().map(|field| (field["field"].clone(), field["operand"]["str"].clone())).collect::<Vec<_>>()
+        };
+        assert_eq!(fields(0), [(json!("x"), json!("1field")), (json!("y"), json!("2field"))]);
+        assert_eq!(fields(1), [(json!("owner"), json!("self.caller")), (json!("amount"), json!("r0"))]);
+        // External records, and other types, cannot be resolved.
+        assert!(instructions[2]["value"].get("fields").is_none());
+        assert!(instructions[3]["value"].get("fields").is_none());
+    }
+}
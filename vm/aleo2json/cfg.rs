@@ -0,0 +1,208 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{aleo2json::ControlFlow, console::network::Network};
+use snarkvm_synthesizer_program::{Command, Program};
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The format of the control-flow graphs of `--cfg`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum CfgFormat {
+    /// Emits the graphs as JSON, i.e. `{ "nodes": [...], "edges": [...] }`.
+    #[default]
+    Json,
+    /// Emits the graphs in the DOT language of Graphviz, one `digraph` per function.
+    Dot,
+}
+
+/// Returns the control-flow graph of the given finalize commands, i.e.
+/// `{ "nodes": [{ "id": 0, "start": 0, "end": 2, "commands": [...] }], "edges": [{ "from": 0, "to": 1, "kind":
+/// "branch" }] }`. Each node is a basic block, from its `start` command to its `end` command, excluded. A block
+/// starts at the first command, at each position, and after each branch. A block ending in a branch has a `branch`
+/// edge to the block of its position, and each block but the last a `fallthrough` edge to the next one.
+fn finalize_cfg<N: Network>(commands: &[Command<N>]) -> Value {
+    let mut starts = vec![0];
+    for (index, command) in commands.iter().enumerate() {
+        match ControlFlow::of(command) {
+            Some(ControlFlow::Position(_)) => starts.push(index),
+            Some(ControlFlow::Branch(_)) => starts.push(index + 1),
+            None => (),
+        }
+    }
+    // A branch may be the last command, and an empty finalize logic is a single empty block.
+    starts.retain(|start| *start == 0 || *start < commands.len());
+    starts.dedup();
+
+    // The block starting at each position, by name.
+    let blocks = starts.iter().enumerate().filter_map(|(block, start)| {
+        let position = commands.get(*start).and_then(ControlFlow::of);
+        match position {
+            Some(ControlFlow::Position(name)) => Some((name.to_string(), block)),
+            _ => None,
+        }
+    });
+    let blocks = blocks.collect::<HashMap<_, _>>();
+
+    let (mut nodes, mut edges) = (Vec::new(), Vec::new());
+    for (block, start) in starts.iter().enumerate() {
+        let end = starts.get(block + 1).copied().unwrap_or(commands.len());
+        let strings = commands[*start..end].iter().map(ToString::to_string).collect::<Vec<_>>();
+        nodes.push(json!({ "id": block, "start": start, "end": end, "commands": strings }));
+        let last = end.checked_sub(1).and_then(|last| ControlFlow::of(&commands[last]));
+        if let Some(ControlFlow::Branch(position)) = last {
+            edges.push(json!({ "from": block, "to": blocks.get(&position.to_string()), "kind": "branch" }));
+        }
+        if block + 1 < starts.len() {
+            edges.push(json!({ "from": block, "to": block + 1, "kind": "fallthrough" }));
+        }
+    }
+    json!({ "nodes": nodes, "edges": edges })
+}
+
+/// Returns the control-flow graph of the finalize logic of each function of the given program that has one, as
+/// in `finalize_cfg`, i.e. `{ "type": "Cfg", "program": "token.aleo", "functions": { "mint": { "nodes": [...],
+/// "edges": [...] } } }`. A finalize logic without any branch is a single node.
+pub fn program_cfg<N: Network>(program: &Program<N>) -> Value {
+    let functions = program.functions().iter().filter_map(|(name, function)| {
+        let finalize = function.finalize_logic()?;
+        Some((name.to_string(), finalize_cfg(finalize.commands())))
+    });
+    json!({
+        "type": "Cfg",
+        "program": program.id().to_string(),
+        "functions": functions.collect::<serde_json::Map<_, _>>(),
+    })
+}
+
+/// Escapes the given string to be quoted in the DOT language.
+fn dot_escape(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Appends the DOT form of the given control-flow graphs, as returned by `program_cfg`, or of those of each program
+/// of a directory, under `"files"`, to the given string.
+fn write_dot(dot: &mut String, json: &Value) {
+    if let Some(files) = json["files"].as_object() {
+        files.values().for_each(|json| write_dot(dot, json));
+        return;
+    }
+    let program = json["program"].as_str().unwrap_or_default();
+    for (name, cfg) in json["functions"].as_object().into_iter().flatten() {
+        dot.push_str(&format!("digraph \"{}/{}\" {{\n", dot_escape(program), dot_escape(name)));
+        for node in cfg["nodes"].as_array().into_iter().flatten() {
+            // Each command is a left-justified line of the label.
+            let commands = node["commands"].as_array().into_iter().flatten().filter_map(Value::as_str);
+            let label = commands.map(|command| format!("{}\\l", dot_escape(command))).collect::<String>();
+            dot.push_str(&format!("  n{} [shape=box, label=\"{label}\"];\n", node["id"]));
+        }
+        for edge in cfg["edges"].as_array().into_iter().flatten() {
+            let kind = edge["kind"].as_str().unwrap_or_default();
+            dot.push_str(&format!("  n{} -> n{} [label=\"{}\"];\n", edge["from"], edge["to"], dot_escape(kind)));
+        }
+        dot.push_str("}\n");
+    }
+}
+
+/// Returns the DOT form of the given control-flow graphs, as returned by `program_cfg`, with one `digraph` per
+/// function, named after its program and itself, i.e. `digraph "token.aleo/mint"`.
+pub fn cfg_to_dot(json: &Value) -> String {
+    let mut dot = String::new();
+    write_dot(&mut dot, json);
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    const PROGRAM: &str = r"
+program flow.aleo;
+
+mapping counts:
+    key as u64.public;
+    value as u64.public;
+
+function count:
+    input r0 as u64.public;
+    async count r0 into r1;
+    output r1 as flow.aleo/count.future;
+
+finalize count:
+    input r0 as u64.public;
+    branch.eq r0 0u64 to end;
+    set r0 into counts[r0];
+    position end;
+    remove counts[0u64];
+
+function straight:
+    input r0 as u64.public;
+    async straight r0 into r1;
+    output r1 as flow.aleo/straight.future;
+
+finalize straight:
+    input r0 as u64.public;
+    set r0 into counts[r0];
+    remove counts[r0];
+
+function plain:
+    input r0 as u64.public;
+";
+
+    #[test]
+    fn test_program_cfg() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let cfg = program_cfg(&program);
+        assert_eq!(cfg["program"], "flow.aleo");
+        // A function without a finalize logic has no graph.
+        assert!(cfg["functions"].get("plain").is_none());
+
+        let count = &cfg["functions"]["count"];
+        let ranges = count["nodes"].as_array().unwrap().iter().map(|node| (node["start"].clone(), node["end"].clone()));
+        assert_eq!(ranges.collect::<Vec<_>>(), [(json!(0), json!(1)), (json!(1), json!(2)), (json!(2), json!(4))]);
+        assert_eq!(count["nodes"][2]["commands"], json!(["position end;", "remove counts[0u64];"]));
+        assert_eq!(
+            count["edges"],
+            json!([
+                { "from": 0, "to": 2, "kind": "branch" },
+                { "from": 0, "to": 1, "kind": "fallthrough" },
+                { "from": 1, "to": 2, "kind": "fallthrough" },
+            ])
+        );
+
+        // A finalize logic without any branch is a single node.
+        let straight = &cfg["functions"]["straight"];
+        assert_eq!(straight["nodes"].as_array().unwrap().len(), 1);
+        assert_eq!(straight["nodes"][0]["end"], 2);
+        assert_eq!(straight["edges"], json!([]));
+    }
+
+    #[test]
+    fn test_cfg_to_dot() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let dot = cfg_to_dot(&program_cfg(&program));
+        assert!(dot.starts_with("digraph \"flow.aleo/count\" {\n"));
+        assert!(dot.contains("  n0 [shape=box, label=\"branch.eq r0 0u64 to end;\\l\"];\n"));
+        assert!(dot.contains("  n0 -> n2 [label=\"branch\"];\n"));
+        assert!(dot.contains("digraph \"flow.aleo/straight\" {\n  n0 [shape=box"));
+        assert_eq!(dot.matches("digraph").count(), 2);
+
+        // Each program of a directory is rendered in turn.
+        let files = json!({ "files": { "a.aleo": program_cfg(&program), "b.aleo": { "error": "..." } } });
+        assert_eq!(cfg_to_dot(&files), dot);
+    }
+}
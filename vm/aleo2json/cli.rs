@@ -0,0 +1,1542 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        add_encoded_fields,
+        add_network,
+        add_parser_boundary,
+        add_schema_version,
+        canonical_diff,
+        cfg_to_dot,
+        changed_programs,
+        check_record_depth,
+        check_renames,
+        check_sections,
+        check_value_depth,
+        collect_programs,
+        colorize_json,
+        compare_programs,
+        convert_abi,
+        convert_program_bytes,
+        count_rule_violations,
+        count_violations,
+        dedup_ratio,
+        error_to_json,
+        execution_to_json,
+        explain_opcode,
+        fetch_program_source,
+        first_unsorted_object,
+        insert_into_index,
+        json_pointers,
+        labeled_differences,
+        merge_annotations,
+        output_path,
+        parse_block_deployments,
+        parse_config_enum,
+        parse_deployment,
+        parse_program,
+        parse_program_lenient,
+        parse_programs,
+        parse_rfc3339,
+        parse_with_timeout,
+        partial_program_to_json,
+        program_source_to_json,
+        program_to_json,
+        read_bytes,
+        read_input_with,
+        record_to_json,
+        render_diagnostic,
+        render_template,
+        repl,
+        retain_modified_since,
+        sample_programs,
+        serve,
+        sort_keys,
+        stats_csv,
+        transition_to_json,
+        validate_source,
+        value_to_json,
+        verify_sha256,
+        walk_program,
+        watch_file,
+        wrap_document,
+        write_gzip,
+        Aleo2JsonError,
+        Catalog,
+        CfgFormat,
+        ColorChoice,
+        Config,
+        ConvertOptions,
+        ErrorFormat,
+        Failure,
+        Failures,
+        FieldRepr,
+        Framing,
+        InputEncoding,
+        InputKind,
+        NetworkName,
+        OperandFormat,
+        OutputFormat,
+        PartitionBy,
+        Phase,
+        Profile,
+        Progress,
+        Redactor,
+        DANGLING_REFERENCE,
+        DEFAULT_MAX_DEPTH,
+        DEFAULT_MAX_SIZE,
+        DEFAULT_REDACT_TOKEN,
+        TS_TYPES,
+    },
+    console::{
+        network::{Network, Testnet3},
+        program::{Plaintext, Record},
+    },
+};
+use snarkvm_ledger_block::{Deployment, Execution, Transition};
+use snarkvm_synthesizer_program::Program;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{parser::ValueSource, ArgMatches, Parser, ValueEnum};
+use serde_json::json;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// The documentation of the exit codes, as given by `ExitStatus`.
+const EXIT_CODES_HELP: &str = "Exit codes:
+  0  The conversion succeeded
+  1  The input failed to parse, or to convert
+  2  The input could not be read, or the output written
+  3  A check of the input or the output failed, i.e. --expect-sha256 or --verify-sorted
+  4  The arguments are invalid
+When several programs of a directory fail, the highest exit code applies.";
+
+#[derive(Debug, Parser)]
+#[clap(name = "aleo2json", author = "The Aleo Team <hello@aleo.org>", after_help = EXIT_CODES_HELP)]
+pub struct Aleo2Json {
+    /// The path to the input.
+    #[clap(required_unless_present_any = ["explain", "serve", "repl", "emit_ts_types", "compare_json"])]
+    pub path: Option<PathBuf>,
+    /// The kind of object to convert.
+    #[clap(long, value_enum, default_value_t = InputKind::Program)]
+    pub kind: InputKind,
+    /// Emits the metadata of the deployment under `"deployment"`, i.e. its edition and fee.
+    #[clap(long)]
+    pub deployment_metadata: bool,
+    /// Types the value of `--kind value` with the structs and records of the given program, i.e. resolving
+    /// `{ x: 1u8, y: 2u8 }` to the struct `point`. Without it, a struct is untyped, as it cannot be told apart.
+    #[clap(long, value_name = "PROGRAM")]
+    pub type_context: Option<PathBuf>,
+    /// The network to parse the input for.
+    #[clap(long, value_enum, env = "ALEO2JSON_NETWORK", default_value_t = NetworkName::Testnet3)]
+    pub network: NetworkName,
+    /// Converts the input for each supported network instead, by network, i.e. `{ "testnet3": { ... } }`, to check
+    /// that a program converts alike for each. A network the input fails to convert for is `{ "error": "..." }`.
+    #[clap(long)]
+    pub all_networks: bool,
+    /// Emits the registers read and written by each instruction, instead of the program.
+    #[clap(long)]
+    pub dataflow: bool,
+    /// Emits the control-flow graph of the finalize logic of each function, instead of the program, with a node per
+    /// basic block, and an edge per branch and fallthrough, as JSON, or as DOT with `--cfg dot`.
+    #[clap(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "json",
+        conflicts_with_all = [
+            "dataflow", "flatten", "grep", "jsonl_index", "template", "summary_csv", "max_functions_parallel"
+        ]
+    )]
+    pub cfg: Option<CfgFormat>,
+    /// Emits a table with one row per instruction and finalize command, instead of the program.
+    #[clap(long, conflicts_with = "dataflow")]
+    pub flatten: bool,
+    /// Emits the rows of the instruction table whose opcode and operands (i.e. `add r0 1u64`) match the given regex.
+    #[clap(long, value_name = "REGEX", conflicts_with = "dataflow")]
+    pub grep: Option<String>,
+    /// Emits each function and closure instruction on a line of its own, keyed by its UID, instead of the program,
+    /// i.e. `{"mint#0":{"type":"Instruction",...}}`, to load into a key-value store or a search index.
+    /// Each line is compact JSON, whatever `--format` and `--pretty` are.
+    #[clap(
+        long,
+        conflicts_with_all = ["dataflow", "flatten", "grep", "multi", "canonicalize", "rename_key", "wrap"]
+    )]
+    pub jsonl_index: bool,
+    /// Emits only the rows of the given function, with `--grep`.
+    #[clap(long, value_name = "NAME", requires = "grep")]
+    pub function: Option<String>,
+    /// Emits each literal as its value and its type, i.e. `{ "value": "42", "type": "u64" }`.
+    #[clap(long)]
+    pub split_literals: bool,
+    /// Emits each field and group literal in decimal and hex forms, i.e. `{ "dec": "42", "hex": "0x2a" }`.
+    /// This includes the nonce of a record.
+    #[clap(long)]
+    pub hex_fields: bool,
+    /// Emits each group literal as its affine coordinates, i.e. `{ "x": "0", "y": "1" }`, instead of its x-coordinate.
+    #[clap(long, conflicts_with_all = ["hex_fields", "field_repr"])]
+    pub expand_group: bool,
+    /// Emits each field and scalar literal as big-endian hex, zero-padded to the size in bytes of its type, i.e.
+    /// `0x00...2a`, instead of the shortest decimal string, so that outputs align and compare byte by byte.
+    #[clap(long, conflicts_with_all = ["hex_fields", "field_repr"])]
+    pub fixed_width_fields: bool,
+    /// The representation of field elements, and of group x-coordinates, in field and group literals.
+    #[clap(long, value_enum, default_value_t = FieldRepr::Canonical, conflicts_with = "hex_fields")]
+    pub field_repr: FieldRepr,
+    /// Emits each boolean and `u8` to `u32` or `i8` to `i32` literal as a native JSON boolean or number, i.e. `42`.
+    /// Wider integers (i.e. `u64`), fields, groups, and scalars stay strings, to avoid a loss of precision.
+    #[clap(long)]
+    pub native_literals: bool,
+    /// Marks each instruction operating only on constant inputs and literals with `"const": true`.
+    #[clap(long)]
+    pub annotate_const: bool,
+    /// The serialization format of the output.
+    #[clap(long, value_enum, env = "ALEO2JSON_FORMAT", default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
+    /// Indents the JSON output.
+    #[clap(long, env = "ALEO2JSON_PRETTY")]
+    pub pretty: bool,
+    /// Indents the JSON output, rendering arrays of scalars (i.e. operands) on a single line.
+    #[clap(long)]
+    pub pretty_arrays_inline: bool,
+    /// Indents the JSON output if its compact form is shorter than the given number of bytes, and compacts it
+    /// otherwise, so that small outputs are readable, and large ones small.
+    #[clap(
+        long,
+        value_name = "BYTES",
+        conflicts_with_all = ["pretty", "pretty_arrays_inline", "max_functions_parallel"]
+    )]
+    pub pretty_threshold: Option<usize>,
+    /// Escapes each non-ASCII character in the JSON output as `\uXXXX`, so that the output is pure ASCII.
+    #[clap(long)]
+    pub ascii: bool,
+    /// When to colorize the error diagnostics.
+    #[clap(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+    /// Highlights the keys, strings, and numbers of the JSON output on a terminal, as given by `--color`.
+    /// An output piped or written to a file is left plain.
+    #[clap(long)]
+    pub color_json: bool,
+    /// The number of columns a tab advances to, when locating errors. A width of 1 counts a tab as one column.
+    #[clap(long, value_name = "N", default_value_t = 1)]
+    pub tab_width: usize,
+    /// The format of the error output. With `json`, a failure is also written to the output as JSON.
+    #[clap(long, value_enum, default_value_t = ErrorFormat::Text)]
+    pub error_format: ErrorFormat,
+    /// Writes the output to the given file, instead of stdout.
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+    /// Compresses the output with gzip, before writing it to the output file, or to stdout.
+    #[clap(long)]
+    pub output_gzip: bool,
+    /// Writes the output of each program in a directory to its own file in the given directory,
+    /// mirroring the input tree, i.e. `<DIR>/nested/main.json`.
+    #[clap(long, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+    /// Groups the programs of a directory by the given key, listing the files of each partition under
+    /// `"partitions"`, i.e. `{ "token": ["programs/token_bridge.aleo", ...] }`, and, with `--output-dir`, writing
+    /// the output of each to the sub-directory of its partition, i.e. `<DIR>/token/token_bridge.json`.
+    /// The namespace of a program is the prefix of its name before the first underscore, or its whole name.
+    #[clap(long, value_enum, value_name = "KEY", conflicts_with = "only_errors")]
+    pub partition_by: Option<PartitionBy>,
+    /// Re-converts the program each time it is saved, until interrupted.
+    #[clap(long)]
+    pub watch: bool,
+    /// Converts each program source read from stdin, writing one JSON response per line to stdout, until EOF.
+    #[clap(long, conflicts_with_all = ["watch", "output", "output_gzip"])]
+    pub serve: bool,
+    /// The framing of the program sources read by `--serve`.
+    #[clap(long, value_enum, default_value_t = Framing::Lines, requires = "serve")]
+    pub framing: Framing,
+    /// Prompts for a program on stdin, ending at a blank line or EOF, and prints its JSON, until EOF.
+    #[clap(long, conflicts_with_all = ["watch", "serve", "output", "output_gzip"])]
+    pub repl: bool,
+    /// The encoding of the input files. An input that is not valid UTF-8 is an error, unless another one is given.
+    #[clap(long, value_enum, default_value_t = InputEncoding::Utf8)]
+    pub input_encoding: InputEncoding,
+    /// Refuses inputs larger than the given number of bytes.
+    #[clap(long, default_value_t = DEFAULT_MAX_SIZE)]
+    pub max_size: u64,
+    /// Refuses types and values nested deeper than the given number of levels, i.e. a struct in a struct in a struct.
+    #[clap(long, value_name = "N", default_value_t = DEFAULT_MAX_DEPTH)]
+    pub max_depth: usize,
+    /// Checks that the program is in canonical form, printing a diff to stderr and failing if it is not.
+    #[clap(long)]
+    pub strict_whitespace: bool,
+    /// Fails to convert a program if parsing it takes longer than the given milliseconds (unlimited by default).
+    #[clap(long, value_name = "MS")]
+    pub timeout: Option<u64>,
+    /// Converts each of the programs concatenated in the input (i.e. separated by blank lines) into a JSON array.
+    #[clap(long, conflicts_with = "strict_whitespace")]
+    pub multi: bool,
+    /// Reads the input as a program serialized to bytes, i.e. as stored in a deployment, rather than as its source.
+    /// An input that is truncated, or has trailing bytes, is invalid.
+    #[clap(
+        long,
+        conflicts_with_all = [
+            "kind", "multi", "all_networks", "input_encoding", "watch", "serve", "repl", "index", "with_raw_operands",
+            "with_lines"
+        ]
+    )]
+    pub from_bytes: bool,
+    /// Emits the number of bytes of the program consumed by the parser under `"parser_consumed"`, and the rest of
+    /// the input under `"parser_remainder"`, i.e. after the part `--lenient` parsed. On a failure to consume all of
+    /// the input, prints them to stderr instead.
+    #[clap(long, conflicts_with_all = ["multi", "from_bytes"])]
+    pub debug_parse: bool,
+    /// Converts as much of a program with a syntax error as the parser can, i.e. its header and its components
+    /// before the invalid one, with the error at which the parser stopped under `"parse_error"`, instead of failing.
+    #[clap(long, conflicts_with_all = ["multi", "from_bytes", "strict_whitespace"])]
+    pub lenient: bool,
+    /// Prints the description and the operand signature of the given opcode, instead of converting.
+    #[clap(long, value_name = "OPCODE")]
+    pub explain: Option<String>,
+    /// Prints the TypeScript declarations of the JSON of a program, instead of converting.
+    #[clap(long)]
+    pub emit_ts_types: bool,
+    /// Fails, before parsing, if the SHA-256 digest of the input does not match the given hex digest.
+    #[clap(long, value_name = "HEX")]
+    pub expect_sha256: Option<String>,
+    /// Emits only the first given number of functions, in declaration order.
+    #[clap(long, value_name = "N")]
+    pub limit_functions: Option<usize>,
+    /// Embeds the JSON of each imported program under `"resolved_imports"`, resolved from `--import-dir`.
+    #[clap(long, requires = "import_dir")]
+    pub inline_imports: bool,
+    /// Attaches the annotations of the given JSON file to the JSON of the input file under `"annotations"`, each keyed
+    /// by the UID of an instruction (i.e. `mint#0`) or the JSON pointer of a node (i.e. `/functions/mint`).
+    /// A key matching no node is a warning. A directory is not annotated.
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["watch", "serve", "repl", "index"])]
+    pub merge: Option<PathBuf>,
+    /// The directory to resolve imported programs from, i.e. `imports/token.aleo`.
+    #[clap(long, value_name = "DIR")]
+    pub import_dir: Option<PathBuf>,
+    /// Validates the program, emitting the violations found under `"violations"`. An invalid literal in the source,
+    /// i.e. an address with an invalid checksum, or `300u8`, fails the conversion at its location instead, before
+    /// parsing.
+    #[clap(long)]
+    pub validate: bool,
+    /// Fails if the validation finds any violation, after emitting the output, as `-D warnings` does in rustc.
+    /// Without it, the violations are informational.
+    #[clap(long, requires = "validate", conflicts_with_all = ["watch", "serve", "repl", "index", "all_networks"])]
+    pub deny_warnings: bool,
+    /// Checks that each name the program references, i.e. a struct, a record, a closure, a function, or a mapping,
+    /// is defined in it or in one of its declared imports, emitting each dangling reference under `"violations"`.
+    /// Fails if any is found, after emitting the output.
+    #[clap(long, conflicts_with_all = ["watch", "serve", "repl", "index", "all_networks"])]
+    pub validate_closed: bool,
+    /// Reports the time spent parsing, building the JSON, and serializing it to stderr, and the slowest files
+    /// of a directory. The output is unchanged.
+    #[clap(long, conflicts_with_all = ["watch", "serve", "repl", "index"])]
+    pub profile: bool,
+    /// The time spent in each phase of the conversion, with `--profile`.
+    #[clap(skip)]
+    profiler: RefCell<Profile>,
+    /// The source of the input file, as read with `--input-encoding`, to locate a conversion error in.
+    #[clap(skip)]
+    source: RefCell<Option<String>>,
+    /// Reports the progress of a directory conversion to stderr, one JSON object per line.
+    #[clap(long)]
+    pub progress: bool,
+    /// Stops a directory conversion at the first program that fails to convert.
+    #[clap(long, conflicts_with = "keep_going")]
+    pub fail_fast: bool,
+    /// Records each program that fails to convert, and continues the directory conversion (the default).
+    #[clap(long)]
+    pub keep_going: bool,
+    /// Emits only the programs in the directory that failed to convert, as `[{ "file": "...", "error": { ... } }]`.
+    #[clap(long, conflicts_with = "output_dir")]
+    pub only_errors: bool,
+    /// Converts only a random sample of the given number of programs in the directory.
+    #[clap(long, value_name = "N")]
+    pub sample: Option<usize>,
+    /// The seed of the random sample, so that the same seed selects the same programs.
+    #[clap(long, value_name = "SEED", default_value_t = 0, requires = "sample")]
+    pub seed: u64,
+    /// Converts only the programs in the directory modified after the given RFC 3339 timestamp.
+    #[clap(long, value_name = "TIMESTAMP", value_parser = parse_rfc3339)]
+    pub since: Option<SystemTime>,
+    /// Converts only the programs in the directory changed in the given git revision range, i.e. `main..HEAD`, as
+    /// listed by `git diff --name-only`. The directory must be in a git repository.
+    #[clap(long, value_name = "RANGE", conflicts_with_all = ["watch", "serve", "repl", "index", "from_bytes"])]
+    pub git_diff: Option<String>,
+    /// Emits each unique program in the directory once, with the paths of its copies, under `"programs"`, i.e.
+    /// `[{ "files": ["a/token.aleo", "b/token.aleo"], "program": { ... } }]`, and the ratio of programs to unique
+    /// programs under `"dedup_ratio"` in the summary. Copies have the same normalized hash, so this implies
+    /// `--with-hashes` and `--normalize-before-hash`.
+    #[clap(
+        long,
+        conflicts_with_all = [
+            "watch", "index", "output_dir", "only_errors", "partition_by", "all_networks", "dataflow", "cfg"
+        ]
+    )]
+    pub dedup: bool,
+    /// Fails on any opcode unknown to `--explain`, instead of tagging it as `"opcode": "unknown:<opcode>"`.
+    #[clap(long)]
+    pub deny_unknown_opcodes: bool,
+    /// Emits the statistics of the program under `"stats"`, i.e. the number of functions and instructions.
+    #[clap(long)]
+    pub stats: bool,
+    /// Includes the statistics of each function in the statistics.
+    #[clap(long, requires = "stats")]
+    pub per_function: bool,
+    /// Emits the statistics of each program as CSV instead, with a header row, and one row per program.
+    #[clap(
+        long,
+        requires = "stats",
+        conflicts_with_all = [
+            "watch", "serve", "repl", "index", "template", "output_dir", "only_errors", "per_function"
+        ]
+    )]
+    pub summary_csv: bool,
+    /// Emits the JSON pointer of each node of the output instead, as an array, i.e. `["", "/id", "/id/name", ...]`,
+    /// including each object key and each array index, to navigate the output without walking it.
+    #[clap(
+        long,
+        conflicts_with_all = [
+            "watch", "serve", "repl", "index", "template", "summary_csv", "cfg", "max_functions_parallel"
+        ]
+    )]
+    pub json_pointer_list: bool,
+    /// Emits only the given top-level section (i.e. `functions`), and `"id"`. May be repeated.
+    #[clap(long, value_name = "SECTION")]
+    pub only: Vec<String>,
+    /// Omits each top-level section that is empty (i.e. `"structs": {}`), instead of always emitting every section.
+    /// Conflicts with `--only`, whose sections are always emitted.
+    #[clap(long, conflicts_with = "only")]
+    pub prune_empty: bool,
+    /// Renames a top-level section (i.e. `functions=procedures`) in the output. May be repeated.
+    #[clap(long, value_name = "FROM=TO")]
+    pub rename_key: Vec<String>,
+    /// Emits the SHA-256 digest of the canonical program, and of each function body under `"body_hash"`.
+    #[clap(long)]
+    pub with_hashes: bool,
+    /// Emits a single stable identifier of the program under `"fingerprint"`, i.e. the hex-encoded SHA-256 digest of
+    /// its ID, its network, and the SHA-256 digest of the canonical program, each on a line of its own, i.e.
+    /// `sha256("token.aleo\ntestnet3\n<program hash>")`. The program hash is not normalized.
+    #[clap(long)]
+    pub with_fingerprint: bool,
+    /// Inlines the structs referenced by the key and the value of each mapping under `"expanded"`.
+    #[clap(long)]
+    pub expand_types: bool,
+    /// Renumbers the registers of each function and closure, and sorts the definitions, before hashing, so that
+    /// programs differing only in these have the same hashes. The plain hashes are of the canonical source.
+    #[clap(long, requires = "with_hashes")]
+    pub normalize_before_hash: bool,
+    /// Emits a rough estimate of the proving cost of each function and closure under `"cost_estimate"`, for ranking.
+    #[clap(long)]
+    pub with_cost: bool,
+    /// Emits the length in bytes of the binary serialization of each function and closure, and of the program,
+    /// under `"bytes_len"`, i.e. the space each takes on chain.
+    #[clap(long)]
+    pub with_byte_sizes: bool,
+    /// Emits the number of field elements encoding each data entry of a record under `"encoded_fields"`, with
+    /// `--kind record`, i.e. the size of its ciphertext, if it is private.
+    #[clap(long)]
+    pub with_encoding: bool,
+    /// Emits the bit offset and the bit width of each struct member and record entry in their bit encoding under
+    /// `"layout"`. A string makes the width, and the offsets after it, `null`.
+    #[clap(long)]
+    pub with_layout: bool,
+    /// Emits the source text of each operand of each function and closure instruction under `"raw"`.
+    #[clap(long, conflicts_with = "multi")]
+    pub with_raw_operands: bool,
+    /// Rewrites each instruction in its canonical, fully explicit form before converting the program, so that
+    /// equivalent spellings have the same output: `gt a b` is written `lt b a`, `gte a b` is written `lte b a`,
+    /// `double a` is written `add a a`, `square a` is written `mul a a`, and a program ID operand, i.e. `token.aleo`,
+    /// is written as its address. The `"str"` of each operand is that of its desugared form, i.e. the address.
+    #[clap(long, conflicts_with = "with_raw_operands")]
+    pub desugar: bool,
+    /// Emits each operand as a tagged object, i.e. `{ "type": "Operand", "kind": "register", ..., "str": "r0" }`.
+    /// This is the default.
+    #[clap(long)]
+    pub operands_as_objects: bool,
+    /// Emits each operand as printed in the program, i.e. `"r0"`, rather than as a tagged object. The features
+    /// annotating operands need the objects, i.e. the access paths, `--with-raw-operands`, `--split-literals`,
+    /// `--hex-fields`, `--native-literals`, `--expand-group`, `--fixed-width-fields`, and `--field-repr`.
+    #[clap(
+        long,
+        conflicts_with_all = [
+            "operands_as_objects", "with_raw_operands", "split_literals", "hex_fields", "native_literals",
+            "expand_group", "fixed_width_fields", "field_repr"
+        ]
+    )]
+    pub operands_as_strings: bool,
+    /// Emits the 1-based source line of each function and closure instruction under `"line"`.
+    #[clap(long, conflicts_with = "multi")]
+    pub with_lines: bool,
+    /// Emits the JSON pointer (RFC 6901) of each definition, function, closure, and instruction to itself under
+    /// `"_ptr"`, so that external annotations can reference it.
+    #[clap(long, conflicts_with_all = ["rename_key", "canonicalize", "dataflow"])]
+    pub with_pointers: bool,
+    /// Replaces each private value (i.e. of a record entry or a transition input) with `--redact-token`.
+    #[clap(long)]
+    pub redact_private: bool,
+    /// The token to replace private values with.
+    #[clap(long, value_name = "TOKEN", default_value = DEFAULT_REDACT_TOKEN, requires = "redact_private")]
+    pub redact_token: String,
+    /// Emits the canonical source of the program, its JSON, and the SHA-256 digest of the source, in one object.
+    #[clap(long, conflicts_with_all = ["flatten", "grep"])]
+    pub canonicalize: bool,
+    /// Emits the version of the structure of the output under `"schema_version"`, which is bumped on breaking changes.
+    #[clap(long)]
+    pub with_meta: bool,
+    /// Emits the interface of each program in the input directory, by program ID, instead of the programs, i.e.
+    /// `{ "token.aleo": { "functions": { ... } } }`. A program ID seen twice with different interfaces is a warning.
+    #[clap(long, conflicts_with_all = ["watch", "output_dir", "only_errors", "dataflow", "flatten", "grep"])]
+    pub index: bool,
+    /// Renders the given Tera template against the JSON of the input, i.e. `{{ id.name }}`, instead of emitting it.
+    #[clap(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["watch", "serve", "repl", "index", "output_dir", "color_json"]
+    )]
+    pub template: Option<PathBuf>,
+    /// Serializes the functions of a program at most the given number at a time, writing each chunk before the next,
+    /// and dropping each function once serialized, to bound the memory of the output of a huge program.
+    /// The output is the same.
+    #[clap(
+        long,
+        value_name = "COUNT",
+        conflicts_with_all = [
+            "watch", "serve", "repl", "index", "template", "output_gzip", "color_json", "verify_sorted"
+        ]
+    )]
+    pub max_functions_parallel: Option<usize>,
+    /// Sorts the keys of every object in the output, so that it does not depend on the order of the declarations.
+    #[clap(long, conflicts_with_all = ["serve", "repl"])]
+    pub sort_keys: bool,
+    /// Checks that the keys of every object in the output of `--sort-keys` are in sorted order, failing otherwise.
+    #[clap(long, requires = "sort_keys", conflicts_with = "watch")]
+    pub verify_sorted: bool,
+    /// Nests each converted document under `"data"`, in an envelope with its type and version, and the tool.
+    #[clap(long)]
+    pub wrap: bool,
+    /// Compares the program with the given deployed program, fetched from `--endpoint`, failing on any difference.
+    #[clap(long, value_name = "PROGRAM_ID", requires = "endpoint")]
+    pub compare_onchain: Option<String>,
+    /// Compares the two given JSON files, i.e. archived outputs, instead of converting the input, emitting their
+    /// structural differences as with `--compare-onchain`, and failing on any difference. The order of the keys of
+    /// an object is not a difference.
+    #[clap(
+        long,
+        num_args = 2,
+        value_names = ["A", "B"],
+        conflicts_with_all = ["compare_onchain", "watch", "serve", "repl", "index"]
+    )]
+    pub compare_json: Vec<PathBuf>,
+    /// The endpoint to fetch deployed programs from, i.e. `https://api.explorer.aleo.org/v1`.
+    #[clap(long, value_name = "URL")]
+    pub endpoint: Option<String>,
+    /// The config file to read the defaults of the flags from, instead of `aleo2json.toml` in the current directory.
+    /// A flag overrides its environment variable (i.e. `ALEO2JSON_NETWORK`), which overrides the config file.
+    #[clap(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+}
+
+impl Aleo2Json {
+    /// Applies the defaults of the config file (i.e. `aleo2json.toml`) to each flag that was neither given on the
+    /// command line nor through its environment variable, according to the given matches of the arguments.
+    pub fn apply_config(&mut self, matches: &ArgMatches) -> Result<()> {
+        let config = Config::discover(self.config.as_deref())?;
+        let is_default = |id: &str| matches!(matches.value_source(id), None | Some(ValueSource::DefaultValue));
+        macro_rules! apply {
+            ($field:ident) => {
+                if let Some(value) = config.$field {
+                    if is_default(stringify!($field)) {
+                        self.$field = value;
+                    }
+                }
+            };
+            ($field:ident, $key:literal) => {
+                if let Some(value) = &config.$field {
+                    if is_default(stringify!($field)) {
+                        self.$field = parse_config_enum($key, value)?;
+                    }
+                }
+            };
+        }
+        apply!(network, "network");
+        apply!(format, "format");
+        apply!(pretty);
+        apply!(pretty_arrays_inline);
+        apply!(ascii);
+        apply!(split_literals);
+        apply!(hex_fields);
+        apply!(native_literals);
+        apply!(with_hashes);
+        apply!(with_meta);
+        apply!(validate);
+        apply!(stats);
+        apply!(max_depth);
+        apply!(max_size);
+        Ok(())
+    }
+
+    /// Converts the input, and writes the output.
+    pub fn run(self) -> Result<()> {
+        if let Some(opcode) = &self.explain {
+            return match explain_opcode(opcode) {
+                Some(info) => self.write(&info.to_string()),
+                None => Err(Failure::usage(format!("Unknown opcode '{opcode}'")).into()),
+            };
+        }
+
+        if self.emit_ts_types {
+            return self.write(TS_TYPES);
+        }
+
+        if let (Some(program_id), Some(endpoint)) = (&self.compare_onchain, &self.endpoint) {
+            let local = self.read_input(self.path()?)?;
+            let onchain = fetch_program_source(endpoint, program_id)?;
+            let options = self.options();
+            let differences = compare_programs(&local, &onchain, &options)?;
+            let count = differences.len();
+            self.write(&options.serialize(&json!({ "program": program_id, "differences": differences }))?)?;
+            return match count {
+                0 => Ok(()),
+                count => Err(Failure::validation(format!(
+                    "The program differs from the deployed '{program_id}' in {count} place(s)"
+                ))
+                .into()),
+            };
+        }
+
+        if let [a, b] = self.compare_json.as_slice() {
+            let (json_a, json_b) = (Self::read_json(a)?, Self::read_json(b)?);
+            let differences = labeled_differences(&json_a, &json_b, ("a", "b"));
+            let count = differences.len();
+            let (a, b) = (a.display().to_string(), b.display().to_string());
+            let output = json!({ "a": a, "b": b, "differences": differences });
+            self.write(&self.options().serialize(&output)?)?;
+            return match count {
+                0 => Ok(()),
+                count => Err(Failure::validation(format!("'{a}' differs from '{b}' in {count} place(s)")).into()),
+            };
+        }
+
+        if self.index {
+            let (index, failures) = self.index_programs(self.path()?)?;
+            self.write(&self.options().serialize(&index)?)?;
+            return Ok(failures.check()?);
+        }
+
+        if self.serve {
+            let options = self.options();
+            check_sections(&options.only)?;
+            check_renames(&options.rename_keys)?;
+            let path = Path::new("<stdin>");
+            return serve(
+                std::io::stdin().lock(),
+                std::io::stdout().lock(),
+                self.framing,
+                self.max_size,
+                self.tab_width,
+                |source| self.convert_string(path, source, &options, false),
+            );
+        }
+
+        if self.repl {
+            let options = self.options();
+            check_sections(&options.only)?;
+            check_renames(&options.rename_keys)?;
+            let path = Path::new("<repl>");
+            return repl(std::io::stdin().lock(), std::io::stdout().lock(), |source| {
+                options.serialize(&self.convert_string(path, source, &options, self.color.colorize_stderr())?)
+            });
+        }
+
+        match self.watch {
+            // On error, print the diagnostic and keep watching.
+            true => watch_file(self.path()?, || {
+                if let Err(error) = self.convert().and_then(|output| self.write(&output)) {
+                    eprintln!("{error}");
+                }
+            }),
+            false => {
+                let (json, failures) = match self.convert_input() {
+                    Ok(result) => result,
+                    Err(error) => {
+                        if self.error_format == ErrorFormat::Json {
+                            self.write(&self.options().serialize(&self.error_to_json(&error))?)?;
+                        }
+                        return Err(error);
+                    }
+                };
+                let violations = count_violations(&json);
+                let dangling = count_rule_violations(&json, DANGLING_REFERENCE);
+                if let Some(chunk_size) = self.max_functions_parallel {
+                    let start = Instant::now();
+                    self.write_chunked(json, chunk_size)?;
+                    self.record(Phase::Serialize, start);
+                    if self.profile {
+                        eprint!("{}", self.profiler.borrow().report());
+                    }
+                    failures.check()?;
+                    return self.check_violations(violations, dangling);
+                }
+                let start = Instant::now();
+                let output = match &self.template {
+                    Some(template) => {
+                        let name = template.display().to_string();
+                        render_template(&name, &std::fs::read_to_string(template)?, &json)?
+                    }
+                    None if self.summary_csv => self.summary_csv(&json)?,
+                    None if self.json_pointer_list => self.options().serialize(&json!(json_pointers(&json)))?,
+                    None if self.cfg == Some(CfgFormat::Dot) => cfg_to_dot(&json),
+                    None => self.options().serialize(&json)?,
+                };
+                self.record(Phase::Serialize, start);
+                self.write(&output)?;
+                if self.profile {
+                    eprint!("{}", self.profiler.borrow().report());
+                }
+                // Fail if the output is not order-canonical, after writing it, so that it can be inspected.
+                if self.verify_sorted {
+                    if let Some(pointer) = first_unsorted_object(&json) {
+                        let message = format!("The keys of the object at '{pointer}' in the output are not sorted");
+                        return Err(Failure::validation(message).into());
+                    }
+                }
+                // Fail if any program in a directory failed to convert.
+                failures.check()?;
+                self.check_violations(violations, dangling)
+            }
+        }
+    }
+
+    /// Fails with `--validate-closed` if any of the given number of dangling references was found, or else with
+    /// `--deny-warnings` if the validation found any of the given number of violations.
+    fn check_violations(&self, violations: usize, dangling: usize) -> Result<()> {
+        if self.validate_closed && dangling > 0 {
+            return Err(Failure::validation(format!("Found {dangling} dangling reference(s)")).into());
+        }
+        match (self.deny_warnings, violations) {
+            (false, _) | (true, 0) => Ok(()),
+            (true, count) => {
+                Err(Failure::validation(format!("Found {count} violation(s), denied by '--deny-warnings'")).into())
+            }
+        }
+    }
+
+    /// Returns the statistics of each program of the input as CSV, one row per program, by path.
+    fn summary_csv(&self, json: &serde_json::Value) -> Result<String> {
+        let path = self.path()?;
+        match path.is_dir() {
+            true => {
+                let files = json["files"].as_object().into_iter().flatten();
+                Ok(stats_csv(files.map(|(file, json)| (file.as_str(), json))))
+            }
+            false => {
+                let file = path.display().to_string();
+                Ok(stats_csv(std::iter::once((file.as_str(), json))))
+            }
+        }
+    }
+
+    /// Records the time spent in the given phase since the given start, with `--profile`.
+    fn record(&self, phase: Phase, start: Instant) {
+        if self.profile {
+            self.profiler.borrow_mut().record(phase, start.elapsed());
+        }
+    }
+
+    /// Finishes the profile of the given file, with `--profile`.
+    fn finish_file(&self, path: &Path) {
+        if self.profile {
+            self.profiler.borrow_mut().finish_file(path);
+        }
+    }
+
+    /// Returns the given conversion error as JSON, locating it in the input, if it has an offset, and the input was
+    /// read, rather than reading it again.
+    fn error_to_json(&self, error: &anyhow::Error) -> serde_json::Value {
+        error_to_json(error, self.source.borrow().as_deref(), self.tab_width)
+    }
+
+    /// Returns the path to the input.
+    fn path(&self) -> Result<&Path> {
+        self.path.as_deref().ok_or_else(|| anyhow!("The path to the input is required"))
+    }
+
+    /// Writes the given output to the output file, or to stdout.
+    fn write(&self, output: &str) -> Result<()> {
+        if self.output_gzip {
+            return match &self.output {
+                Some(path) => write_gzip(std::fs::File::create(path)?, output),
+                None => write_gzip(std::io::stdout().lock(), &format!("{output}\n")),
+            };
+        }
+        match &self.output {
+            Some(path) => Ok(std::fs::write(path, output)?),
+            None if self.color_json && self.format == OutputFormat::Json && self.color.colorize_stdout() => {
+                println!("{}\n", colorize_json(output));
+                Ok(())
+            }
+            None => {
+                println!("{output}\n");
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the given JSON to the output file, or to stdout, serializing the functions of a program `chunk_size` at
+    /// a time, with the same output as `write`.
+    fn write_chunked(&self, json: serde_json::Value, chunk_size: usize) -> Result<()> {
+        let options = self.options();
+        match &self.output {
+            Some(path) => {
+                let mut writer = BufWriter::new(std::fs::File::create(path)?);
+                options.serialize_chunked(&mut writer, json, chunk_size)
+            }
+            None => {
+                let mut stdout = std::io::stdout().lock();
+                options.serialize_chunked(&mut stdout, json, chunk_size)?;
+                Ok(stdout.write_all(b"\n\n")?)
+            }
+        }
+    }
+
+    /// Returns the conversion options given by the flags.
+    pub fn options(&self) -> ConvertOptions {
+        ConvertOptions::new()
+            .network(self.network)
+            .format(self.format)
+            .pretty(self.pretty)
+            .pretty_arrays_inline(self.pretty_arrays_inline)
+            .pretty_threshold(self.pretty_threshold)
+            .ascii(self.ascii)
+            .dataflow(self.dataflow)
+            .cfg(self.cfg.is_some())
+            .flatten(self.flatten)
+            .grep(self.grep.clone())
+            .grep_function(self.function.clone())
+            .jsonl_index(self.jsonl_index)
+            .split_literals(self.split_literals)
+            .hex_fields(self.hex_fields)
+            .expand_group(self.expand_group)
+            .fixed_width_fields(self.fixed_width_fields)
+            .field_repr(self.field_repr)
+            .native_literals(self.native_literals)
+            .limit_functions(self.limit_functions)
+            .import_dir(self.import_dir.clone().filter(|_| self.inline_imports))
+            .validate(self.validate)
+            .validate_closed(self.validate_closed)
+            .deny_unknown_opcodes(self.deny_unknown_opcodes)
+            .stats(self.stats)
+            .per_function(self.per_function)
+            .only(self.only.clone())
+            .prune_empty(self.prune_empty)
+            .rename_keys(self.rename_key.clone())
+            .redact(self.redact_private.then(|| self.redact_token.clone()))
+            .with_hashes(self.with_hashes || self.dedup)
+            .with_fingerprint(self.with_fingerprint)
+            .normalize_before_hash(self.normalize_before_hash || self.dedup)
+            .expand_types(self.expand_types)
+            .canonicalize(self.canonicalize)
+            .annotate_const(self.annotate_const)
+            .max_depth(Some(self.max_depth))
+            .with_cost(self.with_cost)
+            .with_byte_sizes(self.with_byte_sizes)
+            .with_encoding(self.with_encoding)
+            .with_layout(self.with_layout)
+            .with_raw_operands(self.with_raw_operands)
+            .desugar(self.desugar)
+            .operand_format(match self.operands_as_strings {
+                true => OperandFormat::Strings,
+                false => OperandFormat::Objects,
+            })
+            .with_lines(self.with_lines)
+            .with_pointers(self.with_pointers)
+            .with_meta(self.with_meta)
+    }
+
+    /// Reads the input file at the given path with `--input-encoding`, warning if it is not UTF-8.
+    fn read_input(&self, path: &Path) -> Result<String> {
+        if self.input_encoding != InputEncoding::Utf8 {
+            eprintln!("warning: decoding '{}' as {:?}", path.display(), self.input_encoding);
+        }
+        read_input_with(path, self.max_size, self.input_encoding)
+    }
+
+    /// Converts the input into the output format.
+    /// If the input is a directory, each Aleo program in it is converted, recursively.
+    pub fn convert(&self) -> Result<String> {
+        let (json, _) = self.convert_input()?;
+        self.options().serialize(&json)
+    }
+
+    /// Converts the input into JSON, returning the programs in a directory that failed to convert.
+    /// The keys of each object are sorted if `--sort-keys` is set.
+    fn convert_input(&self) -> Result<(serde_json::Value, Failures)> {
+        let path = self.path()?;
+        let options = self.options();
+        self.source.take();
+        // Reject unknown sections and renames upfront, rather than once per program.
+        check_sections(&options.only)?;
+        check_renames(&options.rename_keys)?;
+        let (mut json, failures) = match path.is_dir() {
+            true if self.from_bytes => {
+                let message = "The input of '--from-bytes' must be a file, not a directory";
+                return Err(Failure::usage(message.to_string()).into());
+            }
+            true => self.convert_directory(path, &options)?,
+            false if self.git_diff.is_some() => {
+                let message = "The input of '--git-diff' must be a directory, not a file";
+                return Err(Failure::usage(message.to_string()).into());
+            }
+            false => {
+                let mut json = match self.from_bytes {
+                    true => self.convert_bytes(path, &options)?,
+                    false => {
+                        // Read the input, keeping it to locate an error in.
+                        let mut source = self.source.borrow_mut();
+                        let string = source.insert(self.read_input(path)?);
+                        // Ensure the input has not been tampered with.
+                        if let Some(expected) = &self.expect_sha256 {
+                            verify_sha256(string.as_bytes(), expected)?;
+                        }
+                        self.convert_string(path, string, &options, self.color.colorize_stderr())?
+                    }
+                };
+                if let Some(merge) = &self.merge {
+                    self.merge_file(&mut json, merge)?;
+                }
+                self.finish_file(path);
+                (json, Failures::default())
+            }
+        };
+        if self.sort_keys {
+            sort_keys(&mut json);
+        }
+        Ok((json, failures))
+    }
+
+    /// Converts the binary program at the given path into JSON, as with `--from-bytes`.
+    fn convert_bytes(&self, path: &Path, options: &ConvertOptions) -> Result<serde_json::Value> {
+        let bytes = read_bytes(path, self.max_size)?;
+        // Ensure the input has not been tampered with.
+        if let Some(expected) = &self.expect_sha256 {
+            verify_sha256(&bytes, expected)?;
+        }
+        let start = Instant::now();
+        let json = convert_program_bytes(&bytes, options)?;
+        self.record(Phase::Build, start);
+        Ok(self.finish_json(json, options))
+    }
+
+    /// Parses the program of `--type-context` at the given path, to type a value with.
+    fn read_type_context<N: Network>(&self, path: &Path, color: bool) -> Result<Program<N>> {
+        let string = self.read_input(path)?;
+        parse_program::<N>(&string).map_err(|error| self.parse_error(path, &string, error, color))
+    }
+
+    /// Reads the JSON file at the given path, i.e. of `--compare-json`.
+    fn read_json(path: &Path) -> Result<serde_json::Value> {
+        let string = std::fs::read_to_string(path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+        serde_json::from_str(&string).with_context(|| format!("Failed to parse the JSON of '{}'", path.display()))
+    }
+
+    /// Attaches the annotations of the given file to the given JSON, warning of each key matching no node.
+    fn merge_file(&self, json: &mut serde_json::Value, path: &Path) -> Result<()> {
+        let string = std::fs::read_to_string(path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+        let annotations = serde_json::from_str::<serde_json::Map<_, _>>(&string)
+            .with_context(|| format!("Failed to parse the annotations of '{}'", path.display()))?;
+        for key in merge_annotations(json, &annotations) {
+            eprintln!("warning: the annotation '{key}' of '{}' matches no node", path.display());
+        }
+        Ok(())
+    }
+
+    /// Returns the interface of each Aleo program in the given directory, or of the given program, by program ID,
+    /// and the programs that failed to convert. Each failure and conflict is reported to stderr.
+    fn index_programs(&self, path: &Path) -> Result<(serde_json::Value, Failures)> {
+        let options = self.options();
+        let paths = match path.is_dir() {
+            true => collect_programs(path)?,
+            false => vec![path.to_path_buf()],
+        };
+        let mut index = serde_json::Map::new();
+        let mut failures = Failures::default();
+        for path in &paths {
+            match self.read_input(path).and_then(|string| convert_abi(&string, &options)) {
+                Ok(abi) => {
+                    if let Some(warning) = insert_into_index(&mut index, abi) {
+                        eprintln!("{warning}, in '{}'", path.display());
+                    }
+                }
+                Err(error) => {
+                    failures.record(&error);
+                    eprintln!("Failed to convert '{}': {error}", path.display());
+                }
+            }
+        }
+        Ok((serde_json::Value::Object(index), failures))
+    }
+
+    /// Converts each Aleo program in the given directory into JSON, i.e.
+    /// `{ "files": { "main.aleo": { ... } }, "summary": { "succeeded": 1, "failed": 0, "skipped": 0 } }`.
+    /// A program that fails to convert is recorded as `{ "error": "..." }`, and the conversion continues,
+    /// unless `--fail-fast` is set. Returns the JSON and the programs that failed to convert.
+    /// The programs not modified since `--since` are skipped, and so are those not changed in `--git-diff`.
+    /// With `--dedup`, the programs that converted are listed once per copy under `"programs"` instead.
+    fn convert_directory(&self, directory: &Path, options: &ConvertOptions) -> Result<(serde_json::Value, Failures)> {
+        let mut paths = match &self.git_diff {
+            Some(range) => changed_programs(directory, range)?,
+            None => collect_programs(directory)?,
+        };
+        let skipped = self.since.map_or(0, |since| retain_modified_since(&mut paths, since));
+        // Select the sample of programs, if any, and record it in the output.
+        let sample = self.sample.map(|size| {
+            paths = sample_programs(&paths, size, self.seed);
+            let files = paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+            json!({ "size": size, "seed": self.seed, "files": files })
+        });
+        let mut progress = self.progress.then(|| Progress::new(paths.len()));
+
+        // The programs are converted one after the other, in sorted order, so that the files and the errors are
+        // emitted in the order of their paths, and the output is reproducible.
+        let mut files = serde_json::Map::new();
+        let mut errors = Vec::new();
+        let mut partitions = BTreeMap::<String, Vec<String>>::new();
+        let mut catalog = Catalog::default();
+        let (mut succeeded, mut failures) = (0, Failures::default());
+        for (index, path) in paths.iter().enumerate() {
+            // Keep the source, to locate an error in it with `--only-errors`.
+            let (source, converted) = match self.read_input(path) {
+                Ok(string) => {
+                    let converted = self.convert_string(path, &string, options, false);
+                    (Some(string), converted)
+                }
+                Err(error) => (None, Err(error)),
+            };
+            let partition = self.partition_by.map(|partition_by| partition_by.partition(converted.as_ref().ok(), path));
+            if let Some(partition) = &partition {
+                partitions.entry(partition.clone()).or_default().push(path.display().to_string());
+            }
+            let json = match converted
+                .and_then(|json| self.write_output_file(directory, path, json, partition.as_deref(), options))
+            {
+                // A program that converted is listed once per copy instead.
+                Ok(json) if self.dedup => {
+                    succeeded += 1;
+                    catalog.insert(path.display().to_string(), json);
+                    None
+                }
+                Ok(json) => {
+                    succeeded += 1;
+                    Some(json)
+                }
+                Err(error) => {
+                    failures.record(&error);
+                    if self.only_errors {
+                        let error = error_to_json(&error, source.as_deref(), self.tab_width)["error"].take();
+                        errors.push(json!({ "file": path.display().to_string(), "error": error }));
+                    }
+                    Some(json!({ "error": error.to_string() }))
+                }
+            };
+            if let (false, Some(json)) = (self.only_errors, json) {
+                files.insert(path.display().to_string(), json);
+            }
+            self.finish_file(path);
+
+            if let Some(progress) = &mut progress {
+                progress.update(index + 1, path);
+            }
+            if failures.count > 0 && self.fail_fast {
+                break;
+            }
+        }
+        if self.only_errors {
+            return Ok((serde_json::Value::Array(errors), failures));
+        }
+        let mut summary = json!({ "succeeded": succeeded, "failed": failures.count, "skipped": skipped });
+        if self.dedup {
+            summary["unique"] = json!(catalog.len());
+            summary["dedup_ratio"] = dedup_ratio(succeeded, catalog.len());
+        }
+        let mut json = json!({ "files": files, "summary": summary });
+        if self.dedup {
+            json["programs"] = catalog.to_json();
+        }
+        if let Some(sample) = sample {
+            json["sample"] = sample;
+        }
+        if self.partition_by.is_some() {
+            json["partitions"] = json!(partitions);
+        }
+        Ok((json, failures))
+    }
+
+    /// Writes the given JSON of the given program to its file in `--output-dir`, under the sub-directory of the given
+    /// partition, if any, returning `{ "output": "..." }`. Without `--output-dir`, returns the JSON itself.
+    fn write_output_file(
+        &self,
+        directory: &Path,
+        path: &Path,
+        json: serde_json::Value,
+        partition: Option<&str>,
+        options: &ConvertOptions,
+    ) -> Result<serde_json::Value> {
+        let Some(output_dir) = &self.output_dir else {
+            return Ok(json);
+        };
+        let output_dir = match partition {
+            Some(partition) => output_dir.join(partition),
+            None => output_dir.clone(),
+        };
+        let extension = match self.output_gzip {
+            true => format!("{}.gz", self.format.extension()),
+            false => self.format.extension().to_string(),
+        };
+        let output = output_path(directory, path, &output_dir, &extension);
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let start = Instant::now();
+        let serialized = options.serialize(&json)?;
+        self.record(Phase::Serialize, start);
+        match self.output_gzip {
+            true => write_gzip(std::fs::File::create(&output)?, &serialized)?,
+            false => std::fs::write(&output, serialized)?,
+        }
+        Ok(json!({ "output": output.display().to_string() }))
+    }
+
+    /// Converts the given input into JSON, for the network in the given options, or for each supported network,
+    /// by network, if `--all-networks` is set. The input fails to convert only if it fails for every network.
+    fn convert_string(
+        &self,
+        path: &Path,
+        string: &str,
+        options: &ConvertOptions,
+        color: bool,
+    ) -> Result<serde_json::Value> {
+        if !self.all_networks {
+            return self.convert_network(path, string, options, color);
+        }
+        let mut networks = serde_json::Map::new();
+        let mut last_error = None;
+        for network in NetworkName::value_variants() {
+            let json = match self.convert_network(path, string, &options.clone().network(*network), color) {
+                Ok(json) => json,
+                Err(error) => {
+                    let json = json!({ "error": error.to_string() });
+                    last_error = Some(error);
+                    json
+                }
+            };
+            networks.insert(network.name().to_string(), json);
+        }
+        match last_error {
+            Some(error) if networks.values().all(|json| json.get("error").is_some()) => Err(error),
+            _ => Ok(serde_json::Value::Object(networks)),
+        }
+    }
+
+    /// Converts the given input into JSON, for the network in the given options.
+    fn convert_network(
+        &self,
+        path: &Path,
+        string: &str,
+        options: &ConvertOptions,
+        color: bool,
+    ) -> Result<serde_json::Value> {
+        match options.network {
+            NetworkName::Testnet3 => self.convert_for::<Testnet3>(path, string, options, color),
+        }
+    }
+
+    /// Converts the given input into JSON, for the given network.
+    fn convert_for<N: Network>(
+        &self,
+        path: &Path,
+        string: &str,
+        options: &ConvertOptions,
+        color: bool,
+    ) -> Result<serde_json::Value> {
+        let start = Instant::now();
+        let json = match self.kind {
+            InputKind::Program => self.convert_program::<N>(path, string, options, color)?,
+            InputKind::Execution => execution_to_json(&Execution::<N>::from_str(string)?)?,
+            InputKind::Transition => transition_to_json(&Transition::<N>::from_str(string)?)?,
+            InputKind::Deployment => {
+                let (deployment, metadata) = parse_deployment::<N>(string)?;
+                self.deployment_to_json(&deployment, metadata, options)?
+            }
+            InputKind::Block => {
+                let deployments = parse_block_deployments::<N>(string)?;
+                let programs = deployments
+                    .into_iter()
+                    .map(|(deployment, metadata)| self.deployment_to_json(&deployment, metadata, options));
+                serde_json::Value::Array(programs.collect::<Result<_>>()?)
+            }
+            InputKind::Record => {
+                let record = Record::<N, Plaintext<N>>::from_str(string.trim())?;
+                if let Some(max_depth) = options.max_depth {
+                    check_record_depth(&record, max_depth)?;
+                }
+                let mut json = record_to_json(&record, options.hex_fields);
+                if options.with_encoding {
+                    add_encoded_fields(&mut json, &record)?;
+                }
+                json
+            }
+            InputKind::Value => {
+                let value = Plaintext::<N>::from_str(string.trim())?;
+                if let Some(max_depth) = options.max_depth {
+                    check_value_depth(&value, max_depth)?;
+                }
+                let context = self.type_context.as_deref().map(|path| self.read_type_context::<N>(path, color));
+                value_to_json(&value, context.transpose()?.as_ref())
+            }
+        };
+        // The parsing of any other input is not separated from building its JSON.
+        if self.kind != InputKind::Program {
+            self.record(Phase::Build, start);
+        }
+        Ok(self.finish_json(json, options))
+    }
+
+    /// Applies the options common to every kind of input to the given JSON, i.e. `--redact` and `--wrap`.
+    fn finish_json(&self, mut json: serde_json::Value, options: &ConvertOptions) -> serde_json::Value {
+        add_network(&mut json, options.network);
+        if let Some(token) = &options.redact {
+            walk_program(&mut json, &mut Redactor::new(token.clone()));
+        }
+        if options.with_meta {
+            add_schema_version(&mut json);
+        }
+        if self.wrap {
+            json = wrap_document(json, self.kind);
+        }
+        json
+    }
+
+    /// Converts the program of the given deployment into JSON, with its edition, and its metadata if requested.
+    fn deployment_to_json<N: Network>(
+        &self,
+        deployment: &Deployment<N>,
+        metadata: serde_json::Value,
+        options: &ConvertOptions,
+    ) -> Result<serde_json::Value> {
+        let mut json = program_to_json(deployment.program(), options)?;
+        // The instruction table of `--flatten` is an array, which has no room for the metadata.
+        if let Some(object) = json.as_object_mut() {
+            // A program source has no edition, so it is only emitted for a deployment.
+            object.insert("edition".to_string(), metadata["edition"].clone());
+            if self.deployment_metadata {
+                object.insert("deployment".to_string(), metadata);
+            }
+        }
+        Ok(json)
+    }
+
+    /// Converts the given Aleo program into JSON, colorizing the diagnostic on a parser error if `color` is set.
+    fn convert_program<N: Network>(
+        &self,
+        path: &Path,
+        string: &str,
+        options: &ConvertOptions,
+        color: bool,
+    ) -> Result<serde_json::Value> {
+        // Locate an invalid literal in the source, as the parser would reject it without locating it.
+        if self.validate {
+            validate_source::<N>(string).map_err(|error| self.parse_error(path, string, error, color))?;
+        }
+        if self.lenient {
+            return self.convert_lenient::<N>(path, string, options, color);
+        }
+        // Parse the program, or each of the programs concatenated in the input.
+        let parse: fn(&str) -> Result<Vec<Program<N>>, Aleo2JsonError> = match self.multi {
+            true => parse_programs::<N>,
+            false => |string| parse_program::<N>(string).map(|program| vec![program]),
+        };
+        let start = Instant::now();
+        let parsed = parse_with_timeout(string, self.timeout.map(Duration::from_millis), parse);
+        self.record(Phase::Parse, start);
+        let mut programs = parsed.map_err(|error| self.parse_error(path, string, error, color))?;
+        let start = Instant::now();
+        if self.multi {
+            let programs = programs.iter().map(|program| program_to_json(program, options)).collect::<Result<_>>()?;
+            self.record(Phase::Build, start);
+            return Ok(serde_json::Value::Array(programs));
+        }
+        let program = programs.remove(0);
+        // Ensure the program is in canonical form.
+        if self.strict_whitespace {
+            let path = path.display().to_string();
+            if let Some(diff) = canonical_diff(&path, string, &program) {
+                eprint!("{diff}");
+                return Err(Failure::validation(format!("'{path}' is not in canonical form")).into());
+            }
+        }
+
+        let json = program_source_to_json(&program, string, options).map(|mut json| {
+            if self.debug_parse {
+                add_parser_boundary(&mut json, string, string.len());
+            }
+            json
+        });
+        self.record(Phase::Build, start);
+        json
+    }
+
+    /// Converts as much of the given Aleo program as the parser can parse into JSON, with the error at which the
+    /// parser stopped under `"parse_error"`, or `null` if there is none, i.e. with `--lenient`.
+    fn convert_lenient<N: Network>(
+        &self,
+        path: &Path,
+        string: &str,
+        options: &ConvertOptions,
+        color: bool,
+    ) -> Result<serde_json::Value> {
+        let start = Instant::now();
+        let parsed = parse_with_timeout(string, self.timeout.map(Duration::from_millis), parse_program_lenient::<N>);
+        self.record(Phase::Parse, start);
+        let mut partial = parsed.map_err(|error| self.parse_error(path, string, error, color))?;
+
+        let start = Instant::now();
+        let mut json = partial_program_to_json(&partial, string, options)?;
+        self.record(Phase::Build, start);
+        if let Some(object) = json.as_object_mut() {
+            // The error is located as with `--error-format json`.
+            let error = partial.error.take().map(|error| {
+                error_to_json(&anyhow::Error::new(error), Some(string), self.tab_width)["error"].take()
+            });
+            object.insert("parse_error".to_string(), json!(error));
+        }
+        if self.debug_parse {
+            add_parser_boundary(&mut json, string, partial.consumed);
+        }
+        Ok(json)
+    }
+
+    /// Returns the given parser error, with its diagnostic in the given source as its context, colorized if `color`
+    /// is set.
+    fn parse_error(&self, path: &Path, string: &str, error: Aleo2JsonError, color: bool) -> anyhow::Error {
+        let Some(offset) = error.offset() else {
+            return error.into();
+        };
+        if let (true, Aleo2JsonError::UnconsumedInput { offset }) = (self.debug_parse, &error) {
+            let remainder = &string[*offset..];
+            eprintln!("debug: the parser consumed {offset} byte(s), leaving {remainder:?}");
+        }
+        // Keep the error itself, so that it can still be reported as JSON.
+        let path = path.display().to_string();
+        let diagnostic = render_diagnostic(&path, string, offset, &error.to_string(), color, self.tab_width);
+        anyhow::Error::new(error).context(diagnostic.trim_end().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_to_json() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("main.aleo");
+        std::fs::write(&path, "program main.aleo;\n\nfunction main:\n    frobnicate r0;\n").unwrap();
+
+        let cli = Aleo2Json::parse_from(["aleo2json", path.to_str().unwrap(), "--error-format", "json"]);
+        let error = cli.convert_input().unwrap_err();
+        let json = cli.error_to_json(&error);
+        assert_eq!(json["ok"], false);
+        assert_eq!(json["error"]["kind"], "unconsumed_input");
+        assert_eq!(json["error"]["offset"], 39);
+        assert_eq!(json["error"]["line"], 4);
+        assert_eq!(json["error"]["col"], 5);
+
+        // Errors without an offset have no location.
+        let cli = Aleo2Json::parse_from(["aleo2json", directory.path().join("missing.aleo").to_str().unwrap()]);
+        let json = cli.error_to_json(&cli.convert_input().unwrap_err());
+        assert_eq!(json["error"]["kind"], "io");
+        assert!(json["error"]["line"].is_null());
+
+        // The error is located in the input as decoded with `--input-encoding`.
+        std::fs::write(&path, b"// caf\xe9\nprogram main.aleo;\n\nfunction main:\n    frobnicate r0;\n").unwrap();
+        let cli = Aleo2Json::parse_from(["aleo2json", path.to_str().unwrap(), "--input-encoding", "latin1"]);
+        let json = cli.error_to_json(&cli.convert_input().unwrap_err());
+        assert_eq!(json["error"]["line"], 5);
+        assert_eq!(json["error"]["col"], 5);
+
+        // An input too large to read has no location.
+        let cli = Aleo2Json::parse_from(["aleo2json", path.to_str().unwrap(), "--max-size", "8"]);
+        let json = cli.error_to_json(&cli.convert_input().unwrap_err());
+        assert_eq!(json["error"]["kind"], "input_too_large");
+        assert!(json["error"]["line"].is_null());
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("main.aleo");
+        std::fs::write(&path, "program main.aleo;\n\nfunction main:\n    input r0 as u8.public;\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        // The program lists `"type"` before `"id"`, unless its keys are sorted.
+        let (json, _) = Aleo2Json::parse_from(["aleo2json", path]).convert_input().unwrap();
+        assert_eq!(first_unsorted_object(&json), Some(String::new()));
+        let (json, _) = Aleo2Json::parse_from(["aleo2json", path, "--sort-keys"]).convert_input().unwrap();
+        assert_eq!(first_unsorted_object(&json), None);
+
+        // The check is of the sorted output.
+        assert!(Aleo2Json::try_parse_from(["aleo2json", path, "--verify-sorted"]).is_err());
+        assert!(Aleo2Json::try_parse_from(["aleo2json", path, "--sort-keys", "--verify-sorted"]).is_ok());
+    }
+
+    #[test]
+    fn test_only_errors() {
+        let directory = tempfile::tempdir().unwrap();
+        let good = "program good.aleo;\n\nfunction main:\n    input r0 as u8.public;\n    add r0 r0 into r1;\n";
+        std::fs::write(directory.path().join("good.aleo"), good).unwrap();
+        let bad = directory.path().join("bad.aleo");
+        std::fs::write(&bad, "program bad.aleo;\n\nfunction main:\n    frobnicate r0;\n").unwrap();
+
+        let cli = Aleo2Json::parse_from(["aleo2json", directory.path().to_str().unwrap(), "--only-errors"]);
+        let (json, failures) = cli.convert_input().unwrap();
+        assert_eq!(failures.count, 1);
+        let errors = json.as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["file"], bad.display().to_string());
+        assert_eq!(errors[0]["error"]["kind"], "unconsumed_input");
+        assert_eq!(errors[0]["error"]["line"], 4);
+
+        // The errors are ordered by path, whatever the order in which the files were created.
+        let first = directory.path().join("a.aleo");
+        std::fs::write(&first, "program a.aleo;\n\nfunction main:\n    frobnicate r0;\n").unwrap();
+        let (json, failures) = cli.convert_input().unwrap();
+        assert_eq!(failures.count, 2);
+        let files = json.as_array().unwrap().iter().map(|error| error["file"].clone()).collect::<Vec<_>>();
+        assert_eq!(files, [json!(first.display().to_string()), json!(bad.display().to_string())]);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let directory = tempfile::tempdir().unwrap();
+        let token = "program token.aleo;\n\nfunction main:\n    input r0 as u8.public;\n    add r0 r0 into r1;\n";
+        // A copy numbering its registers differently is the same program.
+        let renumbered = "program token.aleo;\n\nfunction main:\n    input r3 as u8.public;\n    add r3 r3 into r7;\n";
+        for (path, source) in [
+            ("a.aleo", token),
+            ("b.aleo", renumbered),
+            ("nested/c.aleo", token),
+            ("other.aleo", "program other.aleo;\n\nfunction main:\n    input r0 as u8.public;\n"),
+            ("bad.aleo", "program bad.aleo;\n\nfunction main:\n    frobnicate r0;\n"),
+        ] {
+            let path = directory.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+
+        let cli = Aleo2Json::parse_from(["aleo2json", directory.path().to_str().unwrap(), "--dedup"]);
+        let (json, failures) = cli.convert_input().unwrap();
+        assert_eq!(failures.count, 1);
+        // Only the program that failed to convert is listed by file.
+        let files = json["files"].as_object().unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), [&directory.path().join("bad.aleo").display().to_string()]);
+        let programs = json["programs"].as_array().unwrap();
+        assert_eq!(programs.len(), 2);
+        let copies = ["a.aleo", "b.aleo", "nested/c.aleo"].map(|path| directory.path().join(path));
+        assert_eq!(programs[0]["files"], json!(copies.map(|path| path.display().to_string())));
+        assert_eq!(programs[0]["program"]["id"]["name"], "token");
+        assert_eq!(programs[1]["program"]["id"]["name"], "other");
+        assert_eq!(json["summary"]["succeeded"], 4);
+        assert_eq!(json["summary"]["unique"], 2);
+        assert_eq!(json["summary"]["dedup_ratio"], 2.0);
+    }
+
+    #[test]
+    fn test_lenient() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("main.aleo");
+        let source = "program main.aleo;\n\nfunction main:\n    input r0 as u8.public;\n    frobnicate r0;\n";
+        std::fs::write(&path, source).unwrap();
+
+        // The part before the invalid instruction is converted, with the error at which the parser stopped.
+        let cli = Aleo2Json::parse_from(["aleo2json", path.to_str().unwrap(), "--lenient", "--debug-parse"]);
+        let (json, _) = cli.convert_input().unwrap();
+        assert_eq!(json["functions"]["main"]["inputs"].as_array().unwrap().len(), 1);
+        assert_eq!(json["parse_error"]["kind"], "unconsumed_input");
+        assert_eq!(json["parse_error"]["line"], 5);
+        assert_eq!(json["parser_consumed"], source.find("frobnicate").unwrap());
+        assert_eq!(json["parser_remainder"], "frobnicate r0;\n");
+
+        // A valid program has no error.
+        std::fs::write(&path, source.replace("    frobnicate r0;\n", "")).unwrap();
+        let (json, _) = cli.convert_input().unwrap();
+        assert!(json["parse_error"].is_null());
+        assert!(json["parser_remainder"].is_null());
+    }
+
+    #[test]
+    fn test_type_context() {
+        let directory = tempfile::tempdir().unwrap();
+        let (path, context) = (directory.path().join("value.txt"), directory.path().join("main.aleo"));
+        std::fs::write(&path, "{ x: 1u8, y: 2u8 }").unwrap();
+        std::fs::write(&context, "program main.aleo;\n\nstruct point:\n    x as u8;\n    y as u8;\n").unwrap();
+
+        // The value is typed by the structs of the context.
+        let cli = Aleo2Json::parse_from([
+            "aleo2json",
+            path.to_str().unwrap(),
+            "--kind",
+            "value",
+            "--type-context",
+            context.to_str().unwrap(),
+        ]);
+        let (json, _) = cli.convert_input().unwrap();
+        assert_eq!(json["plaintext_type"], "point");
+        assert_eq!(json["members"]["y"]["plaintext_type"], "u8");
+
+        // Without a context, the struct is untyped.
+        let cli = Aleo2Json::parse_from(["aleo2json", path.to_str().unwrap(), "--kind", "value"]);
+        let (json, _) = cli.convert_input().unwrap();
+        assert!(json["plaintext_type"].is_null());
+    }
+
+    #[test]
+    fn test_all_networks() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("main.aleo");
+        std::fs::write(&path, "program main.aleo;\n\nfunction main:\n    input r0 as u8.public;\n").unwrap();
+
+        let cli = Aleo2Json::parse_from(["aleo2json", path.to_str().unwrap(), "--all-networks"]);
+        let (json, _) = cli.convert_input().unwrap();
+        let networks = json.as_object().unwrap();
+        assert_eq!(networks.len(), NetworkName::value_variants().len());
+        assert_eq!(json["testnet3"]["id"]["name"], "main");
+
+        // An input failing to convert for every network fails.
+        std::fs::write(&path, "program main.aleo;\n\nfunction main:\n    frobnicate r0;\n").unwrap();
+        assert!(cli.convert_input().is_err());
+    }
+
+    // A test case recommended by clap (https://docs.rs/clap/latest/clap/_derive/_tutorial/index.html#testing).
+    #[test]
+    fn verify_cli() {
+        use clap::CommandFactory;
+        Aleo2Json::command().debug_assert()
+    }
+}
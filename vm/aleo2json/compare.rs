@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{convert_program, ConvertOptions};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// Fetches the source of the given deployed program from the given endpoint, i.e. `https://api.explorer.aleo.org/v1`.
+pub fn fetch_program_source(endpoint: &str, program_id: &str) -> Result<String> {
+    let url = format!("{}/testnet3/program/{program_id}", endpoint.trim_end_matches('/'));
+    // The endpoint returns the program source as a JSON string.
+    Ok(ureq::get(&url).call()?.into_json::<String>()?)
+}
+
+/// Appends the structural differences between the given JSON values to `differences`, each side under the given
+/// label, i.e. `{ "path": "/functions/main/instructions/0/str", "local": "add r0 1u64 into r1;", "onchain": "..." }`.
+/// Each path is a JSON pointer, and a value missing on one side is `null`. The order of the keys does not matter.
+fn diff(path: &mut String, local: &Value, onchain: &Value, labels: (&str, &str), differences: &mut Vec<Value>) {
+    match (local, onchain) {
+        (Value::Object(local), Value::Object(onchain)) => {
+            let keys = local.keys().chain(onchain.keys().filter(|key| !local.contains_key(*key)));
+            for key in keys.cloned().collect::<Vec<_>>() {
+                let length = path.len();
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                let (local, onchain) = (local.get(&key), onchain.get(&key));
+                diff(path, local.unwrap_or(&Value::Null), onchain.unwrap_or(&Value::Null), labels, differences);
+                path.truncate(length);
+            }
+        }
+        (Value::Array(local), Value::Array(onchain)) => {
+            for index in 0..local.len().max(onchain.len()) {
+                let length = path.len();
+                path.push_str(&format!("/{index}"));
+                let (local, onchain) = (local.get(index), onchain.get(index));
+                diff(path, local.unwrap_or(&Value::Null), onchain.unwrap_or(&Value::Null), labels, differences);
+                path.truncate(length);
+            }
+        }
+        (local, onchain) if local != onchain => {
+            let (local_label, onchain_label) = labels;
+            differences.push(json!({ "path": path.clone(), local_label: local, onchain_label: onchain }));
+        }
+        _ => (),
+    }
+}
+
+/// Returns the structural differences between the given local and on-chain JSON, as JSON pointers into both.
+pub fn json_differences(local: &Value, onchain: &Value) -> Vec<Value> {
+    labeled_differences(local, onchain, ("local", "onchain"))
+}
+
+/// Returns the structural differences between the given JSON values, as JSON pointers into both, with the value of
+/// each side under the given label, i.e. `("a", "b")`, as `json_differences` does.
+pub fn labeled_differences(a: &Value, b: &Value, labels: (&str, &str)) -> Vec<Value> {
+    let mut differences = Vec::new();
+    diff(&mut String::new(), a, b, labels, &mut differences);
+    differences
+}
+
+/// Converts the given local and on-chain program sources with the given options, and returns their structural
+/// differences. Both are converted alike, so that only differences in the programs themselves are reported.
+pub fn compare_programs(local: &str, onchain: &str, options: &ConvertOptions) -> Result<Vec<Value>> {
+    Ok(json_differences(&convert_program(local, options)?, &convert_program(onchain, options)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "program main.aleo;\n\nfunction main:\n    input r0 as u64.public;\n    add r0 1u64 into r1;";
+
+    #[test]
+    fn test_json_differences() {
+        let local = json!({ "a": 1, "b": [1, 2], "c/d": { "e": true } });
+        let onchain = json!({ "a": 1, "b": [1], "c/d": { "e": false }, "f": "g" });
+        assert_eq!(json_differences(&local, &onchain), [
+            json!({ "path": "/b/1", "local": 2, "onchain": null }),
+            json!({ "path": "/c~1d/e", "local": true, "onchain": false }),
+            json!({ "path": "/f", "local": null, "onchain": "g" }),
+        ]);
+        assert!(json_differences(&local, &local).is_empty());
+
+        // The order of the keys is not a difference.
+        let reordered = json!({ "f": "g", "c/d": { "e": false }, "b": [1], "a": 1 });
+        assert!(labeled_differences(&onchain, &reordered, ("a", "b")).is_empty());
+        let differences = labeled_differences(&local, &reordered, ("a", "b"));
+        assert_eq!(differences[0], json!({ "path": "/b/1", "a": 2, "b": null }));
+    }
+
+    #[test]
+    fn test_compare_programs() {
+        let options = ConvertOptions::new();
+        // Whitespace is not a difference.
+        assert!(compare_programs(SOURCE, &SOURCE.replace("add r0", "add   r0"), &options).unwrap().is_empty());
+
+        let differences = compare_programs(SOURCE, &SOURCE.replace("1u64", "2u64"), &options).unwrap();
+        assert!(!differences.is_empty());
+        assert!(differences.iter().all(|difference| {
+            difference["path"].as_str().unwrap().starts_with("/functions/main/instructions/0/")
+        }));
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::Failure;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::path::Path;
+
+/// The name of the config file discovered in the current directory, if `--config` is not given.
+pub const CONFIG_FILE: &str = "aleo2json.toml";
+
+/// The defaults read from a config file, i.e. `aleo2json.toml`, with the same names as the flags, i.e.
+/// `network = "testnet3"` and `pretty = true`. A flag given on the command line, or through its environment
+/// variable, overrides the config file, which overrides the built-in default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// The network to parse the input for, i.e. `testnet3`.
+    pub network: Option<String>,
+    /// The serialization format of the output, i.e. `yaml`.
+    pub format: Option<String>,
+    /// Whether to indent the JSON output.
+    pub pretty: Option<bool>,
+    /// Whether to indent the JSON output, rendering arrays of scalars on a single line.
+    pub pretty_arrays_inline: Option<bool>,
+    /// Whether to escape each non-ASCII character in the JSON output.
+    pub ascii: Option<bool>,
+    /// Whether to emit each literal as its value and its type.
+    pub split_literals: Option<bool>,
+    /// Whether to emit each field and group literal in decimal and hex forms.
+    pub hex_fields: Option<bool>,
+    /// Whether to emit each boolean and 32-bit or narrower integer literal as a native JSON value.
+    pub native_literals: Option<bool>,
+    /// Whether to emit the SHA-256 digest of the program, and of each function body.
+    pub with_hashes: Option<bool>,
+    /// Whether to emit the version of the structure of the output.
+    pub with_meta: Option<bool>,
+    /// Whether to validate the program.
+    pub validate: Option<bool>,
+    /// Whether to emit the statistics of the program.
+    pub stats: Option<bool>,
+    /// The maximum nesting depth of a type or a value.
+    pub max_depth: Option<usize>,
+    /// The maximum size of an input, in bytes.
+    pub max_size: Option<u64>,
+}
+
+impl Config {
+    /// Reads the config file at the given path.
+    pub fn read(path: &Path) -> Result<Self> {
+        let string = std::fs::read_to_string(path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+        // An invalid config file is an invalid argument, rather than an invalid input.
+        toml::from_str(&string)
+            .map_err(|error| Failure::usage(format!("Failed to parse '{}': {error}", path.display())).into())
+    }
+
+    /// Reads the config file at the given path, or else `aleo2json.toml` in the current directory, if it exists.
+    pub fn discover(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::read(path),
+            None if Path::new(CONFIG_FILE).is_file() => Self::read(Path::new(CONFIG_FILE)),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Parses the given config value of an enum, i.e. `"testnet3"`, as its flag would be parsed.
+pub(crate) fn parse_config_enum<T: ValueEnum>(key: &str, value: &str) -> Result<T> {
+    T::from_str(value, true)
+        .map_err(|error| Failure::usage(format!("Invalid value for '{key}' in the config file: {error}")).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::NetworkName;
+
+    #[test]
+    fn test_config() {
+        let config: Config = toml::from_str("network = \"testnet3\"\npretty = true\nmax-depth = 8\n").unwrap();
+        assert_eq!(config.pretty, Some(true));
+        assert_eq!(config.max_depth, Some(8));
+        assert_eq!(config.ascii, None);
+        let network = parse_config_enum::<NetworkName>("network", config.network.as_deref().unwrap()).unwrap();
+        assert_eq!(network, NetworkName::Testnet3);
+        assert!(parse_config_enum::<NetworkName>("network", "mainnet").is_err());
+
+        // An unknown key is an error, rather than being ignored.
+        assert!(toml::from_str::<Config>("sort-keys = true").is_err());
+    }
+}
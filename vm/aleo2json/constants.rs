@@ -0,0 +1,108 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{network::Network, program::ValueType};
+use snarkvm_synthesizer_program::{Instruction, InstructionTrait, Operand, Program};
+
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// Returns `true` if the given operand is constant, given the locators of the constant registers.
+/// A register access (i.e. `r0.amount`) is constant if its register is.
+fn is_constant_operand<N: Network>(operand: &Operand<N>, constants: &HashSet<u64>) -> bool {
+    match operand {
+        Operand::Literal(_) | Operand::ProgramID(_) => true,
+        Operand::Register(register) => constants.contains(&register.locator()),
+        Operand::Signer | Operand::Caller | Operand::BlockHeight => false,
+    }
+}
+
+/// Returns `true` if the given instruction operates only on constants, given the locators of the constant registers.
+/// A `call` or an `async` is never constant, as it depends on the body of another function.
+fn is_constant_instruction<N: Network>(instruction: &Instruction<N>, constants: &HashSet<u64>) -> bool {
+    match instruction {
+        Instruction::Call(_) | Instruction::Async(_) => false,
+        instruction => {
+            let operands = instruction.operands();
+            !operands.is_empty() && operands.iter().all(|operand| is_constant_operand(operand, constants))
+        }
+    }
+}
+
+/// Marks each instruction of each function and closure whose operands are all constant under `"const"`, i.e.
+/// `"const": true`. A single forward pass propagates constants from the constant inputs and the literals to the
+/// destinations of the constant instructions. The inputs of a closure have no mode, so are never constant.
+pub fn add_const_annotations<N: Network>(json: &mut Value, program: &Program<N>) {
+    let functions = program.functions().iter().map(|(name, function)| {
+        let inputs = function.inputs().iter().filter(|input| matches!(input.value_type(), ValueType::Constant(_)));
+        let constants = inputs.map(|input| input.register().locator()).collect::<HashSet<_>>();
+        ("functions", name, constants, function.instructions())
+    });
+    let closures =
+        program.closures().iter().map(|(name, closure)| ("closures", name, HashSet::new(), closure.instructions()));
+    for (section, name, mut constants, instructions) in functions.chain(closures) {
+        let Some(function_json) = json.get_mut(section).and_then(|section| section.get_mut(name.to_string())) else {
+            continue;
+        };
+        for (index, instruction) in instructions.iter().enumerate() {
+            let is_constant = is_constant_instruction(instruction, &constants);
+            if is_constant {
+                constants.extend(instruction.destinations().iter().map(|destination| destination.locator()));
+            }
+            function_json["instructions"][index]["const"] = json!(is_constant);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_const_annotations() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program constants.aleo;
+
+closure twice:
+    input r0 as u8;
+    add r0 r0 into r1;
+    add 1u8 2u8 into r2;
+    output r1 as u8;
+
+function main:
+    input r0 as u64.constant;
+    input r1 as u64.public;
+    add r0 1u64 into r2;
+    mul r2 r2 into r3;
+    add r3 r1 into r4;
+    is.eq r4 r3 into r5;
+    call twice 1u8 into r6;
+    add r6 1u8 into r7;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_const_annotations(&mut json, &program);
+        let constants = |section: &str, name: &str| {
+            let instructions = json[section][name]["instructions"].as_array().unwrap();
+            instructions.iter().map(|instruction| instruction["const"].as_bool().unwrap()).collect::<Vec<_>>()
+        };
+        assert_eq!(constants("closures", "twice"), [false, true]);
+        // The public input, and the output of a call, are not constant, nor is anything computed from them.
+        assert_eq!(constants("functions", "main"), [true, true, false, false, false, false]);
+    }
+}
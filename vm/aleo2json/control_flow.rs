@@ -0,0 +1,107 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{network::Network, program::Identifier};
+use snarkvm_synthesizer_program::{Command, Program};
+
+use serde_json::{json, Value};
+
+/// The effect of a finalize command on the control flow of its finalize logic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlFlow<'a, N: Network> {
+    /// A `branch.eq` or a `branch.neq`, jumping forward to the given position if its condition holds.
+    Branch(&'a Identifier<N>),
+    /// A `position`, marking the target of a branch.
+    Position(&'a Identifier<N>),
+}
+
+impl<'a, N: Network> ControlFlow<'a, N> {
+    /// Returns the effect of the given command on the control flow, or `None` if it falls through to the next.
+    pub fn of(command: &'a Command<N>) -> Option<Self> {
+        match command {
+            Command::BranchEq(branch_eq) => Some(Self::Branch(branch_eq.position())),
+            Command::BranchNeq(branch_neq) => Some(Self::Branch(branch_neq.position())),
+            Command::Position(position) => Some(Self::Position(position.name())),
+            _ => None,
+        }
+    }
+
+    /// Returns the effect as JSON, i.e. `{ "to_position": "end" }` for a branch, and `{ "position": "end" }`
+    /// for a position.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Branch(position) => json!({ "to_position": position.to_string() }),
+            Self::Position(position) => json!({ "position": position.to_string() }),
+        }
+    }
+}
+
+/// Adds the effect of each branch and position command of each finalize logic on its control flow under
+/// `"control_flow"`, i.e. `"control_flow": { "to_position": "end" }` for `branch.eq r0 0u64 to end`, and
+/// `"control_flow": { "position": "end" }` for `position end`, so that the control flow can be rebuilt.
+pub fn add_control_flow<N: Network>(json: &mut Value, program: &Program<N>) {
+    for (name, function) in program.functions() {
+        let Some(finalize) = function.finalize_logic() else {
+            continue;
+        };
+        let Some(commands) = json.pointer_mut(&format!("/functions/{name}/finalize_logic/commands")) else {
+            continue;
+        };
+        for (index, command) in finalize.commands().iter().enumerate() {
+            if let Some(control_flow) = ControlFlow::of(command) {
+                commands[index]["control_flow"] = control_flow.to_json();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_control_flow() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program flow.aleo;
+
+mapping counts:
+    key as u64.public;
+    value as u64.public;
+
+function count:
+    input r0 as u64.public;
+    async count r0 into r1;
+    output r1 as flow.aleo/count.future;
+
+finalize count:
+    input r0 as u64.public;
+    branch.eq r0 0u64 to end;
+    branch.neq r0 1u64 to end;
+    set r0 into counts[r0];
+    position end;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_control_flow(&mut json, &program);
+        let commands = &json["functions"]["count"]["finalize_logic"]["commands"];
+        assert_eq!(commands[0]["control_flow"], json!({ "to_position": "end" }));
+        assert_eq!(commands[1]["control_flow"], json!({ "to_position": "end" }));
+        assert!(commands[2].get("control_flow").is_none());
+        assert_eq!(commands[3]["control_flow"], json!({ "position": "end" }));
+    }
+}
@@ -0,0 +1,926 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        add_access_paths,
+        add_arity,
+        add_assertions,
+        add_byte_sizes,
+        add_cast_fields,
+        add_closure_callers,
+        add_const_annotations,
+        add_control_flow,
+        add_cost_estimates,
+        add_crypto_variants,
+        add_destination_types,
+        add_futures,
+        add_hashes,
+        add_layouts,
+        add_mapping_effects,
+        add_random_sources,
+        add_type_expansions,
+        add_type_provenance,
+        add_unused_inputs,
+        canonical_bundle,
+        check_program_depth,
+        credit_interactions,
+        declaration_order,
+        desugar_program,
+        expand_groups,
+        external_calls,
+        fingerprint,
+        fixed_width_fields,
+        flatten_program,
+        grep_rows,
+        hex_fields,
+        instruction_index,
+        limit_functions,
+        mapping_index,
+        montgomery_fields,
+        native_literals,
+        only_sections,
+        operands_to_strings,
+        program_cfg,
+        program_dataflow,
+        program_dependencies,
+        program_stats,
+        prune_empty_sections,
+        record_types,
+        rename_sections,
+        resolve_imports,
+        split_literals,
+        tag_opcodes,
+        validate,
+        validate_closed,
+        validate_source,
+        walk_program,
+        Aleo2JsonError,
+        ConvertOptions,
+        FieldRepr,
+        InputEncoding,
+        InstructionLines,
+        NetworkName,
+        NodeVisitor,
+        OperandFormat,
+        Pointers,
+        RawOperands,
+    },
+    console::network::{
+        prelude::{FromBytes, Parser},
+        Network,
+        Testnet3,
+    },
+};
+use snarkvm_synthesizer_program::Program;
+
+use anyhow::Result;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::{io::Read, path::Path, sync::mpsc, time::Duration};
+
+/// The default maximum size of an input, in bytes.
+pub const DEFAULT_MAX_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Reads the UTF-8 file at the given path into a string, refusing files larger than `max_size` bytes.
+pub fn read_input(path: &Path, max_size: u64) -> Result<String> {
+    read_input_with(path, max_size, InputEncoding::Utf8)
+}
+
+/// Reads the file at the given path into a string, decoding it from the given encoding,
+/// and refusing files larger than `max_size` bytes, before decoding.
+pub fn read_input_with(path: &Path, max_size: u64, encoding: InputEncoding) -> Result<String> {
+    let bytes = read_bytes(path, max_size)?;
+    match encoding {
+        InputEncoding::Utf8 => String::from_utf8(bytes)
+            .map_err(|error| Aleo2JsonError::InvalidUtf8 { offset: error.utf8_error().valid_up_to() }.into()),
+        // Each Latin-1 byte is the Unicode code point of the same value.
+        InputEncoding::Latin1 => Ok(bytes.into_iter().map(char::from).collect()),
+    }
+}
+
+/// Reads the file at the given path, refusing files larger than `max_size` bytes.
+pub fn read_bytes(path: &Path, max_size: u64) -> Result<Vec<u8>> {
+    let file = std::fs::File::open(path)?;
+    // Check the size upfront, so that oversized files are never read.
+    let size = file.metadata()?.len();
+    if size > max_size {
+        return Err(Aleo2JsonError::InputTooLarge { size, max_size }.into());
+    }
+    // Bound the read as well, in case the file grows, or is not a regular file.
+    let mut bytes = Vec::new();
+    file.take(max_size.saturating_add(1)).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_size {
+        return Err(Aleo2JsonError::InputTooLarge { size: bytes.len() as u64, max_size }.into());
+    }
+    Ok(bytes)
+}
+
+/// Ensures the SHA-256 digest of the given bytes matches the expected hex-encoded digest.
+pub fn verify_sha256(bytes: &[u8], expected: &str) -> Result<(), Aleo2JsonError> {
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    match actual.eq_ignore_ascii_case(expected.trim()) {
+        true => Ok(()),
+        false => Err(Aleo2JsonError::ChecksumMismatch { expected: expected.to_string(), actual }),
+    }
+}
+
+/// Parses the given string into a program, and converts it into JSON with the given options.
+pub fn convert_program(string: &str, options: &ConvertOptions) -> Result<serde_json::Value> {
+    if options.validate {
+        match options.network {
+            NetworkName::Testnet3 => validate_source::<Testnet3>(string)?,
+        }
+    }
+    match options.network {
+        NetworkName::Testnet3 => program_source_to_json(&parse_program::<Testnet3>(string)?, string, options),
+    }
+}
+
+/// Converts the given program, parsed from the given source, into JSON with the given options.
+/// Unlike `program_to_json`, this can emit the source text of each operand (i.e. `--with-raw-operands`), and the
+/// source line of each instruction (i.e. `--with-lines`).
+pub fn program_source_to_json<N: Network>(
+    program: &Program<N>,
+    source: &str,
+    options: &ConvertOptions,
+) -> Result<serde_json::Value> {
+    let mut raw_operands = options.with_raw_operands.then(|| RawOperands::new(source));
+    let mut lines = options.with_lines.then(|| InstructionLines::new(source));
+    let mut visitors = Vec::<&mut dyn NodeVisitor>::new();
+    if let Some(raw_operands) = &mut raw_operands {
+        visitors.push(raw_operands);
+    }
+    if let Some(lines) = &mut lines {
+        visitors.push(lines);
+    }
+    program_to_json_with(program, options, &mut visitors)
+}
+
+/// Converts the given program into JSON with the given options.
+pub fn program_to_json<N: Network>(program: &Program<N>, options: &ConvertOptions) -> Result<serde_json::Value> {
+    program_to_json_with(program, options, &mut [])
+}
+
+/// Returns an iterator over the JSON of each function of the given program, in declaration order, converting each
+/// function lazily, as it is reached, so that the JSON of the functions is never held at once. Each is as in the
+/// `"functions"` of `program.to_json()`, with its opcodes tagged; the transforms across the program (i.e. the
+/// callers of each closure, or the hashes) are not applied.
+pub fn stream_functions<N: Network>(program: &Program<N>) -> impl Iterator<Item = Result<serde_json::Value>> + '_ {
+    program.functions().values().map(|function| {
+        let mut json = function.to_json();
+        tag_opcodes(&mut json, false)?;
+        Ok(json)
+    })
+}
+
+/// Adds the name of the given network to the given JSON object under `"network"`, i.e. `"network": "testnet3"`,
+/// as the meaning of the JSON depends on the network its input was parsed for. Any other JSON is left unchanged.
+pub fn add_network(json: &mut serde_json::Value, network: NetworkName) {
+    if let Some(object) = json.as_object_mut() {
+        object.insert("network".to_string(), serde_json::json!(network.name()));
+    }
+}
+
+/// Converts the given program into JSON with the given options, walking the program JSON with each of the given
+/// visitors in turn, after the built-in transforms (i.e. `--with-hashes`), and before the sections are selected.
+pub fn program_to_json_with<N: Network>(
+    program: &Program<N>,
+    options: &ConvertOptions,
+    visitors: &mut [&mut dyn NodeVisitor],
+) -> Result<serde_json::Value> {
+    // Desugar the program before any conversion, so that each of them (i.e. the hashes) sees the canonical form.
+    let desugared;
+    let program = match options.desugar {
+        true => {
+            desugared = desugar_program(program)?;
+            &desugared
+        }
+        false => program,
+    };
+    // Check the nesting before any conversion recurses into it.
+    if let Some(max_depth) = options.max_depth {
+        check_program_depth(program, max_depth)?;
+    }
+    // The instruction table is a projection of its own, which the other options do not apply to.
+    if options.flatten || options.grep.is_some() {
+        let mut table = flatten_program(program);
+        if let Some(pattern) = &options.grep {
+            grep_rows(&mut table, &Regex::new(pattern)?, options.grep_function.as_deref());
+        }
+        return Ok(table);
+    }
+    // So is the control-flow graph.
+    if options.cfg {
+        return Ok(program_cfg(program));
+    }
+    let mut json = match options.dataflow {
+        true => program_dataflow(program),
+        false => {
+            let mut json = program.to_json();
+            tag_opcodes(&mut json, options.deny_unknown_opcodes)?;
+            add_arity(&mut json);
+            add_crypto_variants(&mut json);
+            add_mapping_effects(&mut json);
+            add_cast_fields(&mut json, program);
+            add_futures(&mut json, program);
+            add_control_flow(&mut json, program);
+            add_random_sources(&mut json, program);
+            add_access_paths::<N>(&mut json);
+            add_destination_types(&mut json, program);
+            add_type_provenance(&mut json, program);
+            if options.expand_types {
+                add_type_expansions(&mut json, program);
+            }
+            if options.annotate_const {
+                add_const_annotations(&mut json, program);
+            }
+            if options.with_cost {
+                add_cost_estimates(&mut json);
+            }
+            if options.with_byte_sizes {
+                add_byte_sizes(&mut json, program)?;
+            }
+            if options.with_layout {
+                add_layouts(&mut json, program);
+            }
+            json["dependencies"] = serde_json::json!(program_dependencies(program));
+            json["record_types"] = record_types(program);
+            json["order"] = serde_json::json!(declaration_order(program));
+            let calls = external_calls(program);
+            json["credit_interactions"] = serde_json::Value::Array(credit_interactions(&calls));
+            json["external_calls"] = serde_json::Value::Array(calls);
+            json["mapping_index"] = serde_json::Value::Array(mapping_index(&json));
+            add_closure_callers(&mut json, program);
+            add_unused_inputs(&mut json, program);
+            add_assertions(&mut json, program);
+            if options.split_literals {
+                split_literals(&mut json);
+            }
+            if options.field_repr == FieldRepr::Montgomery {
+                montgomery_fields::<N>(&mut json);
+            }
+            if options.hex_fields {
+                hex_fields::<N>(&mut json);
+            }
+            if options.native_literals {
+                native_literals(&mut json);
+            }
+            if options.expand_group {
+                expand_groups::<N>(&mut json);
+            }
+            if options.fixed_width_fields {
+                fixed_width_fields::<N>(&mut json);
+            }
+            if options.with_hashes {
+                add_hashes(&mut json, program, options.normalize_before_hash);
+            }
+            if options.with_fingerprint {
+                json["fingerprint"] = serde_json::json!(fingerprint(program, options.network));
+            }
+            for visitor in visitors.iter_mut() {
+                walk_program(&mut json, &mut **visitor);
+            }
+            if options.operand_format == OperandFormat::Strings {
+                operands_to_strings(&mut json);
+            }
+            if options.with_pointers {
+                walk_program(&mut json, &mut Pointers::default());
+            }
+            json
+        }
+    };
+    if options.validate || options.validate_closed {
+        let mut violations = match options.validate {
+            true => validate(program),
+            false => Vec::new(),
+        };
+        if options.validate_closed {
+            violations.extend(validate_closed(program));
+        }
+        json["violations"] = serde_json::Value::Array(violations);
+    }
+    if options.stats {
+        json["stats"] = program_stats(program, options.per_function);
+    }
+    if let Some(import_dir) = &options.import_dir {
+        // Convert the imports with the same options, without inlining their own imports again.
+        let import_options = options.clone().import_dir(None);
+        json["resolved_imports"] = resolve_imports(program, import_dir, &import_options);
+    }
+    if let Some(limit) = options.limit_functions {
+        limit_functions(&mut json, limit);
+    }
+    if options.prune_empty {
+        prune_empty_sections(&mut json);
+    }
+    if !options.only.is_empty() {
+        only_sections(&mut json, &options.only)?;
+    }
+    if !options.rename_keys.is_empty() {
+        rename_sections(&mut json, &options.rename_keys)?;
+    }
+    add_network(&mut json, options.network);
+    if options.jsonl_index {
+        json = instruction_index(&json);
+    }
+    if options.canonicalize {
+        json = canonical_bundle(program, json);
+    }
+    Ok(json)
+}
+
+/// Parses the given string into a program, ensuring the entire string is consumed.
+pub fn parse_program<N: Network>(string: &str) -> Result<Program<N>, Aleo2JsonError> {
+    let (remain, program) = parse_next_program(string, string)?;
+    match remain.is_empty() {
+        true => Ok(program),
+        false => Err(Aleo2JsonError::UnconsumedInput { offset: string.len() - remain.len() }),
+    }
+}
+
+/// Adds the number of bytes of the given source consumed by the parser under `"parser_consumed"`, and the rest of
+/// the source under `"parser_remainder"`, or `null` if the parser consumed all of it. An output that is not an
+/// object, i.e. the instruction table of `--flatten`, is left unchanged.
+pub fn add_parser_boundary(json: &mut serde_json::Value, source: &str, consumed: usize) {
+    if let Some(object) = json.as_object_mut() {
+        let remainder = Some(&source[consumed..]).filter(|remainder| !remainder.is_empty());
+        object.insert("parser_consumed".to_string(), serde_json::json!(consumed));
+        object.insert("parser_remainder".to_string(), serde_json::json!(remainder));
+    }
+}
+
+/// Deserializes the given bytes into a program, as serialized by its `ToBytes`, i.e. as stored in a deployment,
+/// ensuring all of the bytes are consumed.
+pub fn parse_program_bytes<N: Network>(bytes: &[u8]) -> Result<Program<N>, Aleo2JsonError> {
+    let mut remain = bytes;
+    let program =
+        Program::read_le(&mut remain).map_err(|error| Aleo2JsonError::InvalidBytes { message: error.to_string() })?;
+    match remain.is_empty() {
+        true => Ok(program),
+        false => Err(Aleo2JsonError::InvalidBytes { message: format!("{} trailing byte(s)", remain.len()) }),
+    }
+}
+
+/// Deserializes the given bytes into a program, and converts it into JSON with the given options.
+pub fn convert_program_bytes(bytes: &[u8], options: &ConvertOptions) -> Result<serde_json::Value> {
+    match options.network {
+        NetworkName::Testnet3 => program_to_json(&parse_program_bytes::<Testnet3>(bytes)?, options),
+    }
+}
+
+/// Parses the given string into the programs concatenated in it, i.e. separated by blank lines,
+/// failing if any of them fails to parse.
+pub fn parse_programs<N: Network>(string: &str) -> Result<Vec<Program<N>>, Aleo2JsonError> {
+    let mut programs = Vec::new();
+    let mut remain = string;
+    while !remain.is_empty() {
+        let (next, program) = parse_next_program(string, remain)?;
+        programs.push(program);
+        remain = next;
+    }
+    Ok(programs)
+}
+
+/// Runs the given parser on a worker thread, failing if it does not finish within the given timeout, if any.
+/// On a timeout, the worker is left to finish in the background, as a parser cannot be interrupted.
+pub fn parse_with_timeout<T: Send + 'static>(
+    string: &str,
+    timeout: Option<Duration>,
+    parse: fn(&str) -> Result<T, Aleo2JsonError>,
+) -> Result<T, Aleo2JsonError> {
+    let Some(timeout) = timeout else {
+        return parse(string);
+    };
+    let (sender, receiver) = mpsc::channel();
+    let string = string.to_string();
+    std::thread::spawn(move || {
+        // The receiver is gone if the parser timed out, in which case the result is discarded.
+        let _ = sender.send(parse(&string));
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(_) => Err(Aleo2JsonError::Timeout { timeout_ms: timeout.as_millis() }),
+    }
+}
+
+/// Parses the next program from the given remainder of the string, returning the rest of the remainder.
+/// The offset of an error is within the entire string.
+pub(crate) fn parse_next_program<'a, N: Network>(
+    string: &str,
+    remain: &'a str,
+) -> Result<(&'a str, Program<N>), Aleo2JsonError> {
+    // Returns the byte offset of the given remainder within the string.
+    let offset = |remain: &str| string.len() - remain.len();
+
+    match Program::<N>::parse(remain) {
+        Ok((remain, program)) => Ok((remain, program)),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            // The first error is the innermost one, i.e. where the parser actually failed.
+            let (remain, kind) = match error.errors.first() {
+                Some((remain, kind)) => (*remain, format!("{kind:?}")),
+                None => (remain, "unknown error".to_string()),
+            };
+            Err(Aleo2JsonError::Parse { offset: offset(remain), message: kind })
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            Err(Aleo2JsonError::Parse { offset: string.len(), message: "unexpected end of input".to_string() })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::CurrentNetwork, console::network::prelude::ToBytes};
+
+    use serde_json::json;
+    use std::io::Write;
+
+    #[test]
+    fn test_read_input() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"program main.aleo;").unwrap();
+
+        assert_eq!(read_input(file.path(), 18).unwrap(), "program main.aleo;");
+        let error = read_input(file.path(), 17).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Aleo2JsonError>(),
+            Some(Aleo2JsonError::InputTooLarge { size: 18, max_size: 17 })
+        ));
+    }
+
+    #[test]
+    fn test_read_input_encoding() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"// caf\xe9\nprogram main.aleo;").unwrap();
+
+        let error = read_input(file.path(), 64).unwrap_err();
+        assert!(matches!(error.downcast_ref::<Aleo2JsonError>(), Some(Aleo2JsonError::InvalidUtf8 { offset: 6 })));
+        let string = read_input_with(file.path(), 64, InputEncoding::Latin1).unwrap();
+        assert_eq!(string, "// caf\u{e9}\nprogram main.aleo;");
+        // The size is checked before decoding, which may lengthen the input.
+        assert!(read_input_with(file.path(), 26, InputEncoding::Latin1).is_ok());
+    }
+
+    #[test]
+    fn test_convert_program() {
+        let string = "program main.aleo;\n\nfunction main:\n    input r0 as u64.public;\n    add r0 1u64 into r1;\n";
+
+        let json = convert_program(string, &ConvertOptions::new()).unwrap();
+        assert_eq!(json["type"], "ProgramCore");
+
+        let options =
+            ConvertOptions::new().network(NetworkName::Testnet3).split_literals(true).limit_functions(Some(0));
+        let json = convert_program(string, &options).unwrap();
+        assert_eq!(json["functions"], json!({}));
+        assert_eq!(json["total_functions"], 1);
+
+        let json = convert_program(string, &ConvertOptions::new().dataflow(true)).unwrap();
+        assert_eq!(json["type"], "Dataflow");
+
+        assert!(convert_program("program main.aleo;", &ConvertOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_convert_program_bytes() {
+        let string = "program main.aleo;\n\nfunction main:\n    input r0 as u64.public;\n    add r0 1u64 into r1;\n";
+        let bytes = parse_program::<CurrentNetwork>(string).unwrap().to_bytes_le().unwrap();
+        let options = ConvertOptions::new();
+        assert_eq!(convert_program_bytes(&bytes, &options).unwrap(), convert_program(string, &options).unwrap());
+
+        // A truncated or an overlong input is invalid, rather than a parser error.
+        let error = convert_program_bytes(&bytes[..bytes.len() - 1], &options).unwrap_err();
+        assert!(matches!(error.downcast_ref::<Aleo2JsonError>(), Some(Aleo2JsonError::InvalidBytes { .. })));
+        let error = parse_program_bytes::<CurrentNetwork>(&[bytes.as_slice(), &[0]].concat()).unwrap_err();
+        assert_eq!(error.to_string(), "The input is not a valid binary program: 1 trailing byte(s)");
+        assert!(parse_program_bytes::<CurrentNetwork>(string.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_add_parser_boundary() {
+        let source = "program main.aleo;\n\nfunction main:\n    input r0 as u8.public;\n";
+        let mut json = convert_program(source, &ConvertOptions::new()).unwrap();
+        add_parser_boundary(&mut json, source, source.len());
+        assert_eq!(json["parser_consumed"], source.len());
+        assert!(json["parser_remainder"].is_null());
+
+        add_parser_boundary(&mut json, "program main.aleo; }", 18);
+        assert_eq!(json["parser_consumed"], 18);
+        assert_eq!(json["parser_remainder"], " }");
+
+        // An array has no room for the boundary.
+        let mut json = json!([]);
+        add_parser_boundary(&mut json, source, 0);
+        assert_eq!(json, json!([]));
+    }
+
+    #[test]
+    fn test_verify_sha256() {
+        const DIGEST: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+        assert!(verify_sha256(b"abc", DIGEST).is_ok());
+        assert!(verify_sha256(b"abc", &DIGEST.to_uppercase()).is_ok());
+        assert!(matches!(
+            verify_sha256(b"abd", DIGEST),
+            Err(Aleo2JsonError::ChecksumMismatch { actual, .. }) if actual != DIGEST
+        ));
+    }
+
+    #[test]
+    fn test_parse_programs() {
+        let first = "program first.aleo;\n\nfunction main:\n    input r0 as u64.public;\n    add r0 1u64 into r1;\n";
+        let second = "import first.aleo;\n\nprogram second.aleo;\n\nfunction main:\n    input r0 as u64.public;\n";
+        let second = format!("{second}    add r0 r0 into r1;\n");
+        let string = format!("{first}\n\n{second}");
+
+        let programs = parse_programs::<CurrentNetwork>(&string).unwrap();
+        let ids = programs.iter().map(|program| program.id().to_string()).collect::<Vec<_>>();
+        assert_eq!(ids, ["first.aleo", "second.aleo"]);
+        assert!(matches!(
+            parse_program::<CurrentNetwork>(&string),
+            Err(Aleo2JsonError::UnconsumedInput { offset }) if offset == string.find("import").unwrap()
+        ));
+
+        // A program that fails to parse is reported at its offset in the entire input.
+        let string = format!("{first}\nprogram broken.aleo;\n\nfunction main:\n    frobnicate r0;\n");
+        assert!(matches!(
+            parse_programs::<CurrentNetwork>(&string),
+            Err(Aleo2JsonError::Parse { offset, .. }) if offset > first.len()
+        ));
+    }
+
+    #[test]
+    fn test_parse_with_timeout() {
+        let string = "program main.aleo;\n\nfunction main:\n    input r0 as u64.public;\n    add r0 1u64 into r1;\n";
+        let timeout = Some(Duration::from_secs(60));
+        assert!(parse_with_timeout(string, timeout, parse_program::<CurrentNetwork>).is_ok());
+        assert!(parse_with_timeout(string, None, parse_program::<CurrentNetwork>).is_ok());
+        assert!(matches!(
+            parse_with_timeout("program", timeout, parse_program::<CurrentNetwork>),
+            Err(Aleo2JsonError::Parse { .. })
+        ));
+
+        let slow = |_: &str| {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok(())
+        };
+        assert!(matches!(
+            parse_with_timeout(string, Some(Duration::from_millis(10)), slow),
+            Err(Aleo2JsonError::Timeout { timeout_ms: 10 })
+        ));
+    }
+
+    #[test]
+    fn test_external_record_input() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+
+program wrapper.aleo;
+
+function unwrap:
+    input r0 as credits.aleo/credits.record;
+    input r1 as u64.private;
+    call credits.aleo/split r0 r1 into r2 r3;
+    output r2 as credits.aleo/credits.record;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let input = &json["functions"]["unwrap"]["inputs"][0]["value_type"];
+        assert_eq!(input["vtype"], "ExternalRecord");
+        assert_eq!(input["value"], json!({ "program": "credits.aleo", "record": "credits" }));
+        // The visibility of each function input is emitted as declared.
+        let inputs = json["functions"]["unwrap"]["inputs"].as_array().unwrap();
+        let visibilities = inputs.iter().map(|input| input["visibility"].clone()).collect::<Vec<_>>();
+        assert_eq!(visibilities, [json!("external_record"), json!("private")]);
+    }
+
+    #[test]
+    fn test_stream_functions() {
+        let program = parse_program::<CurrentNetwork>(
+            "program stream.aleo;\n\nfunction first:\n    input r0 as u8.public;\n    add r0 r0 into r1;\n\n\
+             function second:\n    input r0 as u8.public;\n",
+        )
+        .unwrap();
+        let mut json = program.to_json();
+        tag_opcodes(&mut json, false).unwrap();
+
+        let mut functions = stream_functions(&program);
+        assert_eq!(functions.next().unwrap().unwrap(), json["functions"]["first"]);
+        assert_eq!(functions.next().unwrap().unwrap(), json["functions"]["second"]);
+        assert!(functions.next().is_none());
+    }
+
+    #[test]
+    fn test_network() {
+        let source = "program network.aleo;\n\nfunction main:\n    input r0 as u8.public;\n";
+        for options in [ConvertOptions::new(), ConvertOptions::new().only(vec!["functions".to_string()])] {
+            let json = convert_program(source, &options.network(NetworkName::Testnet3)).unwrap();
+            assert_eq!(json["network"], "testnet3");
+        }
+        // The instruction table has no room for the network.
+        let json = convert_program(source, &ConvertOptions::new().flatten(true)).unwrap();
+        assert!(json.is_array());
+    }
+
+    #[test]
+    fn test_instruction_uid() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program uid.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function transfer_public:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    add r1 1u64 into r2;
+    output r2 as u64.public;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let instructions = &json["functions"]["transfer_public"]["instructions"];
+        assert_eq!(instructions[0]["index"], 0);
+        assert_eq!(instructions[0]["uid"], "transfer_public#0");
+        assert_eq!(instructions[1]["index"], 1);
+        assert_eq!(instructions[1]["uid"], "transfer_public#1");
+        assert_eq!(json["closures"]["twice"]["instructions"][0]["uid"], "twice#0");
+        // Only functions are entrypoints.
+        assert_eq!(json["functions"]["transfer_public"]["is_entrypoint"], true);
+        assert_eq!(json["closures"]["twice"]["is_entrypoint"], false);
+        // The UIDs are deterministic.
+        assert_eq!(program.to_json(), json);
+    }
+
+    #[test]
+    fn test_mapping_access() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program vault.aleo;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+function withdraw:
+    input r0 as address.public;
+    input r1 as u64.public;
+    async withdraw r0 r1 into r2;
+    output r2 as vault.aleo/withdraw.future;
+
+finalize withdraw:
+    input r0 as address.public;
+    input r1 as u64.public;
+    contains balances[r0] into r2;
+    get.or_use balances[r0] 0u64 into r3;
+    sub r3 r1 into r4;
+    set r4 into balances[r0];
+    remove balances[r0];
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let commands = &json["functions"]["withdraw"]["finalize_logic"]["commands"];
+        let access = |index: usize| {
+            (commands[index]["value"]["access"].clone(), commands[index]["value"]["mapping_name"].clone())
+        };
+        assert_eq!(access(0), (json!("read"), json!("balances")));
+        assert_eq!(access(1), (json!("read"), json!("balances")));
+        assert_eq!(access(3), (json!("write"), json!("balances")));
+        assert_eq!(access(4), (json!("write"), json!("balances")));
+        // Other commands are left untouched.
+        assert_eq!(commands[2]["value"].get("access"), None);
+    }
+
+    #[test]
+    fn test_record_header_and_data() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program token.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+    memo as field.public;
+
+function mint:
+    input r0 as u64.private;
+    cast self.caller r0 0field into r1 as token.record;
+    output r1 as token.record;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let record = &json["records"]["token"];
+        assert_eq!(record["header"], json!({ "owner": { "type": "address", "visibility": "private" } }));
+        let entries = record["data"].as_object().unwrap().keys().collect::<Vec<_>>();
+        assert_eq!(entries, ["amount", "memo"]);
+    }
+
+    #[test]
+    fn test_cast_to() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program casts.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function main:
+    input r0 as u64.private;
+    input r1 as group.private;
+    cast 1field 2field into r2 as point;
+    cast self.caller r0 into r3 as token.record;
+    cast r1 into r4 as group.y;
+    cast.lossy r0 into r5 as u8;
+    output r3 as token.record;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let cast_to = |index: usize| json["functions"]["main"]["instructions"][index]["value"]["cast_to"].clone();
+        assert_eq!(cast_to(0), "point");
+        assert_eq!(cast_to(1), "token.record");
+        assert_eq!(cast_to(2), "group.y");
+        assert_eq!(cast_to(3), "u8");
+    }
+
+    #[test]
+    fn test_operand_kind() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program kinds.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function main:
+    input r0 as token.record;
+    add r0.amount 1u64 into r1;
+    is.eq r0.owner self.caller into r2;
+    output r1 as u64.private;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let operand = |index: usize, operand: usize| {
+            json["functions"]["main"]["instructions"][index]["value"]["operands"][operand].clone()
+        };
+        assert_eq!(operand(0, 0)["kind"], "accessor");
+        assert_eq!(operand(0, 0)["str"], "r0.amount");
+        assert_eq!(operand(0, 1)["kind"], "literal");
+        assert_eq!(operand(1, 1)["kind"], "builtin");
+        assert_eq!(operand(1, 1)["name"], "self.caller");
+        assert_eq!(json["functions"]["main"]["outputs"][0]["operand"]["kind"], "register");
+    }
+
+    #[test]
+    fn test_operand_order() {
+        let string = r"
+program order.aleo;
+
+function main:
+    input r0 as u64.public;
+    input r1 as boolean.public;
+    sub r0 2u64 into r2;
+    sub 2u64 r0 into r3;
+    div r0 3u64 into r4;
+    gt 5u64 r0 into r5;
+    shl r0 1u8 into r6;
+    pow r0 2u8 into r7;
+    ternary r1 r0 1u64 into r8;
+";
+        // The operands of each instruction are emitted as an array in source order, with any option.
+        for options in [ConvertOptions::new(), ConvertOptions::new().split_literals(true).native_literals(true)] {
+            let json = convert_program(string, &options).unwrap();
+            let instructions = json["functions"]["main"]["instructions"].as_array().unwrap();
+            let operands = instructions
+                .iter()
+                .map(|instruction| {
+                    let operands = instruction["value"]["operands"].as_array().unwrap();
+                    operands.iter().map(|operand| operand["str"].as_str().unwrap().to_string()).collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let expected = [
+                vec!["r0", "2u64"],
+                vec!["2u64", "r0"],
+                vec!["r0", "3u64"],
+                vec!["5u64", "r0"],
+                vec!["r0", "1u8"],
+                vec!["r0", "2u8"],
+                vec!["r1", "r0", "1u64"],
+            ];
+            assert_eq!(operands, expected);
+        }
+    }
+
+    #[test]
+    fn test_instruction_destination() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program destinations.aleo;
+
+closure pair:
+    input r0 as u8;
+    add r0 r0 into r1;
+    output r0 as u8;
+    output r1 as u8;
+
+function main:
+    input r0 as u8.private;
+    call pair r0 into r1 r2;
+    assert.eq r1 r0;
+    output r2 as u8.private;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let destination = |index: usize| json["functions"]["main"]["instructions"][index]["destination"].clone();
+        assert_eq!(destination(0), serde_json::json!(["r1", "r2"]));
+        assert_eq!(destination(1), serde_json::Value::Null);
+        // The operation of an assertion has a null destination, as for the instruction.
+        let assertion = &json["functions"]["main"]["instructions"][1]["value"];
+        assert_eq!(assertion.get("destination"), Some(&serde_json::Value::Null));
+        assert_eq!(assertion["operands"].as_array().map(Vec::len), Some(2));
+        assert_eq!(json["closures"]["pair"]["instructions"][0]["destination"], "r1");
+    }
+
+    #[test]
+    fn test_finalize_function() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program counter.aleo;
+
+mapping counts:
+    key as address.public;
+    value as u64.public;
+
+function increment:
+    async increment self.caller into r0;
+    output r0 as counter.aleo/increment.future;
+
+finalize increment:
+    input r0 as address.public;
+    get.or_use counts[r0] 0u64 into r1;
+    add r1 1u64 into r2;
+    set r2 into counts[r0];
+
+function noop:
+    input r0 as u8.private;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let function = &json["functions"]["increment"];
+        assert_eq!(function["finalize_logic"]["function"], function["name"]);
+        assert_eq!(function["finalize_logic"]["function"], "increment");
+        // Functions without finalize logic are unaffected.
+        assert!(json["functions"]["noop"]["finalize_logic"].is_null());
+    }
+
+    #[test]
+    fn test_stats_with_limit_functions() {
+        let program = parse_program::<CurrentNetwork>(
+            "program limited.aleo;\n\nfunction first:\n    input r0 as u8.public;\n\nfunction second:\n    input r0 as u8.public;\n",
+        )
+        .unwrap();
+
+        // The statistics cover every function, even those that are not emitted.
+        let options = ConvertOptions::new().stats(true).per_function(true).limit_functions(Some(1));
+        let json = program_to_json(&program, &options).unwrap();
+        assert_eq!(json["functions"].as_object().unwrap().len(), 1);
+        assert_eq!(json["stats"]["functions"], 2);
+        assert_eq!(json["stats"]["per_function"].as_object().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::opcodes::opcode_of,
+    console::network::{prelude::ToBytes, Network},
+};
+use snarkvm_synthesizer_program::Program;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// The weight of an instruction opcode missing from [`OPCODE_WEIGHTS`].
+pub const DEFAULT_OPCODE_WEIGHT: u64 = 1;
+
+/// The rough proving cost of each instruction opcode, relative to an addition. These are a comparative heuristic,
+/// not constraint counts: tune them here. Keep this table in sync with the instruction opcodes.
+#[rustfmt::skip]
+pub const OPCODE_WEIGHTS: &[(&str, u64)] = &[
+    ("abs", 2), ("abs.w", 2), ("add", 1), ("add.w", 1), ("and", 1), ("div", 10), ("div.w", 10), ("double", 1),
+    ("gt", 5), ("gte", 5), ("inv", 2), ("lt", 5), ("lte", 5), ("mod", 10), ("mul", 2), ("mul.w", 2), ("nand", 1),
+    ("neg", 1), ("nor", 1), ("not", 1), ("or", 1), ("pow", 50), ("pow.w", 50), ("rem", 10), ("rem.w", 10),
+    ("shl", 10), ("shl.w", 10), ("shr", 10), ("shr.w", 10), ("square", 2), ("sqrt", 10), ("sub", 1), ("sub.w", 1),
+    ("xor", 1), ("ternary", 1),
+    ("assert.eq", 1), ("assert.neq", 1), ("is.eq", 1), ("is.neq", 1),
+    // The cost of the callee, and of the finalize logic, is not included.
+    ("call", 1), ("async", 1),
+    ("cast", 2), ("cast.lossy", 2),
+    ("commit.bhp256", 200), ("commit.bhp512", 400), ("commit.bhp768", 600), ("commit.bhp1024", 800),
+    ("commit.ped64", 100), ("commit.ped128", 200),
+    ("hash.bhp256", 200), ("hash.bhp512", 400), ("hash.bhp768", 600), ("hash.bhp1024", 800),
+    ("hash.keccak256", 3000), ("hash.keccak384", 3000), ("hash.keccak512", 3000),
+    ("hash.ped64", 100), ("hash.ped128", 200),
+    ("hash.psd2", 50), ("hash.psd4", 100), ("hash.psd8", 200),
+    ("hash.sha3_256", 3000), ("hash.sha3_384", 3000), ("hash.sha3_512", 3000),
+    ("hash_many.psd2", 50), ("hash_many.psd4", 100), ("hash_many.psd8", 200),
+    ("sign.verify", 500),
+];
+
+/// Returns the weight of the given instruction opcode, i.e. `1` for `add`.
+pub fn opcode_weight(opcode: &str) -> u64 {
+    OPCODE_WEIGHTS.iter().find(|(name, _)| *name == opcode).map_or(DEFAULT_OPCODE_WEIGHT, |(_, weight)| *weight)
+}
+
+/// Adds the estimated proving cost of each function and closure under `"cost_estimate"`, i.e. `"cost_estimate": 203`,
+/// as the sum of the weights of its instructions. Only meant to rank the functions of a program against each other.
+pub fn add_cost_estimates(json: &mut Value) {
+    for section in ["closures", "functions"] {
+        let Some(functions) = json.get_mut(section).and_then(Value::as_object_mut) else {
+            continue;
+        };
+        for function in functions.values_mut() {
+            let instructions = function["instructions"].as_array().map(Vec::as_slice).unwrap_or_default();
+            let cost = instructions
+                .iter()
+                .filter_map(|instruction| instruction["str"].as_str())
+                .map(|instruction| opcode_weight(opcode_of(instruction)))
+                .sum::<u64>();
+            function["cost_estimate"] = json!(cost);
+        }
+    }
+}
+
+/// Adds the length of the `ToBytes` serialization of each function and closure under `"bytes_len"`, i.e.
+/// `"bytes_len": 86`, including its finalize logic, and of the whole program at the top level, for size budgeting.
+/// The program also serializes its header, and its other definitions, so its length exceeds the sum of the others.
+pub fn add_byte_sizes<N: Network>(json: &mut Value, program: &Program<N>) -> Result<()> {
+    for (name, closure) in program.closures() {
+        json["closures"][name.to_string()]["bytes_len"] = json!(closure.to_bytes_le()?.len());
+    }
+    for (name, function) in program.functions() {
+        json["functions"][name.to_string()]["bytes_len"] = json!(function.to_bytes_le()?.len());
+    }
+    json["bytes_len"] = json!(program.to_bytes_le()?.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{parse_program, CurrentNetwork},
+        console::program::Identifier,
+    };
+
+    use snarkvm_synthesizer_program::Instruction;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_every_instruction_is_weighted() {
+        for opcode in Instruction::<CurrentNetwork>::OPCODES {
+            let opcode = opcode.to_string();
+            assert!(OPCODE_WEIGHTS.iter().any(|(name, _)| *name == opcode), "Missing a weight for '{opcode}'");
+        }
+    }
+
+    #[test]
+    fn test_add_cost_estimates() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program costs.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function cheap:
+    input r0 as u64.public;
+    add r0 1u64 into r1;
+    call twice r1 into r2;
+
+function expensive:
+    input r0 as field.private;
+    hash.bhp256 r0 into r1 as field;
+    mul r1 r1 into r2;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_cost_estimates(&mut json);
+        assert_eq!(json["closures"]["twice"]["cost_estimate"], 1);
+        assert_eq!(json["functions"]["cheap"]["cost_estimate"], 2);
+        assert_eq!(json["functions"]["expensive"]["cost_estimate"], 202);
+    }
+
+    #[test]
+    fn test_add_byte_sizes() {
+        let source = r"
+program sizes.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function short:
+    input r0 as u64.public;
+
+function long:
+    input r0 as u64.public;
+    add r0 1u64 into r1;
+    call twice r1 into r2;
+    output r2 as u64.public;
+";
+        let program = parse_program::<CurrentNetwork>(source).unwrap();
+        let mut json = program.to_json();
+        add_byte_sizes(&mut json, &program).unwrap();
+
+        let function = program.get_function(&Identifier::from_str("long").unwrap()).unwrap();
+        assert_eq!(json["functions"]["long"]["bytes_len"], function.to_bytes_le().unwrap().len());
+        assert_eq!(json["bytes_len"], program.to_bytes_le().unwrap().len());
+        let bytes_len = |section: &str, name: &str| json[section][name]["bytes_len"].as_u64().unwrap();
+        assert!(bytes_len("functions", "short") < bytes_len("functions", "long"));
+        let sum = bytes_len("closures", "twice") + bytes_len("functions", "short") + bytes_len("functions", "long");
+        assert!(sum < json["bytes_len"].as_u64().unwrap());
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{network::Network, program::Register};
+use snarkvm_synthesizer_program::{Command, CommandTrait, Instruction, InstructionTrait, Operand, Program};
+
+use serde_json::json;
+
+/// The registers read and written by a single instruction or command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataflowEntry<N: Network> {
+    /// The index of the statement within its block.
+    index: usize,
+    /// The registers read by the statement, in order of first use.
+    reads: Vec<Register<N>>,
+    /// The registers written by the statement.
+    writes: Vec<Register<N>>,
+}
+
+impl<N: Network> DataflowEntry<N> {
+    /// Initializes the dataflow entry for the given instruction.
+    pub fn from_instruction(index: usize, instruction: &Instruction<N>) -> Self {
+        Self { index, reads: registers_read(instruction.operands()), writes: instruction.destinations() }
+    }
+
+    /// Initializes the dataflow entry for the given finalize command.
+    pub fn from_command(index: usize, command: &Command<N>) -> Self {
+        Self { index, reads: registers_read(&command.operands()), writes: command.destinations() }
+    }
+
+    /// Returns the index of the statement within its block.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the registers read by the statement.
+    pub fn reads(&self) -> &[Register<N>] {
+        &self.reads
+    }
+
+    /// Returns the registers written by the statement.
+    pub fn writes(&self) -> &[Register<N>] {
+        &self.writes
+    }
+
+    /// Returns the dataflow entry as JSON.
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "index": self.index,
+            "reads": self.reads.iter().map(|register| register.to_string()).collect::<Vec<_>>(),
+            "writes": self.writes.iter().map(|register| register.to_string()).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Returns the registers read by the given operands, in order of first use.
+/// A register access (i.e. `r0.owner`) reads the register it accesses (i.e. `r0`).
+fn registers_read<N: Network>(operands: &[Operand<N>]) -> Vec<Register<N>> {
+    let mut reads = Vec::new();
+    for operand in operands {
+        if let Operand::Register(register) = operand {
+            let register = Register::Locator(register.locator());
+            if !reads.contains(&register) {
+                reads.push(register);
+            }
+        }
+    }
+    reads
+}
+
+/// Adds the input registers of each function and closure that none of its instructions or outputs reads under
+/// `"unused_inputs"`, i.e. `"unused_inputs": ["r1"]`. An input passed on to the finalize logic is read by the
+/// `async` instruction passing it, so it is used, even if only the finalize logic uses it.
+pub fn add_unused_inputs<N: Network>(json: &mut serde_json::Value, program: &Program<N>) {
+    let closures = program.closures().iter().map(|(name, closure)| {
+        let inputs = closure.inputs().iter().map(|input| input.register().clone()).collect::<Vec<_>>();
+        let outputs = closure.outputs().iter().map(|output| output.operand().clone()).collect::<Vec<_>>();
+        ("closures", name, inputs, outputs, closure.instructions())
+    });
+    let functions = program.functions().iter().map(|(name, function)| {
+        let inputs = function.inputs().iter().map(|input| input.register().clone()).collect::<Vec<_>>();
+        let outputs = function.outputs().iter().map(|output| output.operand().clone()).collect::<Vec<_>>();
+        ("functions", name, inputs, outputs, function.instructions())
+    });
+    for (section, name, inputs, outputs, instructions) in closures.chain(functions) {
+        let mut reads = registers_read(&outputs);
+        for (index, instruction) in instructions.iter().enumerate() {
+            reads.extend_from_slice(DataflowEntry::from_instruction(index, instruction).reads());
+        }
+        let unused = inputs.iter().filter(|input| !reads.contains(input)).map(ToString::to_string).collect::<Vec<_>>();
+        if let Some(function) = json.get_mut(section).and_then(|section| section.get_mut(name.to_string())) {
+            function["unused_inputs"] = json!(unused);
+        }
+    }
+}
+
+/// Returns the dataflow of every closure, function, and finalize block in the program as JSON.
+pub fn program_dataflow<N: Network>(program: &Program<N>) -> serde_json::Value {
+    let mut j_closures = serde_json::Map::new();
+    for (name, closure) in program.closures() {
+        let j_instructions = closure
+            .instructions()
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| DataflowEntry::from_instruction(index, instruction).to_json())
+            .collect::<Vec<_>>();
+        j_closures.insert(name.to_string(), json!({ "instructions": j_instructions }));
+    }
+
+    let mut j_functions = serde_json::Map::new();
+    for (name, function) in program.functions() {
+        let j_instructions = function
+            .instructions()
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| DataflowEntry::from_instruction(index, instruction).to_json())
+            .collect::<Vec<_>>();
+        let j_finalize = match function.finalize_logic() {
+            Some(finalize) => json!(finalize
+                .commands()
+                .iter()
+                .enumerate()
+                .map(|(index, command)| DataflowEntry::from_command(index, command).to_json())
+                .collect::<Vec<_>>()),
+            None => serde_json::Value::Null,
+        };
+        j_functions.insert(name.to_string(), json!({ "instructions": j_instructions, "finalize": j_finalize }));
+    }
+
+    json!({
+        "type": "Dataflow",
+        "program": program.id().to_string(),
+        "closures": j_closures,
+        "functions": j_functions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    const PROGRAM: &str = r"
+program dataflow.aleo;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+function transfer:
+    input r0 as address.public;
+    input r1 as u64.public;
+    add r1 r1 into r2;
+    is.eq r2 r1 into r3;
+    assert.eq r3 true;
+    async transfer r0 r2 into r4;
+    output r4 as dataflow.aleo/transfer.future;
+
+finalize transfer:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use balances[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into balances[r0];
+";
+
+    #[test]
+    fn test_instruction_dataflow() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let function = program.get_function(&"transfer".parse().unwrap()).unwrap();
+
+        let entries = function
+            .instructions()
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| DataflowEntry::from_instruction(index, instruction))
+            .collect::<Vec<_>>();
+
+        // `add r1 r1 into r2` reads `r1` once.
+        assert_eq!(entries[0].to_json(), json!({ "index": 0, "reads": ["r1"], "writes": ["r2"] }));
+        assert_eq!(entries[1].to_json(), json!({ "index": 1, "reads": ["r2", "r1"], "writes": ["r3"] }));
+        // `assert.eq` has no destination.
+        assert_eq!(entries[2].to_json(), json!({ "index": 2, "reads": ["r3"], "writes": [] }));
+        assert_eq!(entries[3].to_json(), json!({ "index": 3, "reads": ["r0", "r2"], "writes": ["r4"] }));
+    }
+
+    #[test]
+    fn test_add_unused_inputs() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program unused.aleo;
+
+closure first:
+    input r0 as u8;
+    input r1 as u8;
+    add r0 r0 into r2;
+    output r2 as u8;
+
+function main:
+    input r0 as u8.public;
+    input r1 as u8.public;
+    input r2 as u8.public;
+    add r0 r0 into r3;
+    output r3 as u8.public;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_unused_inputs(&mut json, &program);
+        assert_eq!(json["closures"]["first"]["unused_inputs"], json!(["r1"]));
+        assert_eq!(json["functions"]["main"]["unused_inputs"], json!(["r1", "r2"]));
+
+        // An input only used by the finalize logic is passed to it, so it is used.
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let mut json = program.to_json();
+        add_unused_inputs(&mut json, &program);
+        assert_eq!(json["functions"]["transfer"]["unused_inputs"], json!([]));
+    }
+
+    #[test]
+    fn test_program_dataflow() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let dataflow = program_dataflow(&program);
+
+        assert_eq!(dataflow["program"], "dataflow.aleo");
+        assert_eq!(
+            dataflow["functions"]["transfer"]["finalize"],
+            json!([
+                { "index": 0, "reads": ["r0"], "writes": ["r2"] },
+                { "index": 1, "reads": ["r2", "r1"], "writes": ["r3"] },
+                { "index": 2, "reads": ["r3", "r0"], "writes": [] },
+            ])
+        );
+    }
+}
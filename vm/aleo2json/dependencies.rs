@@ -0,0 +1,251 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::network::Network;
+use snarkvm_synthesizer_program::{CallOperator, Instruction, Program};
+
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+
+/// Returns the IDs of the external programs the given program depends on, deduplicated and sorted, i.e.
+/// `["credits.aleo", "token.aleo"]`. These are the imported programs, and the programs referenced by a locator,
+/// i.e. `token.aleo/token.record` or `call token.aleo/transfer`, whether imported or not.
+pub fn program_dependencies<N: Network>(program: &Program<N>) -> Vec<String> {
+    let mut dependencies = program.imports().keys().map(|program_id| program_id.to_string()).collect::<BTreeSet<_>>();
+
+    // A locator is the only construct with a program ID followed by a '/', so scan the printed program for them.
+    let string = program.to_string();
+    for (index, _) in string.match_indices(".aleo/") {
+        let start = string[..index]
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .map_or(0, |position| position + 1);
+        if start < index {
+            dependencies.insert(format!("{}.aleo", &string[start..index]));
+        }
+    }
+
+    // A program does not depend on itself.
+    dependencies.remove(&program.id().to_string());
+    dependencies.into_iter().collect()
+}
+
+/// Returns the calls of the given program into other programs, deduplicated and sorted, i.e.
+/// `[{ "from": "pay", "program": "token.aleo", "function": "transfer" }]`, where `from` is the calling function.
+/// An `async` always schedules the finalize of its own function, so only a `call` crosses into another program.
+pub fn external_calls<N: Network>(program: &Program<N>) -> Vec<Value> {
+    let mut calls = BTreeSet::new();
+    for (name, function) in program.functions() {
+        for instruction in function.instructions() {
+            let Instruction::Call(call) = instruction else {
+                continue;
+            };
+            if let CallOperator::Locator(locator) = call.operator() {
+                if locator.program_id() != program.id() {
+                    calls.insert((name.to_string(), locator.program_id().to_string(), locator.resource().to_string()));
+                }
+            }
+        }
+    }
+    calls
+        .into_iter()
+        .map(|(from, program, function)| json!({ "from": from, "program": program, "function": function }))
+        .collect()
+}
+
+/// The functions of `credits.aleo` that move credits, or stake them.
+pub const CREDITS_FUNCTIONS: &[&str] = &[
+    "bond_public",
+    "unbond_public",
+    "unbond_delegator_as_validator",
+    "claim_unbond_public",
+    "set_validator_state",
+    "transfer_public",
+    "transfer_public_to_private",
+    "transfer_private",
+    "transfer_private_to_public",
+    "join",
+    "split",
+    "fee_public",
+    "fee_private",
+];
+
+/// Returns the calls into `credits.aleo` among the given external calls, as returned by `external_calls`, i.e.
+/// `[{ "function": "pay", "operation": "transfer_public" }]`, where `function` is the calling function.
+/// A call to a function of `credits.aleo` missing from `CREDITS_FUNCTIONS` is not a credit interaction.
+pub fn credit_interactions(external_calls: &[Value]) -> Vec<Value> {
+    external_calls
+        .iter()
+        .filter(|call| call["program"] == "credits.aleo")
+        .filter(|call| CREDITS_FUNCTIONS.iter().any(|function| call["function"] == *function))
+        .map(|call| json!({ "function": call["from"], "operation": call["function"] }))
+        .collect()
+}
+
+/// Adds the functions and closures calling each closure of the given program under `"callers"`, deduplicated and
+/// sorted, i.e. `"callers": ["main", "transfer"]`. As a closure is inlined at each call site, this is its reach.
+pub fn add_closure_callers<N: Network>(json: &mut Value, program: &Program<N>) {
+    let mut callers = program.closures().keys().map(|name| (*name, BTreeSet::new())).collect::<HashMap<_, _>>();
+    let functions = program.functions().iter().map(|(name, function)| (name, function.instructions()));
+    let closures = program.closures().iter().map(|(name, closure)| (name, closure.instructions()));
+    for (name, instructions) in functions.chain(closures) {
+        for instruction in instructions {
+            let Instruction::Call(call) = instruction else {
+                continue;
+            };
+            let callee = match call.operator() {
+                CallOperator::Resource(resource) => resource,
+                CallOperator::Locator(locator) if locator.program_id() == program.id() => locator.resource(),
+                CallOperator::Locator(_) => continue,
+            };
+            if let Some(callers) = callers.get_mut(callee) {
+                callers.insert(name.to_string());
+            }
+        }
+    }
+    for (name, callers) in callers {
+        if let Some(closure) = json.get_mut("closures").and_then(|closures| closures.get_mut(name.to_string())) {
+            closure["callers"] = json!(callers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::CurrentNetwork;
+
+    #[test]
+    fn test_program_dependencies() {
+        let program = crate::aleo2json::parse_program::<CurrentNetwork>(
+            r"
+import token.aleo;
+import audit.aleo;
+program deps.aleo;
+
+function pay:
+    input r0 as token.aleo/coin.record;
+    input r1 as u64.public;
+    call wallet.aleo/pay r1 into r2;
+    call token.aleo/burn r0 into r3;
+    async pay r1 into r4;
+    output r4 as deps.aleo/pay.future;
+
+finalize pay:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+",
+        )
+        .unwrap();
+
+        assert_eq!(program_dependencies(&program), ["audit.aleo", "token.aleo", "wallet.aleo"]);
+    }
+
+    #[test]
+    fn test_external_calls() {
+        let program = crate::aleo2json::parse_program::<CurrentNetwork>(
+            r"
+import token.aleo;
+program calls.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function pay:
+    input r0 as u64.public;
+    call token.aleo/mint r0 into r1;
+    call token.aleo/burn r0 into r2;
+    call token.aleo/burn r0 into r3;
+    call twice r0 into r4;
+
+function audit:
+    input r0 as u64.public;
+    call token.aleo/burn r0 into r1;
+",
+        )
+        .unwrap();
+
+        assert_eq!(external_calls(&program), [
+            json!({ "from": "audit", "program": "token.aleo", "function": "burn" }),
+            json!({ "from": "pay", "program": "token.aleo", "function": "burn" }),
+            json!({ "from": "pay", "program": "token.aleo", "function": "mint" }),
+        ]);
+    }
+
+    #[test]
+    fn test_credit_interactions() {
+        let program = crate::aleo2json::parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+import token.aleo;
+program payments.aleo;
+
+function pay:
+    input r0 as address.public;
+    input r1 as u64.public;
+    call credits.aleo/transfer_public r0 r1 into r2;
+    call token.aleo/transfer_public r0 r1 into r3;
+
+function stake:
+    input r0 as address.public;
+    input r1 as u64.public;
+    call credits.aleo/bond_public r0 r1 into r2;
+",
+        )
+        .unwrap();
+
+        assert_eq!(credit_interactions(&external_calls(&program)), [
+            json!({ "function": "pay", "operation": "transfer_public" }),
+            json!({ "function": "stake", "operation": "bond_public" }),
+        ]);
+    }
+
+    #[test]
+    fn test_add_closure_callers() {
+        let program = crate::aleo2json::parse_program::<CurrentNetwork>(
+            r"
+program closures.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+closure quadruple:
+    input r0 as u64;
+    call twice r0 into r1;
+    call twice r1 into r2;
+    output r2 as u64;
+
+closure unused:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function main:
+    input r0 as u64.public;
+    call quadruple r0 into r1;
+    call twice r1 into r2;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_closure_callers(&mut json, &program);
+        assert_eq!(json["closures"]["twice"]["callers"], json!(["main", "quadruple"]));
+        assert_eq!(json["closures"]["quadruple"]["callers"], json!(["main"]));
+        assert_eq!(json["closures"]["unused"]["callers"], json!([]));
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::Aleo2JsonError,
+    console::{
+        network::Network,
+        program::{Entry, Identifier, Plaintext, PlaintextType, Record},
+    },
+};
+use snarkvm_synthesizer_program::Program;
+
+use std::collections::HashMap;
+
+/// The default maximum nesting depth of a type or a value, i.e. of a struct in a struct.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Returns the deepest path into the given type, as one segment per level, i.e. `[".inner", "[]"]`,
+/// given the deepest path into each struct declared before it.
+fn type_path<N: Network>(
+    plaintext_type: &PlaintextType<N>,
+    structs: &HashMap<Identifier<N>, Vec<String>>,
+) -> Vec<String> {
+    match plaintext_type {
+        PlaintextType::Literal(_) => Vec::new(),
+        PlaintextType::Struct(name) => structs.get(name).cloned().unwrap_or_default(),
+        PlaintextType::Array(array_type) => {
+            let mut path = vec!["[]".to_string()];
+            path.extend(type_path(array_type.next_element_type(), structs));
+            path
+        }
+    }
+}
+
+/// Returns the deepest path into the given members, i.e. `[".inner", "[]"]`.
+fn members_path<'a, N: Network>(
+    members: impl Iterator<Item = (&'a Identifier<N>, &'a PlaintextType<N>)>,
+    structs: &HashMap<Identifier<N>, Vec<String>>,
+) -> Vec<String> {
+    let paths = members.map(|(name, plaintext_type)| {
+        let mut path = vec![format!(".{name}")];
+        path.extend(type_path(plaintext_type, structs));
+        path
+    });
+    paths.max_by_key(Vec::len).unwrap_or_default()
+}
+
+/// Fails if the nesting of any struct, record, or mapping type of the given program exceeds the given depth,
+/// reporting its deepest path, i.e. `outer.inner[].x`. Each struct member and array element is one level.
+/// As each struct is declared after the structs it uses, the path into each is computed once, in declaration order.
+pub fn check_program_depth<N: Network>(program: &Program<N>, max_depth: usize) -> Result<(), Aleo2JsonError> {
+    let check = |name: String, path: Vec<String>| match path.len() > max_depth {
+        true => Err(Aleo2JsonError::MaxDepthExceeded { max_depth, path: format!("{name}{}", path.concat()) }),
+        false => Ok(()),
+    };
+    let mut structs = HashMap::new();
+    for (name, struct_) in program.structs() {
+        let path = members_path(struct_.members().iter(), &structs);
+        check(name.to_string(), path.clone())?;
+        structs.insert(*name, path);
+    }
+    for (name, record) in program.records() {
+        let entries = record.entries().iter().map(|(name, entry)| (name, entry.plaintext_type()));
+        check(name.to_string(), members_path(entries, &structs))?;
+    }
+    for (name, mapping) in program.mappings() {
+        check(format!("{name}.key"), type_path(mapping.key().plaintext_type(), &structs))?;
+        check(format!("{name}.value"), type_path(mapping.value().plaintext_type(), &structs))?;
+    }
+    Ok(())
+}
+
+/// Fails if the nesting of the given plaintext at the given path and depth exceeds the given depth.
+fn check_plaintext_depth<N: Network>(
+    plaintext: &Plaintext<N>,
+    path: &mut String,
+    depth: usize,
+    max_depth: usize,
+) -> Result<(), Aleo2JsonError> {
+    if depth > max_depth {
+        return Err(Aleo2JsonError::MaxDepthExceeded { max_depth, path: path.clone() });
+    }
+    let length = path.len();
+    match plaintext {
+        Plaintext::Literal(..) => (),
+        Plaintext::Struct(members, _) => {
+            for (name, member) in members {
+                path.push_str(&format!(".{name}"));
+                check_plaintext_depth(member, path, depth + 1, max_depth)?;
+                path.truncate(length);
+            }
+        }
+        Plaintext::Array(elements, _) => {
+            for (index, element) in elements.iter().enumerate() {
+                path.push_str(&format!("[{index}]"));
+                check_plaintext_depth(element, path, depth + 1, max_depth)?;
+                path.truncate(length);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fails if the nesting of any entry of the given record exceeds the given depth, reporting its path, i.e.
+/// `data.inner[0].x`. Each entry is one level, as in `check_program_depth`.
+pub fn check_record_depth<N: Network>(
+    record: &Record<N, Plaintext<N>>,
+    max_depth: usize,
+) -> Result<(), Aleo2JsonError> {
+    for (name, entry) in record.data() {
+        let (Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext)) = entry;
+        check_plaintext_depth(plaintext, &mut format!("data.{name}"), 1, max_depth)?;
+    }
+    Ok(())
+}
+
+/// Fails if the nesting of the given plaintext value exceeds the given depth, reporting its path, i.e.
+/// `value.inner[0].x`. Each struct member and array element is one level, as in `check_record_depth`.
+pub fn check_value_depth<N: Network>(plaintext: &Plaintext<N>, max_depth: usize) -> Result<(), Aleo2JsonError> {
+    check_plaintext_depth(plaintext, &mut "value".to_string(), 0, max_depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    use std::str::FromStr;
+
+    const PROGRAM: &str = r"
+program nested.aleo;
+
+struct inner:
+    x as field;
+
+struct middle:
+    values as [inner; 2u32];
+
+struct outer:
+    flag as boolean;
+    middle as middle;
+
+mapping balances:
+    key as address.public;
+    value as outer.public;
+
+function main:
+    input r0 as u8.public;
+";
+
+    #[test]
+    fn test_check_program_depth() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        assert!(check_program_depth(&program, 4).is_ok());
+        // The first type exceeding the depth is reported, along its deepest path.
+        let error = check_program_depth(&program, 3).unwrap_err();
+        assert_eq!(error.kind(), "max_depth_exceeded");
+        assert_eq!(error.to_string(), "The nesting of 'outer.middle.values[].x' exceeds the maximum depth of 3");
+        let error = check_program_depth(&program, 2).unwrap_err();
+        assert_eq!(error.to_string(), "The nesting of 'middle.values[].x' exceeds the maximum depth of 2");
+    }
+
+    #[test]
+    fn test_check_record_depth() {
+        let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(
+            "{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, \
+             point: { values: [1u8.private, 2u8.private] }, _nonce: 0group.public }",
+        )
+        .unwrap();
+        assert!(check_record_depth(&record, 3).is_ok());
+        let error = check_record_depth(&record, 2).unwrap_err();
+        assert_eq!(error.to_string(), "The nesting of 'data.point.values[0]' exceeds the maximum depth of 2");
+    }
+}
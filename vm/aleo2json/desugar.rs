@@ -0,0 +1,145 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{network::Network, program::Literal};
+use snarkvm_synthesizer_program::{
+    Add,
+    Command,
+    Instruction,
+    InstructionTrait,
+    LessThan,
+    LessThanOrEqual,
+    Mul,
+    Operand,
+    Program,
+};
+
+use anyhow::Result;
+
+/// Returns the given operand in its explicit form, i.e. the address a program ID evaluates to.
+fn desugar_operand<N: Network>(operand: &Operand<N>) -> Result<Operand<N>> {
+    match operand {
+        Operand::ProgramID(program_id) => Ok(Operand::Literal(Literal::Address(program_id.to_address()?))),
+        operand => Ok(operand.clone()),
+    }
+}
+
+/// Returns the given instruction in its canonical form, as in `desugar_program`.
+fn desugar_instruction<N: Network>(instruction: &Instruction<N>) -> Result<Instruction<N>> {
+    let operands = instruction.operands().iter().map(desugar_operand).collect::<Result<Vec<_>>>()?;
+    let destinations = instruction.destinations();
+    match (instruction, operands.as_slice(), destinations.as_slice()) {
+        (Instruction::GreaterThan(_), [first, second], [destination]) => {
+            Ok(LessThan::new(vec![second.clone(), first.clone()], destination.clone())?.into())
+        }
+        (Instruction::GreaterThanOrEqual(_), [first, second], [destination]) => {
+            Ok(LessThanOrEqual::new(vec![second.clone(), first.clone()], destination.clone())?.into())
+        }
+        (Instruction::Double(_), [first], [destination]) => {
+            Ok(Add::new(vec![first.clone(), first.clone()], destination.clone())?.into())
+        }
+        (Instruction::Square(_), [first], [destination]) => {
+            Ok(Mul::new(vec![first.clone(), first.clone()], destination.clone())?.into())
+        }
+        _ => instruction.with_operands(operands),
+    }
+}
+
+/// Returns the given program with each instruction of its closures, functions, and finalize logic in its canonical,
+/// fully explicit form, so that equivalent spellings of a program convert alike, with `--desugar`. The rules are:
+/// - `gt a b into c` is written `lt b a into c`, and `gte a b into c` is written `lte b a into c`;
+/// - `double a into b` is written `add a a into b`, and `square a into b` is written `mul a a into b`;
+/// - a program ID operand, i.e. `token.aleo`, is written as the address literal it evaluates to.
+///
+/// Each instruction is rewritten into a single one, so the index of each instruction is unchanged, and a desugared
+/// program desugars to itself.
+pub fn desugar_program<N: Network>(program: &Program<N>) -> Result<Program<N>> {
+    program.map_instructions(desugar_instruction, |command| match command {
+        Command::Instruction(instruction) => Ok(Command::Instruction(desugar_instruction(instruction)?)),
+        command => Ok(command.clone()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, parse_program, ConvertOptions, CurrentNetwork};
+
+    const SOURCE: &str = r"
+import token.aleo;
+
+program sugar.aleo;
+
+closure compare:
+    input r0 as field;
+    input r1 as field;
+    gt r0 r1 into r2;
+    double r0 into r3;
+    output r2 as boolean;
+
+function main:
+    input r0 as u64.public;
+    input r1 as field.public;
+    gte r0 1u64 into r2;
+    square r1 into r3;
+    assert.neq self.caller token.aleo;
+    async main r0 into r4;
+    output r4 as sugar.aleo/main.future;
+
+finalize main:
+    input r0 as u64.public;
+    gt r0 2u64 into r1;
+";
+
+    #[test]
+    fn test_desugar_program() {
+        let program = parse_program::<CurrentNetwork>(SOURCE).unwrap();
+        let desugared = desugar_program(&program).unwrap();
+        let instructions = |name: &str| -> Vec<String> {
+            let function = desugared.get_function(&name.parse().unwrap()).unwrap();
+            function.instructions().iter().map(ToString::to_string).collect()
+        };
+        let closure = desugared.get_closure(&"compare".parse().unwrap()).unwrap();
+        let closure = closure.instructions().iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(closure, ["lt r1 r0 into r2;", "add r0 r0 into r3;"]);
+        let address = "token.aleo".parse::<crate::console::program::ProgramID<CurrentNetwork>>().unwrap();
+        let address = address.to_address().unwrap();
+        assert_eq!(instructions("main"), [
+            "lte 1u64 r0 into r2;".to_string(),
+            "mul r1 r1 into r3;".to_string(),
+            format!("assert.neq self.caller {address} ;"),
+            "async main r0 into r4;".to_string(),
+        ]);
+        let finalize = desugared.get_function(&"main".parse().unwrap()).unwrap();
+        assert_eq!(finalize.finalize_logic().unwrap().commands()[0].to_string(), "lt 2u64 r0 into r1;");
+
+        // A desugared program desugars to itself.
+        assert_eq!(desugar_program(&desugared).unwrap(), desugared);
+    }
+
+    #[test]
+    fn test_desugar_equivalent_spellings() {
+        let options = ConvertOptions::new().desugar(true);
+        let sugared = convert_program(SOURCE, &options).unwrap();
+        let explicit = SOURCE
+            .replace("gt r0 r1 into r2", "lt r1 r0 into r2")
+            .replace("double r0 into r3", "add r0 r0 into r3")
+            .replace("gte r0 1u64 into r2", "lte 1u64 r0 into r2");
+        assert_eq!(convert_program(&explicit, &options).unwrap(), sugared);
+        assert_eq!(sugared["closures"]["compare"]["instructions"][0]["opcode"], "lt");
+        // Without the option, the spellings differ.
+        let options = ConvertOptions::new();
+        assert_ne!(convert_program(&explicit, &options).unwrap(), convert_program(SOURCE, &options).unwrap());
+    }
+}
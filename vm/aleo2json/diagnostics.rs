@@ -0,0 +1,210 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::Aleo2JsonError;
+
+use anstyle::{AnsiColor, Color, Style};
+use clap::ValueEnum;
+use serde_json::json;
+use std::io::IsTerminal;
+
+/// When to colorize the diagnostic output, and the JSON output with `--color-json`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Colorizes the diagnostic output if stderr is a terminal.
+    #[default]
+    Auto,
+    /// Always colorizes the diagnostic output.
+    Always,
+    /// Never colorizes the diagnostic output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns `true` if the diagnostic output on stderr should be colorized.
+    pub fn colorize_stderr(&self) -> bool {
+        match self {
+            Self::Auto => std::io::stderr().is_terminal(),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+
+    /// Returns `true` if the output on stdout should be colorized, i.e. with `--color-json`.
+    pub fn colorize_stdout(&self) -> bool {
+        match self {
+            Self::Auto => std::io::stdout().is_terminal(),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// The format of the error output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// Prints the error as text to stderr.
+    #[default]
+    Text,
+    /// Also prints the error as JSON to the output, i.e. `{ "ok": false, "error": { ... } }`.
+    Json,
+}
+
+/// Returns the 1-based line and column of the given byte offset in the source.
+/// A tab advances the column to the next multiple of `tab_width`, so a width of 1 counts it as one column.
+/// Both `\n` and `\r\n` end a line, and the end of the source is on its last line, whether it ends with one or not.
+pub fn line_column(source: &str, offset: usize, tab_width: usize) -> (usize, usize) {
+    let tab_width = tab_width.max(1);
+    // A trailing line break does not start another line.
+    let content = source.strip_suffix('\n').map_or(source, |source| source.strip_suffix('\r').unwrap_or(source));
+    let prefix = &source[..offset.min(content.len())];
+    let line = prefix.matches('\n').count() + 1;
+    let line_start = prefix.rfind('\n').map_or(0, |index| index + 1);
+    let width = prefix[line_start..].chars().fold(0, |width, c| match c {
+        '\t' => (width / tab_width + 1) * tab_width,
+        // The carriage return of a `\r\n` is part of the line break.
+        '\r' => width,
+        _ => width + 1,
+    });
+    (line, width + 1)
+}
+
+/// Returns the given conversion error as JSON, i.e.
+/// `{ "ok": false, "error": { "kind": "parse", "message": "...", "offset": 42, "line": 3, "col": 5 } }`.
+/// The line and column are only given if the error has an offset, and the source is given.
+pub fn error_to_json(error: &anyhow::Error, source: Option<&str>, tab_width: usize) -> serde_json::Value {
+    let (kind, message, offset) = match error.downcast_ref::<Aleo2JsonError>() {
+        Some(error) => (error.kind(), error.to_string(), error.offset()),
+        None if error.is::<std::io::Error>() => ("io", error.to_string(), None),
+        None => ("other", error.to_string(), None),
+    };
+    // Locate the error in the source, if it has an offset.
+    let location = offset.zip(source).map(|(offset, source)| line_column(source, offset, tab_width));
+    json!({
+        "ok": false,
+        "error": {
+            "kind": kind,
+            "message": message,
+            "offset": offset,
+            "line": location.map(|(line, _)| line),
+            "col": location.map(|(_, column)| column),
+        },
+    })
+}
+
+/// Renders the given error message against the source, with a caret at the given byte offset, i.e.
+///
+/// ```text
+/// error: Parser did not consume all of the string
+///  --> main.aleo:5:5
+///   |
+/// 5 |     foo r0 into r1;
+///   |     ^
+/// ```
+pub fn render_diagnostic(
+    path: &str,
+    source: &str,
+    offset: usize,
+    message: &str,
+    color: bool,
+    tab_width: usize,
+) -> String {
+    let paint = |style: Style, text: &str| match color {
+        true => format!("{}{text}{}", style.render(), style.render_reset()),
+        false => text.to_string(),
+    };
+    let error_style = Style::new().bold().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+    let gutter_style = Style::new().bold().fg_color(Some(Color::Ansi(AnsiColor::Blue)));
+
+    let (line, column) = line_column(source, offset, tab_width);
+    let source_line = source.lines().nth(line - 1).unwrap_or_default();
+    let width = line.to_string().len();
+    let padding = " ".repeat(width);
+    // Preserve tabs in the caret line so that the caret lines up with the source, whatever the tab width.
+    let (_, characters) = line_column(source, offset, 1);
+    let indent =
+        source_line.chars().take(characters - 1).map(|c| if c == '\t' { '\t' } else { ' ' }).collect::<String>();
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "{}{}\n",
+        paint(error_style, "error"),
+        paint(Style::new().bold(), &format!(": {message}"))
+    ));
+    output.push_str(&format!("{padding}{} {path}:{line}:{column}\n", paint(gutter_style, "-->")));
+    output.push_str(&format!("{padding} {}\n", paint(gutter_style, "|")));
+    output.push_str(&format!("{} {source_line}\n", paint(gutter_style, &format!("{line:>width$} |"))));
+    output.push_str(&format!("{padding} {} {indent}{}\n", paint(gutter_style, "|"), paint(error_style, "^")));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "program main.aleo;\n\nfunction main:\n    foo r0 into r1;\n";
+
+    #[test]
+    fn test_line_column() {
+        assert_eq!(line_column(SOURCE, 0, 1), (1, 1));
+        assert_eq!(line_column(SOURCE, 8, 1), (1, 9));
+        assert_eq!(line_column(SOURCE, SOURCE.find("foo").unwrap(), 1), (4, 5));
+        // The end of the source is at the end of its last line.
+        assert_eq!(line_column(SOURCE, SOURCE.len(), 1), (4, 20));
+        assert_eq!(line_column(SOURCE.trim_end(), SOURCE.trim_end().len(), 1), (4, 20));
+    }
+
+    #[test]
+    fn test_line_column_crlf() {
+        let crlf = SOURCE.replace('\n', "\r\n");
+        assert_eq!(line_column(&crlf, crlf.find("foo").unwrap(), 1), (4, 5));
+        assert_eq!(line_column(&crlf, crlf.find("function").unwrap() - 1, 1), (2, 1));
+        assert_eq!(line_column(&crlf, crlf.len(), 1), (4, 20));
+        assert_eq!(line_column(crlf.trim_end(), crlf.trim_end().len(), 1), (4, 20));
+
+        // Mixed line endings are counted alike.
+        let mixed = "program main.aleo;\r\n\nfunction main:\r\n    foo r0 into r1;";
+        assert_eq!(line_column(mixed, mixed.find("foo").unwrap(), 1), (4, 5));
+        assert_eq!(line_column(mixed, mixed.len(), 1), (4, 20));
+
+        let offset = crlf.find("foo").unwrap();
+        let plain = render_diagnostic("main.aleo", &crlf, offset, "unexpected input", false, 1);
+        assert_eq!(plain, "error: unexpected input\n --> main.aleo:4:5\n  |\n4 |     foo r0 into r1;\n  |     ^\n");
+    }
+
+    #[test]
+    fn test_line_column_tab_width() {
+        let source = "function main:\n\tfoo r0;\n  \tbar r1;\n";
+        let foo = source.find("foo").unwrap();
+        let bar = source.find("bar").unwrap();
+        assert_eq!(line_column(source, foo, 1), (2, 2));
+        assert_eq!(line_column(source, foo, 4), (2, 5));
+        // A tab advances to the next tab stop.
+        assert_eq!(line_column(source, bar, 1), (3, 4));
+        assert_eq!(line_column(source, bar, 4), (3, 5));
+        assert_eq!(line_column(source, bar, 8), (3, 9));
+    }
+
+    #[test]
+    fn test_render_diagnostic() {
+        let offset = SOURCE.find("foo").unwrap();
+
+        let plain = render_diagnostic("main.aleo", SOURCE, offset, "unexpected input", false, 1);
+        assert_eq!(plain, "error: unexpected input\n --> main.aleo:4:5\n  |\n4 |     foo r0 into r1;\n  |     ^\n");
+
+        let colored = render_diagnostic("main.aleo", SOURCE, offset, "unexpected input", true, 1);
+        assert!(colored.contains("\u{1b}["));
+        assert_ne!(colored, plain);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::{json, Map, Value};
+
+/// Returns the mappings accessed by the given finalize commands, and whether each is `read`, `write`, or `read_write`,
+/// i.e. `{ "account": "read_write" }`. Each command is classified by its `"access"` and `"mapping_name"`.
+fn finalize_effects(commands: &[Value]) -> Map<String, Value> {
+    let mut effects = Map::new();
+    for command in commands {
+        let value = &command["value"];
+        let (Some(access), Some(mapping)) =
+            (value.get("access").and_then(Value::as_str), value.get("mapping_name").and_then(Value::as_str))
+        else {
+            continue;
+        };
+        let effect = match effects.get(mapping).and_then(Value::as_str) {
+            Some(previous) if previous != access => "read_write",
+            _ => access,
+        };
+        effects.insert(mapping.to_string(), json!(effect));
+    }
+    effects
+}
+
+/// Returns the side effects of a function with the given mapping effects, i.e. `pure` without any,
+/// `read` if it only reads mappings, or `write` if it writes any mapping.
+fn purity(effects: &Map<String, Value>) -> &'static str {
+    match (effects.is_empty(), effects.values().all(|effect| effect == "read")) {
+        (true, _) => "pure",
+        (false, true) => "read",
+        (false, false) => "write",
+    }
+}
+
+/// Adds the mappings each function reads and writes in its finalize logic under `"mapping_effects"`,
+/// i.e. `"mapping_effects": { "account": "read_write" }`, and their summary under `"purity"`, i.e. `"purity": "write"`.
+/// A function without finalize logic has no effects, and is `pure`.
+pub fn add_mapping_effects(json: &mut Value) {
+    let Some(functions) = json.get_mut("functions").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for function in functions.values_mut() {
+        let effects = match function["finalize_logic"]["commands"].as_array() {
+            Some(commands) => finalize_effects(commands),
+            None => Map::new(),
+        };
+        function["purity"] = json!(purity(&effects));
+        function["mapping_effects"] = Value::Object(effects);
+    }
+}
+
+/// Returns the functions reading and writing each mapping of the given program JSON, as classified by
+/// `add_mapping_effects`, i.e. `[{ "mapping": "account", "readers": ["transfer"], "writers": ["mint", "transfer"] }]`,
+/// which is the transpose of the `"mapping_effects"` of each function. The mappings, and the functions of each, are
+/// in declaration order. A mapping accessed without being declared follows the declared ones.
+pub fn mapping_index(json: &Value) -> Vec<Value> {
+    let mut index = Map::new();
+    if let Some(mappings) = json.get("mappings").and_then(Value::as_object) {
+        for mapping in mappings.keys() {
+            index.insert(mapping.clone(), json!({ "mapping": mapping, "readers": [], "writers": [] }));
+        }
+    }
+    let functions = json.get("functions").and_then(Value::as_object).into_iter().flatten();
+    for (function, function_json) in functions {
+        let Some(effects) = function_json.get("mapping_effects").and_then(Value::as_object) else {
+            continue;
+        };
+        for (mapping, effect) in effects {
+            let entry = index
+                .entry(mapping.clone())
+                .or_insert_with(|| json!({ "mapping": mapping, "readers": [], "writers": [] }));
+            if matches!(effect.as_str(), Some("read" | "read_write")) {
+                entry["readers"].as_array_mut().into_iter().for_each(|readers| readers.push(json!(function)));
+            }
+            if matches!(effect.as_str(), Some("write" | "read_write")) {
+                entry["writers"].as_array_mut().into_iter().for_each(|writers| writers.push(json!(function)));
+            }
+        }
+    }
+    index.into_iter().map(|(_, entry)| entry).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_mapping_effects() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program effects.aleo;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+mapping frozen:
+    key as address.public;
+    value as boolean.public;
+
+mapping supply:
+    key as u8.public;
+    value as u64.public;
+
+function transfer:
+    input r0 as u64.public;
+    async transfer self.caller r0 into r1;
+    output r1 as effects.aleo/transfer.future;
+
+finalize transfer:
+    input r0 as address.public;
+    input r1 as u64.public;
+    contains frozen[r0] into r2;
+    assert.eq r2 false;
+    get.or_use account[r0] 0u64 into r3;
+    add r3 r1 into r4;
+    set r4 into account[r0];
+    remove supply[0u8];
+
+function noop:
+    input r0 as u8.private;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_mapping_effects(&mut json);
+        assert_eq!(
+            json["functions"]["transfer"]["mapping_effects"],
+            json!({ "frozen": "read", "account": "read_write", "supply": "write" })
+        );
+        assert_eq!(json["functions"]["transfer"]["purity"], "write");
+        assert_eq!(json["functions"]["noop"]["mapping_effects"], json!({}));
+        assert_eq!(json["functions"]["noop"]["purity"], "pure");
+    }
+
+    #[test]
+    fn test_mapping_index() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program index.aleo;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+mapping unused:
+    key as u8.public;
+    value as u8.public;
+
+function mint:
+    input r0 as u64.public;
+    async mint self.caller r0 into r1;
+    output r1 as index.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    set r1 into account[r0];
+
+function balance:
+    async balance self.caller into r0;
+    output r0 as index.aleo/balance.future;
+
+finalize balance:
+    input r0 as address.public;
+    get account[r0] into r1;
+
+function transfer:
+    input r0 as u64.public;
+    async transfer self.caller r0 into r1;
+    output r1 as index.aleo/transfer.future;
+
+finalize transfer:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    sub r2 r1 into r3;
+    set r3 into account[r0];
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_mapping_effects(&mut json);
+        assert_eq!(mapping_index(&json), [
+            json!({ "mapping": "account", "readers": ["balance", "transfer"], "writers": ["mint", "transfer"] }),
+            json!({ "mapping": "unused", "readers": [], "writers": [] }),
+        ]);
+    }
+
+    #[test]
+    fn test_purity() {
+        let effects = |effects: Value| effects.as_object().unwrap().clone();
+        assert_eq!(purity(&Map::new()), "pure");
+        assert_eq!(purity(&effects(json!({ "frozen": "read", "account": "read" }))), "read");
+        assert_eq!(purity(&effects(json!({ "frozen": "read", "supply": "write" }))), "write");
+        assert_eq!(purity(&effects(json!({ "account": "read_write" }))), "write");
+    }
+}
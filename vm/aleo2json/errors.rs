@@ -0,0 +1,114 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::ExitStatus;
+
+#[derive(Debug, Error)]
+pub enum Aleo2JsonError {
+    #[error("Parser error: {message}")]
+    Parse { offset: usize, message: String },
+
+    #[error("Parser did not consume all of the string")]
+    UnconsumedInput { offset: usize },
+
+    #[error("The input is {size} bytes, which exceeds the maximum size of {max_size} bytes")]
+    InputTooLarge { size: u64, max_size: u64 },
+
+    #[error("The input is not valid UTF-8 at byte {offset}, use `--input-encoding` to read another encoding")]
+    InvalidUtf8 { offset: usize },
+
+    #[error("The input is not a valid binary program: {message}")]
+    InvalidBytes { message: String },
+
+    #[error("The SHA-256 digest of the input is {actual}, but expected {expected}")]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("Invalid literal '{literal}': {message}")]
+    InvalidLiteral { offset: usize, literal: String, message: String },
+
+    #[error("Unknown opcode '{opcode}' in '{instruction}'")]
+    UnknownOpcode { opcode: String, instruction: String },
+
+    #[error("Unknown section '{section}', expected one of: {valid}")]
+    UnknownSection { section: String, valid: String },
+
+    #[error("Invalid rename '{rename}', expected '<from>=<to>'")]
+    InvalidRename { rename: String },
+
+    #[error("Renaming '{from}' to '{to}' collides with another key")]
+    RenameCollision { from: String, to: String },
+
+    #[error("Parsing did not finish within {timeout_ms} ms")]
+    Timeout { timeout_ms: u128 },
+
+    #[error("The nesting of '{path}' exceeds the maximum depth of {max_depth}")]
+    MaxDepthExceeded { max_depth: usize, path: String },
+}
+
+impl Aleo2JsonError {
+    /// Returns the kind of the error, i.e. `parse`.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Parse { .. } => "parse",
+            Self::UnconsumedInput { .. } => "unconsumed_input",
+            Self::InputTooLarge { .. } => "input_too_large",
+            Self::InvalidUtf8 { .. } => "invalid_utf8",
+            Self::InvalidBytes { .. } => "invalid_bytes",
+            Self::ChecksumMismatch { .. } => "checksum_mismatch",
+            Self::InvalidLiteral { .. } => "invalid_literal",
+            Self::UnknownOpcode { .. } => "unknown_opcode",
+            Self::UnknownSection { .. } => "unknown_section",
+            Self::InvalidRename { .. } => "invalid_rename",
+            Self::RenameCollision { .. } => "rename_collision",
+            Self::Timeout { .. } => "timeout",
+            Self::MaxDepthExceeded { .. } => "max_depth_exceeded",
+        }
+    }
+
+    /// Returns the byte offset in the source at which the error occurred, if any.
+    pub const fn offset(&self) -> Option<usize> {
+        match self {
+            Self::Parse { offset, .. } | Self::UnconsumedInput { offset } | Self::InvalidLiteral { offset, .. } => {
+                Some(*offset)
+            }
+            Self::InputTooLarge { .. }
+            | Self::InvalidUtf8 { .. }
+            | Self::InvalidBytes { .. }
+            | Self::ChecksumMismatch { .. }
+            | Self::UnknownOpcode { .. }
+            | Self::UnknownSection { .. }
+            | Self::InvalidRename { .. }
+            | Self::RenameCollision { .. }
+            | Self::Timeout { .. }
+            | Self::MaxDepthExceeded { .. } => None,
+        }
+    }
+
+    /// Returns the exit status of the error, i.e. `ExitStatus::Parse`.
+    pub const fn exit_status(&self) -> ExitStatus {
+        match self {
+            Self::Parse { .. }
+            | Self::UnconsumedInput { .. }
+            | Self::UnknownOpcode { .. }
+            | Self::InvalidBytes { .. }
+            | Self::Timeout { .. }
+            | Self::MaxDepthExceeded { .. } => ExitStatus::Parse,
+            Self::InputTooLarge { .. } | Self::InvalidUtf8 { .. } => ExitStatus::Io,
+            Self::ChecksumMismatch { .. } | Self::InvalidLiteral { .. } => ExitStatus::Validation,
+            Self::UnknownSection { .. } | Self::InvalidRename { .. } | Self::RenameCollision { .. } => {
+                ExitStatus::Usage
+            }
+        }
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::literal::dec_and_hex,
+    console::{
+        network::{prelude::Visibility, Network},
+        program::{Entry, Plaintext, Record},
+    },
+};
+use snarkvm_ledger_block::{Block, Deployment, Execution, Transaction, Transition};
+
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use serde_json::json;
+use std::str::FromStr;
+
+/// The kind of object to convert.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum InputKind {
+    /// An Aleo program.
+    #[default]
+    Program,
+    /// An execution, as a JSON-string.
+    Execution,
+    /// A transition, as a JSON-string.
+    Transition,
+    /// The program deployed by a deployment transaction, or by a deployment, as a JSON-string.
+    Deployment,
+    /// A record plaintext, i.e. `{ owner: aleo1....private, amount: 5u64.private, _nonce: 0group.public }`.
+    Record,
+    /// The programs deployed in a block, as a JSON-string.
+    Block,
+    /// A plaintext value, i.e. `{ amount: 5u64, owner: aleo1... }`, typed by the program of `--type-context`.
+    Value,
+}
+
+/// The version of the envelope of a wrapped document.
+pub const WRAP_VERSION: u64 = 1;
+
+/// The version of the structure of the emitted documents, emitted under `"schema_version"` with `--with-meta`.
+/// Bump it whenever a key is removed or renamed, or its value changes type; adding a key is not a breaking change.
+pub const SCHEMA_VERSION: u64 = 1;
+
+impl InputKind {
+    /// Returns the type of a wrapped document of this kind, i.e. `aleo_program`.
+    pub const fn document_type(&self) -> &'static str {
+        match self {
+            Self::Program => "aleo_program",
+            Self::Execution => "aleo_execution",
+            Self::Transition => "aleo_transition",
+            Self::Deployment => "aleo_deployment",
+            Self::Record => "aleo_record",
+            Self::Block => "aleo_block",
+            Self::Value => "aleo_value",
+        }
+    }
+}
+
+/// Nests the given JSON of an input of the given kind under `"data"`, in an envelope with its type and version, i.e.
+/// `{ "type": "aleo_program", "version": 1, "tool": { "name": "aleo2json", "version": "..." }, "data": { ... } }`.
+pub fn wrap_document(json: serde_json::Value, kind: InputKind) -> serde_json::Value {
+    json!({
+        "type": kind.document_type(),
+        "version": WRAP_VERSION,
+        "tool": { "name": "aleo2json", "version": env!("CARGO_PKG_VERSION") },
+        "data": json,
+    })
+}
+
+/// Adds the version of the structure of the given document under `"schema_version"`, i.e. `"schema_version": 1`,
+/// so that a loader of persisted documents can branch on it. An array (i.e. of `--flatten`) is left unchanged.
+pub fn add_schema_version(json: &mut serde_json::Value) {
+    if let Some(object) = json.as_object_mut() {
+        object.insert("schema_version".to_string(), json!(SCHEMA_VERSION));
+    }
+}
+
+/// Returns the given record entry as its value and its visibility, i.e. `{ "value": "5u64", "visibility": "private" }`.
+fn entry_to_json<N: Network>(entry: &Entry<N, Plaintext<N>>) -> serde_json::Value {
+    let (value, visibility) = match entry {
+        Entry::Constant(plaintext) => (plaintext, "constant"),
+        Entry::Public(plaintext) => (plaintext, "public"),
+        Entry::Private(plaintext) => (plaintext, "private"),
+    };
+    json!({ "value": value.to_string(), "visibility": visibility })
+}
+
+/// Returns the number of encrypted and plaintext data entries of the given record, i.e. `(1, 2)`. A private entry is
+/// encrypted under the address of the owner in the ciphertext of the record, while a constant or public entry is not.
+fn entry_counts<N: Network, Private: Visibility>(record: &Record<N, Private>) -> (usize, usize) {
+    let encrypted = record.data().values().filter(|entry| matches!(entry, Entry::Private(..))).count();
+    (encrypted, record.data().len() - encrypted)
+}
+
+/// Returns the given record plaintext as JSON, i.e.
+/// `{ "type": "Record", "owner": { "value": "aleo1...", "visibility": "private" }, "data": { ... }, "nonce": "..." }`.
+/// The number of data entries encrypted in the ciphertext of the record, and of the others, are under
+/// `"encrypted_entries"` and `"plaintext_entries"`, to tell at a glance whether the record is fully encrypted.
+/// If `hex_fields` is set, the nonce is its x-coordinate in decimal and hex forms, i.e. `{ "dec": ..., "hex": ... }`.
+/// The entries of `"data"` are in the order of the record, which is the order in which they are encoded.
+pub fn record_to_json<N: Network>(record: &Record<N, Plaintext<N>>, hex_fields: bool) -> serde_json::Value {
+    let j_data = record
+        .data()
+        .iter()
+        .map(|(name, entry)| (name.to_string(), entry_to_json(entry)))
+        .collect::<serde_json::Map<_, _>>();
+
+    // The nonce is a group element, which is represented by its x-coordinate, as for a group literal.
+    let x_coordinate = record.nonce().to_x_coordinate().to_string();
+    let j_nonce = match hex_fields {
+        true => x_coordinate.strip_suffix("field").and_then(dec_and_hex::<N>),
+        false => None,
+    };
+
+    let (encrypted_entries, plaintext_entries) = entry_counts(record);
+    json!({
+        "type": "Record",
+        "owner": entry_to_json(&record.owner().to_entry()),
+        "data": j_data,
+        "nonce": j_nonce.unwrap_or_else(|| json!(record.nonce().to_string())),
+        "encrypted_entries": encrypted_entries,
+        "plaintext_entries": plaintext_entries,
+    })
+}
+
+/// Adds the number of field elements encoding each data entry of the given record to its JSON under
+/// `"encoded_fields"`, i.e. `"encoded_fields": 1`. An entry is encoded as its bits, followed by a terminus bit,
+/// packed into field elements; a private entry is encrypted into as many field elements.
+pub fn add_encoded_fields<N: Network>(json: &mut serde_json::Value, record: &Record<N, Plaintext<N>>) -> Result<()> {
+    for (name, entry) in record.data() {
+        let (Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext)) = entry;
+        json["data"][name.to_string()]["encoded_fields"] = json!(plaintext.size_in_fields()?);
+    }
+    Ok(())
+}
+
+/// Returns the given transition as JSON.
+pub fn transition_to_json<N: Network>(transition: &Transition<N>) -> Result<serde_json::Value> {
+    Ok(json!({
+        "type": "Transition",
+        "id": transition.id().to_string(),
+        "program": transition.program_id().to_string(),
+        "function": transition.function_name().to_string(),
+        "inputs": serde_json::to_value(transition.inputs())?,
+        "outputs": serde_json::to_value(transition.outputs())?,
+        "tpk": transition.tpk().to_string(),
+        "tcm": transition.tcm().to_string(),
+    }))
+}
+
+/// Returns the given execution as JSON.
+pub fn execution_to_json<N: Network>(execution: &Execution<N>) -> Result<serde_json::Value> {
+    let j_transitions = execution.transitions().map(transition_to_json).collect::<Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "type": "Execution",
+        "global_state_root": execution.global_state_root().to_string(),
+        "transitions": j_transitions,
+    }))
+}
+
+/// Parses the given deployment transaction, or deployment, as a JSON-string.
+/// Returns the deployment and its metadata, i.e. `{ "program_id": "token.aleo", "edition": 0, "fee": 1000 }`.
+/// The transaction ID and the fee are only included for a deployment transaction.
+pub fn parse_deployment<N: Network>(string: &str) -> Result<(Deployment<N>, serde_json::Value)> {
+    if let Ok(transaction) = Transaction::<N>::from_str(string) {
+        return transaction_deployment(&transaction);
+    }
+    match Deployment::<N>::from_str(string) {
+        Ok(deployment) => {
+            let metadata = json!({
+                "program_id": deployment.program_id().to_string(),
+                "edition": deployment.edition(),
+            });
+            Ok((deployment, metadata))
+        }
+        Err(_) => bail!("The input is neither a deployment transaction nor a deployment"),
+    }
+}
+
+/// Returns the deployment of the given deployment transaction, and its metadata, as `parse_deployment` does.
+fn transaction_deployment<N: Network>(transaction: &Transaction<N>) -> Result<(Deployment<N>, serde_json::Value)> {
+    let Some(deployment) = transaction.deployment() else {
+        bail!("The transaction '{}' does not contain a program deployment", transaction.id());
+    };
+    let metadata = json!({
+        "transaction_id": transaction.id().to_string(),
+        "program_id": deployment.program_id().to_string(),
+        "edition": deployment.edition(),
+        "fee": transaction.fee_amount().ok().map(|fee| *fee),
+    });
+    Ok((deployment.clone(), metadata))
+}
+
+/// Parses the given block, as a JSON-string, and returns the deployment of each of its accepted deployment
+/// transactions, and its metadata, in the order of the block. A block without deployments has none.
+pub fn parse_block_deployments<N: Network>(string: &str) -> Result<Vec<(Deployment<N>, serde_json::Value)>> {
+    let block = Block::<N>::from_str(string)?;
+    block.deployments().map(|confirmed| transaction_deployment(confirmed.transaction())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{parse_program, CurrentNetwork},
+        console::{
+            network::prelude::{TestRng, ToBits},
+            program::{Identifier, Plaintext, Uniform},
+            types::{Field, Group},
+        },
+    };
+
+    use serde_json::Value;
+    use snarkvm_ledger_block::{Input, Output};
+    use std::str::FromStr;
+
+    fn sample_transition() -> Transition<CurrentNetwork> {
+        let rng = &mut TestRng::default();
+
+        let input = Input::Public(Uniform::rand(rng), Some(Plaintext::from_str("5u64").unwrap()));
+        let output = Output::Public(Uniform::rand(rng), Some(Plaintext::from_str("10u64").unwrap()));
+        Transition::new(
+            "token.aleo".parse().unwrap(),
+            "mint".parse().unwrap(),
+            vec![input],
+            vec![output],
+            Group::rand(rng),
+            Field::rand(rng),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_transition_to_json() {
+        let expected = sample_transition();
+        // Round-trip the transition through its string representation.
+        let transition = Transition::<CurrentNetwork>::from_str(&expected.to_string()).unwrap();
+        let json = transition_to_json(&transition).unwrap();
+
+        assert_eq!(json["id"], expected.id().to_string());
+        assert_eq!(json["program"], "token.aleo");
+        assert_eq!(json["function"], "mint");
+        assert_eq!(json["inputs"][0]["type"], "public");
+        assert_eq!(json["inputs"][0]["value"], "5u64");
+        assert_eq!(json["outputs"][0]["value"], "10u64");
+    }
+
+    #[test]
+    fn test_execution_to_json() {
+        let transition = sample_transition();
+        let execution = Execution::from([transition.clone()].into_iter(), Default::default(), None).unwrap();
+        let execution = Execution::<CurrentNetwork>::from_str(&execution.to_string()).unwrap();
+        let json = execution_to_json(&execution).unwrap();
+
+        assert_eq!(json["type"], "Execution");
+        assert_eq!(json["transitions"][0], transition_to_json(&transition).unwrap());
+    }
+
+    #[test]
+    fn test_record_to_json() {
+        let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(
+            "{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, amount: 5u64.private, memo: 1field.public, _nonce: 0group.public }",
+        )
+        .unwrap();
+        let json = record_to_json(&record, false);
+
+        assert_eq!(json["type"], "Record");
+        assert_eq!(json["owner"]["visibility"], "private");
+        assert_eq!(json["data"]["amount"], json!({ "value": "5u64", "visibility": "private" }));
+        assert_eq!(json["data"]["memo"], json!({ "value": "1field", "visibility": "public" }));
+        assert_eq!(json["nonce"], "0group");
+        assert_eq!((json["encrypted_entries"].clone(), json["plaintext_entries"].clone()), (json!(1), json!(1)));
+
+        let json = record_to_json(&record, true);
+        assert_eq!(json["nonce"], json!({ "dec": "0", "hex": "0x0" }));
+        assert_eq!(json["data"]["memo"], json!({ "value": "1field", "visibility": "public" }));
+    }
+
+    #[test]
+    fn test_add_encoded_fields() {
+        let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(
+            "{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, amount: 5u64.private, \
+             memo: 1field.public, _nonce: 0group.public }",
+        )
+        .unwrap();
+        let mut json = record_to_json(&record, false);
+        add_encoded_fields(&mut json, &record).unwrap();
+        // A `u64` and its terminus bit fit in one field element, unlike a whole field element and its terminus bit.
+        assert_eq!(json["data"]["amount"]["encoded_fields"], 1);
+        assert_eq!(json["data"]["memo"]["encoded_fields"], 2);
+        assert!(json["owner"].get("encoded_fields").is_none());
+    }
+
+    #[test]
+    fn test_record_entry_order() {
+        // The entries are declared out of alphabetical order.
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program order.aleo;
+
+record coin:
+    owner as address.private;
+    zeta as u64.private;
+    alpha as field.public;
+    mid as boolean.constant;
+
+function main:
+    input r0 as u8.public;
+",
+        )
+        .unwrap();
+        let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(
+            "{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, zeta: 5u64.private, \
+             alpha: 1field.public, mid: true.constant, _nonce: 0group.public }",
+        )
+        .unwrap();
+
+        // The entries of the record type, and of the record, are in declaration order.
+        let keys = |json: &Value| json.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        let declared = keys(&program.to_json()["records"]["coin"]["data"]);
+        let entries = keys(&record_to_json(&record, false)["data"]);
+        assert_eq!(declared, ["zeta", "alpha", "mid"]);
+        assert_eq!(entries, declared);
+
+        // Encoding the entries in the order of the JSON gives the data bits of the record.
+        let mut data_bits = Vec::new();
+        for name in &entries {
+            let name = Identifier::<CurrentNetwork>::from_str(name).unwrap();
+            name.write_bits_le(&mut data_bits);
+            record.data()[&name].write_bits_le(&mut data_bits);
+        }
+        // The data bits follow the owner, and their number as a `u32`.
+        let start = record.owner().to_bits_le().len() + 32;
+        assert_eq!(&record.to_bits_le()[start..start + data_bits.len()], data_bits.as_slice());
+    }
+
+    #[test]
+    fn test_wrap_document() {
+        let json = wrap_document(json!({ "type": "ProgramCore" }), InputKind::Program);
+        assert_eq!(json["type"], "aleo_program");
+        assert_eq!(json["version"], WRAP_VERSION);
+        assert_eq!(json["tool"]["name"], "aleo2json");
+        assert_eq!(json["data"], json!({ "type": "ProgramCore" }));
+        assert_eq!(wrap_document(json!([]), InputKind::Record)["type"], "aleo_record");
+    }
+
+    #[test]
+    fn test_add_schema_version() {
+        let mut json = json!({ "type": "ProgramCore" });
+        add_schema_version(&mut json);
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+
+        let mut json = json!([]);
+        add_schema_version(&mut json);
+        assert_eq!(json, json!([]));
+    }
+
+    #[test]
+    fn test_parse_deployment_invalid() {
+        let error = parse_deployment::<CurrentNetwork>(r#"{"type":"execute"}"#).unwrap_err();
+        assert_eq!(error.to_string(), "The input is neither a deployment transaction nor a deployment");
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::Aleo2JsonError;
+
+/// The exit status of `aleo2json`, whose discriminant is its exit code. Where several failures apply, i.e. to the
+/// programs of a directory, the one with the highest code is returned.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExitStatus {
+    /// The conversion succeeded.
+    #[default]
+    Success = 0,
+    /// The input failed to parse, or to convert.
+    Parse = 1,
+    /// The input could not be read, or the output written.
+    Io = 2,
+    /// A check of the input or the output failed, i.e. `--expect-sha256` or `--verify-sorted`.
+    Validation = 3,
+    /// The arguments are invalid, i.e. an unknown flag or section.
+    Usage = 4,
+}
+
+impl ExitStatus {
+    /// Returns the exit code of the status, i.e. `2` for an I/O error.
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the exit status of the given error, from the first of its causes that has one.
+    /// An error without any such cause is a conversion failure.
+    pub fn of(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(failure) = cause.downcast_ref::<Failure>() {
+                return failure.status;
+            }
+            if let Some(error) = cause.downcast_ref::<Aleo2JsonError>() {
+                return error.exit_status();
+            }
+            if cause.is::<std::io::Error>() {
+                return Self::Io;
+            }
+            if cause.is::<clap::Error>() {
+                return Self::Usage;
+            }
+        }
+        Self::Parse
+    }
+}
+
+/// A failure with the given exit status, i.e. of a check, or of some programs in a directory.
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct Failure {
+    /// The exit status of the failure.
+    pub status: ExitStatus,
+    /// The message of the failure.
+    pub message: String,
+}
+
+impl Failure {
+    /// Initializes a failed check of the input or the output, with the given message.
+    pub fn validation(message: String) -> Self {
+        Self { status: ExitStatus::Validation, message }
+    }
+
+    /// Initializes an invalid argument, with the given message.
+    pub fn usage(message: String) -> Self {
+        Self { status: ExitStatus::Usage, message }
+    }
+}
+
+/// The number of programs of a directory that failed to convert, and the highest exit status among them.
+#[derive(Debug, Default)]
+pub struct Failures {
+    /// The number of programs that failed to convert.
+    pub count: usize,
+    /// The highest exit status among the failures.
+    pub status: ExitStatus,
+}
+
+impl Failures {
+    /// Records the given failure of a program.
+    pub fn record(&mut self, error: &anyhow::Error) {
+        self.count += 1;
+        self.status = self.status.max(ExitStatus::of(error));
+    }
+
+    /// Fails if any program failed to convert, with the highest exit status among them.
+    pub fn check(self) -> Result<(), Failure> {
+        match self.count {
+            0 => Ok(()),
+            count => Err(Failure { status: self.status, message: format!("Failed to convert {count} program(s)") }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_status() {
+        let parse = anyhow::Error::new(Aleo2JsonError::Parse { offset: 0, message: String::new() });
+        assert_eq!(ExitStatus::of(&parse), ExitStatus::Parse);
+        let io = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::NotFound)).context("Failed to read");
+        assert_eq!(ExitStatus::of(&io).code(), 2);
+        let usage = anyhow::Error::new(Aleo2JsonError::InvalidRename { rename: "x".to_string() });
+        assert_eq!(ExitStatus::of(&usage).code(), 4);
+        assert_eq!(ExitStatus::of(&anyhow::anyhow!("Invalid execution")), ExitStatus::Parse);
+
+        // A batch fails with the highest exit status of its failures.
+        let mut failures = Failures::default();
+        assert!(Failures::default().check().is_ok());
+        failures.record(&parse);
+        failures.record(&io);
+        failures.record(&parse);
+        let failure = failures.check().unwrap_err();
+        assert_eq!(failure.to_string(), "Failed to convert 3 program(s)");
+        assert_eq!(ExitStatus::of(&failure.into()), ExitStatus::Io);
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{
+    network::Network,
+    program::{Identifier, PlaintextType},
+};
+use snarkvm_synthesizer_program::Program;
+
+use serde_json::{json, Map, Value};
+
+/// Returns the given type with each struct it references inlined, i.e. `{ "x": "field", "y": "field" }` for
+/// `point`, and `{ "element": ..., "length": 2 }` for `[point; 2u32]`. A struct missing from the given program,
+/// or referencing itself through the structs being expanded, is left as its name.
+fn expand_type<N: Network>(
+    plaintext_type: &PlaintextType<N>,
+    program: &Program<N>,
+    expanding: &mut Vec<Identifier<N>>,
+) -> Value {
+    match plaintext_type {
+        PlaintextType::Literal(literal_type) => json!(literal_type.to_string()),
+        PlaintextType::Struct(name) => {
+            let Some(struct_) = program.structs().get(name).filter(|_| !expanding.contains(name)) else {
+                return json!(name.to_string());
+            };
+            expanding.push(*name);
+            let members = struct_
+                .members()
+                .iter()
+                .map(|(member, member_type)| (member.to_string(), expand_type(member_type, program, expanding)))
+                .collect::<Map<_, _>>();
+            expanding.pop();
+            Value::Object(members)
+        }
+        PlaintextType::Array(array_type) => json!({
+            "element": expand_type(array_type.next_element_type(), program, expanding),
+            "length": **array_type.length(),
+        }),
+    }
+}
+
+/// Adds the layout of the key and the value of each mapping referencing a struct under `"expanded"`, i.e.
+/// `"value": { "type": "MapValue", ..., "expanded": { "x": "field", "y": "field" } }`, with each struct inlined,
+/// so that the stored layout is self-contained. A key or a value of a literal type is left unchanged.
+pub fn add_type_expansions<N: Network>(json: &mut Value, program: &Program<N>) {
+    for (name, mapping) in program.mappings() {
+        let Some(mapping_json) = json.get_mut("mappings").and_then(|mappings| mappings.get_mut(name.to_string())) else {
+            continue;
+        };
+        let types = [("key", mapping.key().plaintext_type()), ("value", mapping.value().plaintext_type())];
+        for (key, plaintext_type) in types {
+            if !matches!(plaintext_type, PlaintextType::Literal(_)) {
+                mapping_json[key]["expanded"] = expand_type(plaintext_type, program, &mut Vec::new());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_type_expansions() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program layouts.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+struct path:
+    points as [point; 2u32];
+    closed as boolean;
+
+mapping paths:
+    key as u64.public;
+    value as path.public;
+
+mapping origins:
+    key as point.public;
+    value as u8.public;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_type_expansions(&mut json, &program);
+        let point = json!({ "x": "field", "y": "field" });
+        assert_eq!(
+            json["mappings"]["paths"]["value"]["expanded"],
+            json!({ "points": { "element": point, "length": 2 }, "closed": "boolean" })
+        );
+        assert!(json["mappings"]["paths"]["key"].get("expanded").is_none());
+        assert_eq!(json["mappings"]["origins"]["key"]["expanded"], point);
+    }
+}
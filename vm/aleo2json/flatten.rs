@@ -0,0 +1,237 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::opcodes::opcode_of,
+    console::{network::Network, program::Register},
+};
+use snarkvm_synthesizer_program::{CommandTrait, InstructionTrait, Operand, Program};
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+/// Returns a single row of the instruction table.
+/// The destination is `null` if there is none, or the destination registers separated by spaces, i.e. `"r2 r3"`.
+fn row<N: Network>(
+    program: &str,
+    function: &str,
+    block: &str,
+    index: usize,
+    statement: &str,
+    operands: &[Operand<N>],
+    destinations: &[Register<N>],
+) -> Value {
+    let destination = match destinations.is_empty() {
+        true => Value::Null,
+        false => json!(destinations.iter().map(|register| register.to_string()).collect::<Vec<_>>().join(" ")),
+    };
+    json!({
+        "program": program,
+        "function": function,
+        "block": block,
+        "index": index,
+        "opcode": opcode_of(statement),
+        "operands": operands.iter().map(|operand| operand.to_string()).collect::<Vec<_>>(),
+        "destination": destination,
+    })
+}
+
+/// Returns the program as a table with one row per instruction and finalize command, in declaration order, i.e.
+/// `[{ "program": "token.aleo", "function": "mint", "block": "function", "index": 0, "opcode": "cast", ... }]`.
+/// The block is one of `closure`, `function`, or `finalize`.
+pub fn flatten_program<N: Network>(program: &Program<N>) -> Value {
+    let program_id = program.id().to_string();
+    let mut rows = Vec::new();
+
+    for (name, closure) in program.closures() {
+        for (index, instruction) in closure.instructions().iter().enumerate() {
+            let statement = instruction.to_string();
+            let destinations = instruction.destinations();
+            rows.push(row(
+                &program_id,
+                &name.to_string(),
+                "closure",
+                index,
+                &statement,
+                instruction.operands(),
+                &destinations,
+            ));
+        }
+    }
+
+    for (name, function) in program.functions() {
+        let name = name.to_string();
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            let statement = instruction.to_string();
+            let destinations = instruction.destinations();
+            rows.push(row(&program_id, &name, "function", index, &statement, instruction.operands(), &destinations));
+        }
+        if let Some(finalize) = function.finalize_logic() {
+            for (index, command) in finalize.commands().iter().enumerate() {
+                let statement = command.to_string();
+                let destinations = command.destinations();
+                rows.push(row(&program_id, &name, "finalize", index, &statement, &command.operands(), &destinations));
+            }
+        }
+    }
+
+    Value::Array(rows)
+}
+
+/// Returns the instructions of each closure and function of the given program JSON, in declaration order, each in
+/// an object of its own keyed by its UID, i.e. `[{ "mint#0": { "type": "Instruction", ... } }]`.
+/// Unlike the instruction table, each instruction is kept whole, as emitted in the program.
+pub fn instruction_index(json: &Value) -> Value {
+    let functions = ["closures", "functions"].into_iter().filter_map(|section| json[section].as_object());
+    let instructions = functions.flat_map(|functions| functions.values()).flat_map(|function| {
+        function["instructions"].as_array().map(Vec::as_slice).unwrap_or_default()
+    });
+    let entries = instructions.filter_map(|instruction| {
+        let uid = instruction["uid"].as_str()?;
+        Some(json!({ uid: instruction }))
+    });
+    Value::Array(entries.collect())
+}
+
+/// Keeps only the rows of the given instruction table whose opcode and operands match the given regex,
+/// i.e. `hash\.` or `\br0\b`, and whose function is the given function, if any.
+/// The regex is matched against the opcode followed by the operands, separated by spaces, i.e. `add r0 1u64`.
+pub fn grep_rows(table: &mut Value, regex: &Regex, function: Option<&str>) {
+    let Some(rows) = table.as_array_mut() else {
+        return;
+    };
+    rows.retain(|row| {
+        let operands = row["operands"].as_array().into_iter().flatten().filter_map(Value::as_str);
+        let text = std::iter::once(row["opcode"].as_str().unwrap_or_default()).chain(operands).collect::<Vec<_>>();
+        function.map_or(true, |function| row["function"] == function) && regex.is_match(&text.join(" "))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_flatten_program() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program flat.aleo;
+
+mapping totals:
+    key as address.public;
+    value as u64.public;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function deposit:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    async deposit self.caller r1 into r2;
+    output r2 as flat.aleo/deposit.future;
+
+finalize deposit:
+    input r0 as address.public;
+    input r1 as u64.public;
+    set r1 into totals[r0];
+",
+        )
+        .unwrap();
+
+        let rows = flatten_program(&program);
+        let rows = rows.as_array().unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[0],
+            json!({
+                "program": "flat.aleo",
+                "function": "twice",
+                "block": "closure",
+                "index": 0,
+                "opcode": "add",
+                "operands": ["r0", "r0"],
+                "destination": "r1",
+            })
+        );
+        assert_eq!(rows[1]["opcode"], "call");
+        assert_eq!(rows[2]["block"], "function");
+        assert_eq!(rows[2]["index"], 1);
+        assert_eq!(rows[2]["operands"], json!(["self.caller", "r1"]));
+        assert_eq!(rows[3]["block"], "finalize");
+        assert_eq!(rows[3]["opcode"], "set");
+        assert!(rows[3]["destination"].is_null());
+    }
+
+    #[test]
+    fn test_instruction_index() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program index.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function main:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    mul r1 2u64 into r2;
+    output r2 as u64.public;
+",
+        )
+        .unwrap();
+
+        let json = program.to_json();
+        let index = instruction_index(&json);
+        let index = index.as_array().unwrap();
+        let uids = index.iter().flat_map(|entry| entry.as_object().unwrap().keys().cloned()).collect::<Vec<_>>();
+        assert_eq!(uids, ["twice#0", "main#0", "main#1"]);
+        assert_eq!(index[2]["main#1"], json["functions"]["main"]["instructions"][1]);
+    }
+
+    #[test]
+    fn test_grep_rows() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program grep.aleo;
+
+function first:
+    input r0 as field.public;
+    hash.bhp256 r0 into r1 as field;
+    add r1 r0 into r2;
+    output r2 as field.public;
+
+function second:
+    input r0 as field.public;
+    hash.psd2 r0 into r1 as field;
+    output r1 as field.public;
+",
+        )
+        .unwrap();
+
+        let opcodes = |pattern: &str, function: Option<&str>| {
+            let mut table = flatten_program(&program);
+            grep_rows(&mut table, &Regex::new(pattern).unwrap(), function);
+            table.as_array().unwrap().iter().map(|row| row["opcode"].as_str().unwrap().to_string()).collect::<Vec<_>>()
+        };
+        assert_eq!(opcodes(r"^hash\.", None), ["hash.bhp256", "hash.psd2"]);
+        assert_eq!(opcodes(r"^hash\.", Some("second")), ["hash.psd2"]);
+        assert_eq!(opcodes(r"\br1\b", None), ["add"]);
+        assert!(opcodes("mul", None).is_empty());
+    }
+}
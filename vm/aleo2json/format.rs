@@ -0,0 +1,538 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anstyle::{AnsiColor, Color, Style};
+use anyhow::Result;
+use clap::ValueEnum;
+use flate2::{write::GzEncoder, Compression};
+use serde_json::Value;
+use std::{fmt::Write, io::Write as _};
+
+/// The indentation of each level of pretty-printed JSON.
+const INDENT: &str = "  ";
+
+/// The serialization format of the output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Serializes the output as JSON.
+    #[default]
+    Json,
+    /// Serializes the output as YAML.
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Returns the file extension of the output format, i.e. `json`.
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+        }
+    }
+
+    /// Serializes the given value in the output format, indenting JSON if `pretty` is set.
+    pub fn serialize(&self, value: &serde_json::Value, pretty: bool) -> Result<String> {
+        match self {
+            Self::Json => Ok(to_json_string(value, pretty)),
+            Self::Yaml => Ok(serde_yaml::to_string(value)?),
+        }
+    }
+}
+
+/// Serializes the given value as JSON, indented if `pretty` is set, with the serializer of `serde_json`.
+/// Every JSON writer of the output goes through it, so that the `no-std-serialize` feature replaces them all.
+#[cfg(not(feature = "no-std-serialize"))]
+pub fn to_json_string(value: &Value, pretty: bool) -> String {
+    match pretty {
+        true => format!("{value:#}"),
+        false => value.to_string(),
+    }
+}
+
+/// Serializes the given value as JSON, indented if `pretty` is set, by hand, with the same output as the
+/// serializer of `serde_json`, as with the `no-std-serialize` feature.
+#[cfg(feature = "no-std-serialize")]
+pub fn to_json_string(value: &Value, pretty: bool) -> String {
+    to_string_fallback(value, pretty)
+}
+
+/// Serializes the given value as indented JSON if its compact serialization is shorter than `threshold` bytes,
+/// or else as compact JSON, so that small outputs are readable, and large ones small.
+pub fn to_string_by_threshold(value: &Value, threshold: usize) -> Result<String> {
+    let compact = to_json_string(value, false);
+    match compact.len() < threshold {
+        true => Ok(to_json_string(value, true)),
+        false => Ok(compact),
+    }
+}
+
+/// Serializes the given value as indented JSON, like `serde_json::to_string_pretty`,
+/// except that arrays of scalars are rendered on a single line, i.e. `"operands": ["r0", "r1"]`.
+pub fn to_string_pretty_arrays_inline(value: &Value) -> Result<String> {
+    let mut output = String::new();
+    write_pretty_arrays_inline(&mut output, value, 0)?;
+    Ok(output)
+}
+
+/// Writes the given value at the given indentation level.
+fn write_pretty_arrays_inline(output: &mut String, value: &Value, level: usize) -> Result<()> {
+    let is_scalar = |value: &Value| !value.is_array() && !value.is_object();
+    match value {
+        // Render empty collections and arrays of scalars on a single line.
+        Value::Array(array) if array.iter().all(is_scalar) => {
+            let items = array.iter().map(|item| to_json_string(item, false)).collect::<Vec<_>>();
+            write!(output, "[{}]", items.join(", "))?;
+        }
+        Value::Object(object) if object.is_empty() => output.push_str("{}"),
+        Value::Array(array) => {
+            output.push('[');
+            for (index, item) in array.iter().enumerate() {
+                output.push_str(if index == 0 { "\n" } else { ",\n" });
+                output.push_str(&INDENT.repeat(level + 1));
+                write_pretty_arrays_inline(output, item, level + 1)?;
+            }
+            write!(output, "\n{}]", INDENT.repeat(level))?;
+        }
+        Value::Object(object) => {
+            output.push('{');
+            for (index, (key, item)) in object.iter().enumerate() {
+                output.push_str(if index == 0 { "\n" } else { ",\n" });
+                write!(output, "{}{}: ", INDENT.repeat(level + 1), to_json_string(&Value::from(key.as_str()), false))?;
+                write_pretty_arrays_inline(output, item, level + 1)?;
+            }
+            write!(output, "\n{}}}", INDENT.repeat(level))?;
+        }
+        scalar => output.push_str(&to_json_string(scalar, false)),
+    }
+    Ok(())
+}
+
+/// Serializes the given value as JSON, indented if `pretty` is set, byte for byte as `serde_json::to_string` and
+/// `serde_json::to_string_pretty` do, but without their serializer. This is the JSON writer of the
+/// `no-std-serialize` feature, as used by `to_json_string`.
+pub fn to_string_fallback(value: &Value, pretty: bool) -> String {
+    let mut output = String::new();
+    write_fallback(&mut output, value, pretty.then_some(0));
+    output
+}
+
+/// Writes the given value, at the given indentation level if it is indented.
+fn write_fallback(output: &mut String, value: &Value, level: Option<usize>) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(boolean) => output.push_str(if *boolean { "true" } else { "false" }),
+        // A number is displayed as it is serialized, i.e. a float in its shortest round-trip form.
+        Value::Number(number) => output.push_str(&number.to_string()),
+        Value::String(string) => write_escaped(output, string),
+        Value::Array(array) => write_collection(output, ('[', ']'), array.iter().map(|item| (None, item)), level),
+        Value::Object(object) => {
+            write_collection(output, ('{', '}'), object.iter().map(|(key, item)| (Some(key), item)), level)
+        }
+    }
+}
+
+/// Writes the given items of an array, or entries of an object, between the given delimiters. An empty collection
+/// is written on a single line, i.e. `[]`.
+fn write_collection<'a>(
+    output: &mut String,
+    (open, close): (char, char),
+    items: impl ExactSizeIterator<Item = (Option<&'a String>, &'a Value)>,
+    level: Option<usize>,
+) {
+    output.push(open);
+    let is_empty = items.len() == 0;
+    for (index, (key, item)) in items.enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+        if let Some(level) = level {
+            output.push('\n');
+            output.push_str(&INDENT.repeat(level + 1));
+        }
+        if let Some(key) = key {
+            write_escaped(output, key);
+            output.push_str(if level.is_some() { ": " } else { ":" });
+        }
+        write_fallback(output, item, level.map(|level| level + 1));
+    }
+    if let (false, Some(level)) = (is_empty, level) {
+        output.push('\n');
+        output.push_str(&INDENT.repeat(level));
+    }
+    output.push(close);
+}
+
+/// Writes the given string as a JSON string, escaping `"`, `\`, and each control character as `serde_json` does,
+/// i.e. `\n` and `\u001b`. Every other character, including non-ASCII ones, is written as is.
+fn write_escaped(output: &mut String, string: &str) {
+    output.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{c}' => output.push_str("\\f"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+/// Highlights the given serialized JSON for a terminal, i.e. with each key in blue, each string in green, each number
+/// in cyan, and each of `true`, `false`, and `null` in magenta. The layout of the JSON is kept as is.
+pub fn colorize_json(json: &str) -> String {
+    let paint = |output: &mut String, color: AnsiColor, text: &str| {
+        let style = Style::new().fg_color(Some(Color::Ansi(color)));
+        let _ = write!(output, "{}{text}{}", style.render(), style.render_reset());
+    };
+    let mut output = String::with_capacity(json.len() * 2);
+    let mut rest = json;
+    while let Some(c) = rest.chars().next() {
+        let length = match c {
+            '"' => {
+                // Find the closing quote, skipping each escaped character.
+                let mut escaped = false;
+                let end = rest[1..].find(|c| {
+                    let is_end = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    is_end
+                });
+                let length = end.map_or(rest.len(), |end| end + 2);
+                // A string followed by a colon is a key.
+                let color = match rest[length..].trim_start().starts_with(':') {
+                    true => AnsiColor::Blue,
+                    false => AnsiColor::Green,
+                };
+                paint(&mut output, color, &rest[..length]);
+                length
+            }
+            '-' | '0'..='9' => {
+                let is_number = |c: char| matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9');
+                let length = rest.find(|c: char| !is_number(c)).unwrap_or(rest.len());
+                paint(&mut output, AnsiColor::Cyan, &rest[..length]);
+                length
+            }
+            't' | 'f' | 'n' => {
+                let length = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+                paint(&mut output, AnsiColor::Magenta, &rest[..length]);
+                length
+            }
+            c => {
+                output.push(c);
+                c.len_utf8()
+            }
+        };
+        rest = &rest[length..];
+    }
+    output
+}
+
+/// Escapes each non-ASCII character in the given JSON as `\uXXXX`, using a surrogate pair outside of the BMP.
+/// As JSON is only structured by ASCII characters, non-ASCII characters can only occur in strings.
+pub fn escape_non_ascii(json: &str) -> String {
+    let mut output = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c.is_ascii() {
+            true => output.push(c),
+            false => {
+                let mut units = [0; 2];
+                c.encode_utf16(&mut units).iter().for_each(|unit| output.push_str(&format!("\\u{unit:04x}")));
+            }
+        }
+    }
+    output
+}
+
+/// Sorts the keys of every object in the given JSON, recursively, with `--sort-keys`.
+pub fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            let mut entries = std::mem::take(object).into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(first, _), (second, _)| first.cmp(second));
+            for (key, mut value) in entries {
+                sort_keys(&mut value);
+                object.insert(key, value);
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(sort_keys),
+        _ => (),
+    }
+}
+
+/// Returns the JSON pointer of the first object in the given JSON whose keys are not in sorted order, if any,
+/// i.e. `/functions/main`, or `""` for the root. The objects are visited depth-first, in the order of the output.
+pub fn first_unsorted_object(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(object) => {
+            if object.keys().zip(object.keys().skip(1)).any(|(previous, key)| previous > key) {
+                return Some(String::new());
+            }
+            object.iter().find_map(|(key, value)| {
+                let pointer = first_unsorted_object(value)?;
+                Some(format!("/{}{pointer}", key.replace('~', "~0").replace('/', "~1")))
+            })
+        }
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .find_map(|(index, value)| first_unsorted_object(value).map(|pointer| format!("/{index}{pointer}"))),
+        _ => None,
+    }
+}
+
+/// Returns the given value serialized as JSON, indented as if nested at the given level, if `pretty` is set.
+fn serialize_nested(value: &Value, pretty: bool, level: usize) -> Result<String> {
+    match pretty {
+        true => Ok(to_json_string(value, true).replace('\n', &format!("\n{}", INDENT.repeat(level)))),
+        false => Ok(to_json_string(value, false)),
+    }
+}
+
+/// Returns the separator before the entry of the given index of an object at the given level, i.e. `,`.
+fn entry_separator(index: usize, pretty: bool, level: usize) -> String {
+    match (index, pretty) {
+        (0, false) => String::new(),
+        (_, false) => ",".to_string(),
+        (0, true) => format!("\n{}", INDENT.repeat(level + 1)),
+        (_, true) => format!(",\n{}", INDENT.repeat(level + 1)),
+    }
+}
+
+/// Returns the given key of an object, followed by the key-value separator, i.e. `"main":`.
+fn entry_key(key: &str, pretty: bool) -> Result<String> {
+    Ok(format!("{}{}", to_json_string(&Value::from(key), false), if pretty { ": " } else { ":" }))
+}
+
+/// Returns the end of a non-empty object at the given level, i.e. `}`.
+fn object_end(pretty: bool, level: usize) -> String {
+    match pretty {
+        true => format!("\n{}}}", INDENT.repeat(level)),
+        false => "}".to_string(),
+    }
+}
+
+/// Writes the given JSON to the given writer, as it would be serialized as a whole, with the functions of a program
+/// serialized `chunk_size` at a time. Each chunk is written, and the writer flushed, before the next is serialized,
+/// and each function is dropped once serialized, so that the output of the functions is never held at once.
+/// Each chunk is passed through `escape`, i.e. [`escape_non_ascii`], before it is written.
+pub fn write_chunked<W: std::io::Write>(
+    writer: &mut W,
+    json: Value,
+    pretty: bool,
+    chunk_size: usize,
+    escape: impl Fn(String) -> String,
+) -> Result<()> {
+    let object = match json {
+        Value::Object(object) if !object.is_empty() => object,
+        json => return Ok(writer.write_all(escape(serialize_nested(&json, pretty, 0)?).as_bytes())?),
+    };
+    let mut output = "{".to_string();
+    for (index, (key, value)) in object.into_iter().enumerate() {
+        output.push_str(&entry_separator(index, pretty, 0));
+        output.push_str(&entry_key(&key, pretty)?);
+        let functions = match value {
+            Value::Object(functions) if key == "functions" && !functions.is_empty() => functions,
+            value => {
+                output.push_str(&serialize_nested(&value, pretty, 1)?);
+                continue;
+            }
+        };
+        output.push('{');
+        for (index, (name, function)) in functions.into_iter().enumerate() {
+            output.push_str(&entry_separator(index, pretty, 1));
+            output.push_str(&entry_key(&name, pretty)?);
+            output.push_str(&serialize_nested(&function, pretty, 2)?);
+            if (index + 1) % chunk_size.max(1) == 0 {
+                writer.write_all(escape(std::mem::take(&mut output)).as_bytes())?;
+                writer.flush()?;
+            }
+        }
+        output.push_str(&object_end(pretty, 1));
+    }
+    output.push_str(&object_end(pretty, 0));
+    writer.write_all(escape(output).as_bytes())?;
+    Ok(writer.flush()?)
+}
+
+/// Compresses the given output with gzip as a whole, writing it to the given writer.
+pub fn write_gzip<W: std::io::Write>(writer: W, output: &str) -> Result<()> {
+    let mut encoder = GzEncoder::new(writer, Compression::default());
+    encoder.write_all(output.as_bytes())?;
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_write_chunked() {
+        let function = |name: &str| json!({ "name": name, "inputs": [], "outputs": ["u64.public"], "finalize": {} });
+        let value = json!({
+            "id": "tokén.aleo",
+            "structs": {},
+            "functions": { "mint": function("mint"), "burn": function("burn"), "send": function("send") },
+            "dependencies": [],
+        });
+        for pretty in [false, true] {
+            let expected = OutputFormat::Json.serialize(&value, pretty).unwrap();
+            for chunk_size in [0, 1, 2, 3, 10] {
+                let mut output = Vec::new();
+                write_chunked(&mut output, value.clone(), pretty, chunk_size, |chunk| chunk).unwrap();
+                assert_eq!(String::from_utf8(output).unwrap(), expected);
+            }
+            // Each chunk is escaped alike.
+            let mut output = Vec::new();
+            write_chunked(&mut output, value.clone(), pretty, 1, |chunk| escape_non_ascii(&chunk)).unwrap();
+            assert_eq!(String::from_utf8(output).unwrap(), escape_non_ascii(&expected));
+        }
+        // Any other value is written as a whole.
+        for value in [json!({}), json!([1, 2]), json!({ "functions": {} })] {
+            let mut output = Vec::new();
+            write_chunked(&mut output, value.clone(), true, 1, |chunk| chunk).unwrap();
+            assert_eq!(String::from_utf8(output).unwrap(), serde_json::to_string_pretty(&value).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_serialize() {
+        let value = json!({ "id": "token.aleo", "functions": ["mint", "burn"] });
+
+        assert_eq!(
+            OutputFormat::Json.serialize(&value, false).unwrap(),
+            r#"{"id":"token.aleo","functions":["mint","burn"]}"#
+        );
+        assert_eq!(
+            OutputFormat::Json.serialize(&value, true).unwrap(),
+            "{\n  \"id\": \"token.aleo\",\n  \"functions\": [\n    \"mint\",\n    \"burn\"\n  ]\n}"
+        );
+        assert_eq!(
+            OutputFormat::Yaml.serialize(&value, false).unwrap(),
+            "id: token.aleo\nfunctions:\n- mint\n- burn\n"
+        );
+    }
+
+    #[test]
+    fn test_to_string_by_threshold() {
+        let value = json!({ "id": "token.aleo", "functions": ["mint", "burn"] });
+        let compact = OutputFormat::Json.serialize(&value, false).unwrap();
+        assert_eq!(compact.len(), 47);
+
+        // The threshold is on the length of the compact serialization, not of the indented one.
+        assert_eq!(to_string_by_threshold(&value, 48).unwrap(), OutputFormat::Json.serialize(&value, true).unwrap());
+        assert_eq!(to_string_by_threshold(&value, 47).unwrap(), compact);
+        assert_eq!(to_string_by_threshold(&value, 0).unwrap(), compact);
+    }
+
+    #[test]
+    fn test_to_string_pretty_arrays_inline() {
+        let value = json!({
+            "id": "token.aleo",
+            "operands": ["r0", 1, null],
+            "empty": [],
+            "none": {},
+            "instructions": [{ "opcode": "add", "operands": ["r0", "r1"] }],
+        });
+        assert_eq!(
+            to_string_pretty_arrays_inline(&value).unwrap(),
+            r#"{
+  "id": "token.aleo",
+  "operands": ["r0", 1, null],
+  "empty": [],
+  "none": {},
+  "instructions": [
+    {
+      "opcode": "add",
+      "operands": ["r0", "r1"]
+    }
+  ]
+}"#
+        );
+
+        // Without arrays of scalars, the output matches the stock pretty printer.
+        let value = json!({ "a": { "b": [{ "c": "\"quoted\"" }] }, "d": {} });
+        assert_eq!(to_string_pretty_arrays_inline(&value).unwrap(), serde_json::to_string_pretty(&value).unwrap());
+    }
+
+    #[test]
+    fn test_escape_non_ascii() {
+        let value = json!({ "comment": "caf\u{e9} \u{1f980}", "id": "token.aleo" });
+        let escaped = escape_non_ascii(&serde_json::to_string(&value).unwrap());
+        assert_eq!(escaped, r#"{"comment":"caf\u00e9 \ud83e\udd80","id":"token.aleo"}"#);
+        assert!(escaped.is_ascii());
+        assert_eq!(serde_json::from_str::<Value>(&escaped).unwrap(), value);
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let mut value = json!({ "b": [{ "d": 1, "c": 2 }], "a": { "f": null, "e": {} } });
+        assert_eq!(first_unsorted_object(&value), Some(String::new()));
+        sort_keys(&mut value);
+        assert_eq!(first_unsorted_object(&value), None);
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"a":{"e":{},"f":null},"b":[{"c":2,"d":1}]}"#);
+    }
+
+    #[test]
+    fn test_first_unsorted_object() {
+        assert_eq!(first_unsorted_object(&json!({ "a": [{ "b": 1, "c": { "d": {}, "e/f": null } }] })), None);
+        assert_eq!(first_unsorted_object(&json!({ "b": 1, "a": 2 })), Some(String::new()));
+        let value = json!({ "a": [1, { "c/d": { "y": 1, "x": 2 } }], "b": { "z": 1, "y": 2 } });
+        assert_eq!(first_unsorted_object(&value), Some("/a/1/c~1d".to_string()));
+    }
+
+    #[test]
+    fn test_colorize_json() {
+        let value = json!({ "id": "a\\\"b", "n": [-1.5e3, true, null], "o": {} });
+        let json = serde_json::to_string_pretty(&value).unwrap();
+        let colored = colorize_json(&json);
+        let blue = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Blue)));
+        assert!(colored.contains(&format!("{}\"id\"{}", blue.render(), blue.render_reset())));
+        assert!(colored.contains("\"a\\\\\\\"b\""));
+        // Without the escape sequences, the JSON is unchanged.
+        let plain = regex::Regex::new("\u{1b}\\[[0-9;]*m").unwrap().replace_all(&colored, "");
+        assert_eq!(plain, json);
+    }
+
+    #[test]
+    fn test_write_gzip() {
+        let mut compressed = Vec::new();
+        write_gzip(&mut compressed, r#"{"id":"token.aleo"}"#).unwrap();
+        assert_eq!(&compressed[..2], [0x1f, 0x8b]);
+
+        let mut decompressed = String::new();
+        std::io::Read::read_to_string(&mut flate2::read::GzDecoder::new(&compressed[..]), &mut decompressed).unwrap();
+        assert_eq!(decompressed, r#"{"id":"token.aleo"}"#);
+    }
+
+    #[test]
+    fn test_to_string_fallback() {
+        let value = json!({
+            "id": "tokén.aleo",
+            "escapes": "\"\\\u{8}\u{c}\n\r\t\u{1b}\u{7f}",
+            "numbers": [0, -1, u64::MAX, 1.5, 1e300],
+            "empty": { "array": [], "object": {} },
+            "flags": [true, false, null],
+        });
+        assert_eq!(to_string_fallback(&value, false), serde_json::to_string(&value).unwrap());
+        assert_eq!(to_string_fallback(&value, true), serde_json::to_string_pretty(&value).unwrap());
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{
+    network::Network,
+    program::{FinalizeType, ValueType},
+};
+use snarkvm_synthesizer_program::{Command, Instruction, Program};
+
+use serde_json::{json, Value};
+
+/// Adds the future each `async` instruction schedules under `"async"`, i.e.
+/// `"async": { "target": "token.aleo/transfer", "arguments": ["r0"], "future": "r1" }`,
+/// and the future each `await` command waits on under `"await"`, i.e.
+/// `"await": { "future": "r0", "target": "credits.aleo/transfer_public", "input": 0 }`.
+/// The target of an awaited future is given by the finalize input it is read from. Each future output of a function
+/// is linked to the finalize logic consuming it under `"future"`, i.e.
+/// `"future": { "target": "token.aleo/transfer", "finalize": "transfer", "async": 2 }`, with the `async` instruction
+/// producing it. The finalize is `null` if the future is not of the finalize logic of the function.
+pub fn add_futures<N: Network>(json: &mut Value, program: &Program<N>) {
+    for (name, function) in program.functions() {
+        let Some(function_json) = json.get_mut("functions").and_then(|functions| functions.get_mut(name.to_string()))
+        else {
+            continue;
+        };
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            let Instruction::Async(async_) = instruction else {
+                continue;
+            };
+            function_json["instructions"][index]["async"] = json!({
+                "target": format!("{}/{}", program.id(), async_.function_name()),
+                "arguments": async_.operands().iter().map(|operand| operand.to_string()).collect::<Vec<_>>(),
+                "future": async_.destinations().iter().map(|register| register.to_string()).collect::<String>(),
+            });
+        }
+
+        for (index, output) in function.outputs().iter().enumerate() {
+            let ValueType::Future(locator) = output.value_type() else {
+                continue;
+            };
+            let register = output.operand().to_string();
+            let producer = function.instructions().iter().position(|instruction| match instruction {
+                Instruction::Async(async_) => {
+                    async_.destinations().iter().any(|destination| destination.to_string() == register)
+                }
+                _ => false,
+            });
+            let consumed = function.finalize_logic().is_some()
+                && locator.program_id() == program.id()
+                && locator.resource() == name;
+            function_json["outputs"][index]["future"] = json!({
+                "target": locator.to_string(),
+                "finalize": consumed.then(|| name.to_string()),
+                "async": producer,
+            });
+        }
+
+        let Some(finalize) = function.finalize_logic() else {
+            continue;
+        };
+        for (index, command) in finalize.commands().iter().enumerate() {
+            let Command::Await(await_) = command else {
+                continue;
+            };
+            let input = finalize.inputs().iter().position(|input| input.register() == await_.register());
+            let target = input.and_then(|input| match finalize.inputs()[input].finalize_type() {
+                FinalizeType::Future(locator) => Some(locator.to_string()),
+                FinalizeType::Plaintext(_) => None,
+            });
+            function_json["finalize_logic"]["commands"][index]["await"] = json!({
+                "future": await_.register().to_string(),
+                "target": target,
+                "input": input,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_futures() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+
+program futures.aleo;
+
+function pay:
+    input r0 as address.public;
+    input r1 as u64.public;
+    call credits.aleo/transfer_public r0 r1 into r2;
+    async pay r2 r1 into r3;
+    output r3 as futures.aleo/pay.future;
+
+finalize pay:
+    input r0 as credits.aleo/transfer_public.future;
+    input r1 as u64.public;
+    await r0;
+    add r1 r1 into r2;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_futures(&mut json, &program);
+        let function = &json["functions"]["pay"];
+        assert!(function["instructions"][0].get("async").is_none());
+        assert_eq!(
+            function["instructions"][1]["async"],
+            json!({ "target": "futures.aleo/pay", "arguments": ["r2", "r1"], "future": "r3" })
+        );
+        assert_eq!(
+            function["outputs"][0]["future"],
+            json!({ "target": "futures.aleo/pay", "finalize": "pay", "async": 1 })
+        );
+        let commands = &function["finalize_logic"]["commands"];
+        assert_eq!(
+            commands[0]["await"],
+            json!({ "future": "r0", "target": "credits.aleo/transfer_public", "input": 0 })
+        );
+        assert!(commands[1].get("await").is_none());
+    }
+}
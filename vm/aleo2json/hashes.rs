@@ -0,0 +1,216 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{walk_program, NetworkName, NodeVisitor},
+    console::{network::Network, program::Identifier},
+};
+use snarkvm_synthesizer_program::Program;
+
+use regex::{Captures, Regex};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, str::FromStr};
+
+/// Returns the hex-encoded SHA-256 digest of the given canonical serialization.
+pub(crate) fn sha256_hex(canonical: &str) -> String {
+    format!("{:x}", Sha256::digest(canonical.as_bytes()))
+}
+
+/// Returns the given printed function or closure with its registers renumbered in order of first use, i.e.
+/// `r0`, `r2`, `r5` as `r0`, `r1`, `r2`. The registers of its finalize logic are renumbered on their own.
+fn normalize_registers(body: &str) -> String {
+    // A register is not preceded by a `.`, as is a struct member named like one, i.e. `r0.r1`.
+    let register = Regex::new(r"(^|[^.\w])r(\d+)\b").unwrap();
+    let mut numbers = HashMap::new();
+    let mut normalized = String::with_capacity(body.len());
+    for line in body.lines() {
+        if line.trim_start().starts_with("finalize ") {
+            numbers.clear();
+        }
+        let line = register.replace_all(line, |captures: &Captures| {
+            let next = numbers.len();
+            let number = *numbers.entry(captures[2].to_string()).or_insert(next);
+            format!("{}r{number}", &captures[1])
+        });
+        normalized.push_str(&line);
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Returns the normalized form of the given program, i.e. its printed definitions in sorted order, with the
+/// registers of each function and closure renumbered by `normalize_registers`. Semantically identical programs
+/// differing only in the order of their definitions, or in the numbering of their registers, have the same form.
+fn normalize_program<N: Network>(program: &Program<N>) -> String {
+    let mut definitions = program.imports().values().map(ToString::to_string).collect::<Vec<_>>();
+    definitions.extend(program.mappings().values().map(ToString::to_string));
+    definitions.extend(program.structs().values().map(ToString::to_string));
+    definitions.extend(program.records().values().map(ToString::to_string));
+    definitions.extend(program.closures().values().map(|closure| normalize_registers(&closure.to_string())));
+    definitions.extend(program.functions().values().map(|function| normalize_registers(&function.to_string())));
+    definitions.sort();
+    format!("program {};\n\n{}", program.id(), definitions.join("\n"))
+}
+
+/// Adds the SHA-256 digest of the canonical form of the program under `"program_hash"`, and of each function
+/// and closure body (including its finalize logic) under `"body_hash"`. The canonical form is the printed program,
+/// so the digests do not depend on the whitespace or the comments of the source. If `normalize` is set, the
+/// registers are renumbered, and the definitions sorted, before hashing, as in `normalize_program`.
+pub fn add_hashes<N: Network>(json: &mut Value, program: &Program<N>, normalize: bool) {
+    walk_program(json, &mut Hasher::new(program, normalize));
+}
+
+/// Returns the fingerprint of the given program on the given network, i.e. a single stable identifier of it, as the
+/// hex-encoded SHA-256 digest of its ID, the name of the network, and the hex-encoded SHA-256 digest of its canonical
+/// form (as in `"program_hash"`, without normalization), each followed by a line break, i.e.
+/// `sha256("token.aleo\ntestnet3\n<program hash>\n")`.
+pub fn fingerprint<N: Network>(program: &Program<N>, network: NetworkName) -> String {
+    let program_hash = sha256_hex(&program.to_string());
+    sha256_hex(&format!("{}\n{}\n{program_hash}\n", program.id(), network.name()))
+}
+
+/// A visitor adding the hashes of the given program, as `add_hashes` does.
+pub struct Hasher<'a, N: Network> {
+    /// The program to hash.
+    program: &'a Program<N>,
+    /// Whether to normalize the program before hashing it.
+    normalize: bool,
+}
+
+impl<'a, N: Network> Hasher<'a, N> {
+    /// Initializes a visitor adding the hashes of the given program, normalized if `normalize` is set.
+    pub fn new(program: &'a Program<N>, normalize: bool) -> Self {
+        Self { program, normalize }
+    }
+}
+
+impl<N: Network> NodeVisitor for Hasher<'_, N> {
+    fn visit_program(&mut self, json: &mut Value) {
+        let canonical = match self.normalize {
+            true => normalize_program(self.program),
+            false => self.program.to_string(),
+        };
+        json["program_hash"] = Value::String(sha256_hex(&canonical));
+    }
+
+    fn visit_function(&mut self, section: &str, name: &str, json: &mut Value) {
+        let Ok(name) = Identifier::<N>::from_str(name) else {
+            return;
+        };
+        let body = match section {
+            "functions" => self.program.functions().get(&name).map(ToString::to_string),
+            _ => self.program.closures().get(&name).map(ToString::to_string),
+        };
+        if let Some(body) = body {
+            let body = if self.normalize { normalize_registers(&body) } else { body };
+            json["body_hash"] = Value::String(sha256_hex(&body));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    const SOURCE: &str = r"
+program hashes.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function first:
+    input r0 as u64.public;
+    add r0 1u64 into r1;
+    output r1 as u64.public;
+
+function second:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    output r1 as u64.public;
+";
+
+    /// Returns the JSON of the given source, with its hashes, normalized if `normalize` is set.
+    fn hashes_with(source: &str, normalize: bool) -> Value {
+        let program = parse_program::<CurrentNetwork>(source).unwrap();
+        let mut json = program.to_json();
+        add_hashes(&mut json, &program, normalize);
+        json
+    }
+
+    /// Returns the JSON of the given source, with its hashes.
+    fn hashes(source: &str) -> Value {
+        hashes_with(source, false)
+    }
+
+    #[test]
+    fn test_add_hashes() {
+        let json = hashes(SOURCE);
+        assert_eq!(json["program_hash"].as_str().unwrap().len(), 64);
+        assert_ne!(json["functions"]["first"]["body_hash"], json["functions"]["second"]["body_hash"]);
+        assert!(json["closures"]["twice"]["body_hash"].is_string());
+
+        // The hashes do not depend on whitespace.
+        assert_eq!(hashes(&SOURCE.replace("add r0 1u64", "add   r0   1u64")), json);
+
+        // Changing a function only changes its own body hash, and the program hash.
+        let changed = hashes(&SOURCE.replace("add r0 1u64", "add r0 2u64"));
+        assert_ne!(changed["program_hash"], json["program_hash"]);
+        assert_ne!(changed["functions"]["first"]["body_hash"], json["functions"]["first"]["body_hash"]);
+        assert_eq!(changed["functions"]["second"]["body_hash"], json["functions"]["second"]["body_hash"]);
+        assert_eq!(changed["closures"]["twice"]["body_hash"], json["closures"]["twice"]["body_hash"]);
+    }
+
+    #[test]
+    fn test_normalized_hashes() {
+        let renumbered = SOURCE.replace("add r0 1u64 into r1;\n    output r1", "add r0 1u64 into r7;\n    output r7");
+        let renamed = SOURCE.replace("function first:", "function zeroth:");
+        // Without normalization, the numbering of the registers changes the hashes.
+        assert_ne!(hashes(&renumbered)["program_hash"], hashes(SOURCE)["program_hash"]);
+
+        let json = hashes_with(SOURCE, true);
+        let renumbered = hashes_with(&renumbered, true);
+        assert_eq!(renumbered["program_hash"], json["program_hash"]);
+        assert_eq!(renumbered["functions"]["first"]["body_hash"], json["functions"]["first"]["body_hash"]);
+        // A renamed function is still a different program.
+        assert_ne!(hashes_with(&renamed, true)["program_hash"], json["program_hash"]);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let program = parse_program::<CurrentNetwork>(SOURCE).unwrap();
+        let expected = fingerprint(&program, NetworkName::Testnet3);
+        // The fingerprint is reproducible from the ID, the network, and the program hash.
+        let program_hash = hashes(SOURCE)["program_hash"].as_str().unwrap().to_string();
+        assert_eq!(expected, sha256_hex(&format!("hashes.aleo\ntestnet3\n{program_hash}\n")));
+
+        // The fingerprint does not depend on whitespace, but on the program.
+        let changed = parse_program::<CurrentNetwork>(&SOURCE.replace("add r0 1u64", "add   r0   1u64")).unwrap();
+        assert_eq!(fingerprint(&changed, NetworkName::Testnet3), expected);
+        let changed = parse_program::<CurrentNetwork>(&SOURCE.replace("add r0 1u64", "add r0 2u64")).unwrap();
+        assert_ne!(fingerprint(&changed, NetworkName::Testnet3), expected);
+    }
+
+    #[test]
+    fn test_normalize_registers() {
+        let body = "function main:\n    input r3 as u64.public;\n    add r3 r3.r9 into r9;\n\
+                    finalize main:\n    input r4 as u64.public;\n";
+        let expected = "function main:\n    input r0 as u64.public;\n    add r0 r0.r9 into r1;\n\
+                        finalize main:\n    input r0 as u64.public;\n";
+        assert_eq!(normalize_registers(body), expected);
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{hashes::sha256_hex, parse_program, program_to_json, ConvertOptions},
+    console::network::Network,
+};
+use snarkvm_synthesizer_program::Program;
+
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+/// Resolves the imports of the given program from the given directory, recursively.
+/// Returns a map from each imported program ID to its JSON, i.e. `{ "token.aleo": { ... } }`.
+/// An import that cannot be resolved becomes an error entry, i.e. `{ "token.aleo": { "error": "..." } }`.
+/// Each resolved import has the SHA-256 digest of its printed program under `"hash"`, as `"program_hash"` is,
+/// to pin the exact version of each dependency.
+pub fn resolve_imports<N: Network>(program: &Program<N>, import_dir: &Path, options: &ConvertOptions) -> Value {
+    let mut resolved = Map::new();
+    let mut stack = vec![program.id().to_string()];
+    resolve_imports_into(program, import_dir, options, &mut stack, &mut resolved);
+    Value::Object(resolved)
+}
+
+/// Resolves the imports of the given program into the given map.
+/// The stack holds the program IDs currently being resolved, to detect cyclic imports.
+fn resolve_imports_into<N: Network>(
+    program: &Program<N>,
+    import_dir: &Path,
+    options: &ConvertOptions,
+    stack: &mut Vec<String>,
+    resolved: &mut Map<String, Value>,
+) {
+    for program_id in program.imports().keys() {
+        let program_id = program_id.to_string();
+        // Skip imports that have already been resolved.
+        if resolved.contains_key(&program_id) {
+            continue;
+        }
+        // Ensure the import does not (transitively) import itself.
+        if stack.contains(&program_id) {
+            let error = format!("Cyclic import of '{program_id}' (via {})", stack.join(" -> "));
+            resolved.insert(program_id, json!({ "error": error }));
+            continue;
+        }
+
+        // Read and parse the imported program, i.e. `{import_dir}/token.aleo`.
+        let path = import_dir.join(&program_id);
+        let import = match std::fs::read_to_string(&path) {
+            Ok(string) => parse_program::<N>(&string).map_err(|error| error.to_string()),
+            Err(error) => Err(format!("Failed to read '{}': {error}", path.display())),
+        };
+        let import = import.and_then(|import| {
+            let mut json = program_to_json(&import, options).map_err(|error| error.to_string())?;
+            // The instruction table of `--flatten` is an array, which has no room for the hash.
+            if let Some(object) = json.as_object_mut() {
+                object.insert("hash".to_string(), Value::String(sha256_hex(&import.to_string())));
+            }
+            Ok((import, json))
+        });
+        match import {
+            Ok((import, json)) => {
+                // Insert the import before resolving its own imports, so that it is only converted once.
+                resolved.insert(program_id.clone(), json);
+                stack.push(program_id);
+                resolve_imports_into(&import, import_dir, options, stack, resolved);
+                stack.pop();
+            }
+            Err(error) => {
+                resolved.insert(program_id, json!({ "error": error }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::CurrentNetwork;
+
+    #[test]
+    fn test_resolve_imports() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(
+            directory.path().join("token.aleo"),
+            "import ledger.aleo;\nprogram token.aleo;\n\nfunction noop:\n    input r0 as u8.private;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            directory.path().join("ledger.aleo"),
+            "import main.aleo;\nprogram ledger.aleo;\n\nfunction noop:\n    input r0 as u8.private;\n",
+        )
+        .unwrap();
+        std::fs::write(directory.path().join("broken.aleo"), "program broken.aleo;\n\nfunction").unwrap();
+
+        let program = parse_program::<CurrentNetwork>(
+            "import token.aleo;\nimport missing.aleo;\nimport broken.aleo;\nprogram main.aleo;\n\nfunction noop:\n    input r0 as u8.private;\n",
+        )
+        .unwrap();
+
+        let resolved = resolve_imports(&program, directory.path(), &ConvertOptions::new());
+        let resolved = resolved.as_object().unwrap();
+        assert_eq!(resolved.len(), 5);
+        // Check the imports, including the transitive import.
+        assert_eq!(resolved["token.aleo"]["type"], "ProgramCore");
+        assert_eq!(resolved["ledger.aleo"]["type"], "ProgramCore");
+        // Check the hash of each import, which is that of its printed program, whatever its whitespace.
+        let token = std::fs::read_to_string(directory.path().join("token.aleo")).unwrap();
+        let token = parse_program::<CurrentNetwork>(&token).unwrap();
+        assert_eq!(resolved["token.aleo"]["hash"], sha256_hex(&token.to_string()));
+        assert_ne!(resolved["token.aleo"]["hash"], resolved["ledger.aleo"]["hash"]);
+        assert!(resolved["missing.aleo"].get("hash").is_none());
+        // Check the error entries.
+        assert!(resolved["missing.aleo"]["error"].as_str().unwrap().starts_with("Failed to read"));
+        assert!(resolved["broken.aleo"]["error"].is_string());
+        assert!(resolved["main.aleo"]["error"].as_str().unwrap().starts_with("Cyclic import of 'main.aleo'"));
+    }
+}
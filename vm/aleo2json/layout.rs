@@ -0,0 +1,214 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{
+    account::Signature,
+    network::{prelude::SizeInBits, Network},
+    program::{Identifier, LiteralType, PlaintextType},
+    types::{Address, Boolean, Field, Group, Scalar, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8},
+};
+use snarkvm_synthesizer_program::Program;
+
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// The number of bits of the variant of a plaintext, i.e. a literal, a struct, or an array.
+const VARIANT_BITS: u64 = 2;
+/// The number of bits of the type of a literal.
+const LITERAL_TYPE_BITS: u64 = 8;
+/// The number of bits of the size of a literal, of a struct member, or of an array element.
+const SIZE_BITS: u64 = 16;
+/// The number of bits of the number of members of a struct.
+const STRUCT_LENGTH_BITS: u64 = 8;
+/// The number of bits of the length of an array.
+const ARRAY_LENGTH_BITS: u64 = 32;
+/// The number of bits of the size of the name of a struct member.
+const NAME_SIZE_BITS: u64 = 8;
+/// The number of bits of the visibility of a record entry.
+const VISIBILITY_BITS: u64 = 2;
+
+/// Returns the number of bits of a literal of the given type, or `None` for a string, whose size varies.
+fn literal_bits<N: Network>(literal_type: &LiteralType) -> Option<u64> {
+    let bits = match literal_type {
+        LiteralType::Address => Address::<N>::size_in_bits(),
+        LiteralType::Boolean => Boolean::<N>::size_in_bits(),
+        LiteralType::Field => Field::<N>::size_in_bits(),
+        LiteralType::Group => Group::<N>::size_in_bits(),
+        LiteralType::I8 => I8::<N>::size_in_bits(),
+        LiteralType::I16 => I16::<N>::size_in_bits(),
+        LiteralType::I32 => I32::<N>::size_in_bits(),
+        LiteralType::I64 => I64::<N>::size_in_bits(),
+        LiteralType::I128 => I128::<N>::size_in_bits(),
+        LiteralType::U8 => U8::<N>::size_in_bits(),
+        LiteralType::U16 => U16::<N>::size_in_bits(),
+        LiteralType::U32 => U32::<N>::size_in_bits(),
+        LiteralType::U64 => U64::<N>::size_in_bits(),
+        LiteralType::U128 => U128::<N>::size_in_bits(),
+        LiteralType::Scalar => Scalar::<N>::size_in_bits(),
+        LiteralType::Signature => Signature::<N>::size_in_bits(),
+        LiteralType::String => return None,
+    };
+    Some(bits as u64)
+}
+
+/// Returns the number of bits of a plaintext of the given type, given the number of bits of each struct declared
+/// before it, or `None` if it contains a string, whose size varies.
+fn type_bits<N: Network>(
+    plaintext_type: &PlaintextType<N>,
+    structs: &HashMap<Identifier<N>, Option<u64>>,
+) -> Option<u64> {
+    match plaintext_type {
+        PlaintextType::Literal(literal_type) => {
+            Some(VARIANT_BITS + LITERAL_TYPE_BITS + SIZE_BITS + literal_bits::<N>(literal_type)?)
+        }
+        PlaintextType::Struct(name) => structs.get(name).copied().flatten(),
+        PlaintextType::Array(array_type) => {
+            let element = SIZE_BITS + type_bits(array_type.next_element_type(), structs)?;
+            Some(VARIANT_BITS + ARRAY_LENGTH_BITS + **array_type.length() as u64 * element)
+        }
+    }
+}
+
+/// Returns the layout of the given members, by name, i.e. `{ "x": { "offset": 34, "width": 279 } }`, and the end
+/// of the last member. Each member is preceded by `header` bits, given its name, from the `start` offset.
+/// The offset and the width are `null` once a string member makes them vary.
+fn members_layout<'a, N: Network>(
+    members: impl Iterator<Item = (&'a Identifier<N>, &'a PlaintextType<N>)>,
+    structs: &HashMap<Identifier<N>, Option<u64>>,
+    start: u64,
+    header: impl Fn(&Identifier<N>) -> u64,
+) -> (Map<String, Value>, Option<u64>) {
+    let (mut layout, mut offset) = (Map::new(), Some(start));
+    for (name, plaintext_type) in members {
+        let member_offset = offset.map(|offset| offset + header(name));
+        let width = type_bits(plaintext_type, structs);
+        layout.insert(name.to_string(), json!({ "offset": member_offset, "width": width }));
+        offset = member_offset.zip(width).map(|(offset, width)| offset + width);
+    }
+    (layout, offset)
+}
+
+/// Adds the bit offset and the bit width of each member of each struct and record, in their `to_bits_le` encoding,
+/// under `"layout"`, i.e. `"layout": { "x": { "offset": 34, "width": 279 } }`. The offset of a struct member is
+/// from the start of the struct, and the offset of a record entry from the start of the data of the record, after
+/// its owner. The width includes the variant, type, and size bits of the value. As each struct is declared after
+/// the structs it uses, the size of each is computed once, in declaration order.
+pub fn add_layouts<N: Network>(json: &mut Value, program: &Program<N>) {
+    let name_bits = |name: &Identifier<N>| name.size_in_bits() as u64;
+    let mut structs = HashMap::new();
+    for (name, struct_) in program.structs() {
+        // Each member is preceded by the size of its name, its name, and the size of its value.
+        let header = |name: &Identifier<N>| NAME_SIZE_BITS + name_bits(name) + SIZE_BITS;
+        let start = VARIANT_BITS + STRUCT_LENGTH_BITS;
+        let (layout, end) = members_layout(struct_.members().iter(), &structs, start, header);
+        json["structs"][name.to_string()]["layout"] = Value::Object(layout);
+        structs.insert(*name, end);
+    }
+    for (name, record) in program.records() {
+        // Each entry is preceded by its name, and its visibility.
+        let header = |name: &Identifier<N>| name_bits(name) + VISIBILITY_BITS;
+        let entries = record.entries().iter().map(|(name, entry)| (name, entry.plaintext_type()));
+        let (layout, _) = members_layout(entries, &structs, 0, header);
+        json["records"][name.to_string()]["layout"] = Value::Object(layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{parse_program, CurrentNetwork},
+        console::{
+            network::prelude::ToBits,
+            program::{Entry, Plaintext, Record},
+        },
+    };
+
+    use std::str::FromStr;
+
+    const PROGRAM: &str = r"
+program layout.aleo;
+
+struct point:
+    x as u8;
+    y as field;
+
+struct segment:
+    ends as [point; 2u32];
+    label as u16;
+
+struct named:
+    name as string;
+    after as u8;
+
+record coin:
+    owner as address.private;
+    amount as u64.private;
+    origin as point.public;
+
+function main:
+    input r0 as u8.public;
+";
+
+    #[test]
+    fn test_add_layouts() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let mut json = program.to_json();
+        add_layouts(&mut json, &program);
+
+        // Check each offset and width against the encoding of a struct.
+        let point = Plaintext::<CurrentNetwork>::from_str("{ x: 7u8, y: 1field }").unwrap();
+        let bits = point.to_bits_le();
+        let range = |layout: &Value| {
+            let (offset, width) = (layout["offset"].as_u64().unwrap() as usize, layout["width"].as_u64().unwrap());
+            offset..offset + width as usize
+        };
+        let layout = &json["structs"]["point"]["layout"];
+        for (member, expected) in [("x", "7u8"), ("y", "1field")] {
+            let expected = Plaintext::<CurrentNetwork>::from_str(expected).unwrap().to_bits_le();
+            assert_eq!(&bits[range(&layout[member])], expected.as_slice());
+        }
+        assert_eq!(range(&layout["y"]).end, bits.len());
+
+        // The width of a nested struct and an array is that of their encoding.
+        let segment = Plaintext::<CurrentNetwork>::from_str(
+            "{ ends: [{ x: 0u8, y: 0field }, { x: 1u8, y: 1field }], label: 3u16 }",
+        )
+        .unwrap();
+        assert_eq!(range(&json["structs"]["segment"]["layout"]["label"]).end, segment.to_bits_le().len());
+
+        // A string makes the width, and the offsets after it, vary.
+        let layout = &json["structs"]["named"]["layout"];
+        assert!(layout["name"]["offset"].is_u64());
+        assert!(layout["name"]["width"].is_null());
+        assert_eq!(layout["after"], json!({ "offset": null, "width": 34 }));
+
+        // Check each offset and width against the encoding of the data of a record.
+        let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(
+            "{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, amount: 5u64.private, \
+             origin: { x: 7u8.public, y: 1field.public }, _nonce: 0group.public }",
+        )
+        .unwrap();
+        let mut data_bits = Vec::new();
+        for (name, entry) in record.data() {
+            name.write_bits_le(&mut data_bits);
+            entry.write_bits_le(&mut data_bits);
+        }
+        let layout = &json["records"]["coin"]["layout"];
+        let amount = Entry::<CurrentNetwork, Plaintext<CurrentNetwork>>::Private(Plaintext::from_str("5u64").unwrap());
+        // The visibility of the entry precedes its value.
+        assert_eq!(&data_bits[range(&layout["amount"])], &amount.to_bits_le()[2..]);
+        assert_eq!(range(&layout["origin"]).end, data_bits.len());
+    }
+}
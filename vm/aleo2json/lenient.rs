@@ -0,0 +1,189 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        convert::parse_next_program,
+        program_source_to_json,
+        raw_operands::mask_comments,
+        Aleo2JsonError,
+        ConvertOptions,
+    },
+    console::{
+        network::{
+            prelude::{many0, tag, Parser, ParserResult, Sanitizer},
+            Network,
+        },
+        program::ProgramID,
+    },
+};
+use snarkvm_synthesizer_program::{Import, Program};
+
+use anyhow::Result;
+use serde_json::Value;
+
+/// The keywords starting each component of a program, after its header.
+const COMPONENT_KEYWORDS: &[&str] = &["mapping", "struct", "record", "closure", "function"];
+
+/// A program parsed from as much of a string as the parser could, i.e. by `--lenient`.
+pub struct PartialProgram<N: Network> {
+    /// The program parsed from the prefix of the string, i.e. its header and its components before the invalid one.
+    pub program: Program<N>,
+    /// The imports of the header, if none of the components is valid, as a program without components holds none.
+    pub imports: Vec<Import<N>>,
+    /// The number of bytes of the string consumed by the parser.
+    pub consumed: usize,
+    /// The error at which the parser stopped, if any.
+    pub error: Option<Aleo2JsonError>,
+}
+
+/// Parses the header of a program, i.e. its imports, and its ID, as `Program::parse` does.
+fn parse_program_header<N: Network>(string: &str) -> ParserResult<'_, (Vec<Import<N>>, ProgramID<N>)> {
+    let (string, imports) = many0(Import::parse)(string)?;
+    let (string, _) = Sanitizer::parse(string)?;
+    let (string, _) = tag("program")(string)?;
+    let (string, _) = Sanitizer::parse_whitespaces(string)?;
+    let (string, id) = ProgramID::parse(string)?;
+    let (string, _) = Sanitizer::parse_whitespaces(string)?;
+    let (string, _) = tag(";")(string)?;
+    Ok((string, (imports, id)))
+}
+
+/// Returns the byte offset of the last component starting in the given source with its comments masked, i.e. at
+/// `function main:`, if any.
+fn last_component_start(masked: &str) -> Option<usize> {
+    let starts = COMPONENT_KEYWORDS.iter().flat_map(|keyword| {
+        masked.match_indices(keyword).map(|(offset, _)| offset).filter(|offset| {
+            let before = masked[..*offset].chars().next_back();
+            let after = masked[offset + keyword.len()..].chars().next();
+            before.map_or(true, char::is_whitespace) && after.is_some_and(char::is_whitespace)
+        })
+    });
+    starts.max()
+}
+
+/// Parses as much of the given string as possible into a program. On an invalid component, the components from the
+/// last one on are dropped until the rest are valid, and if none of them is, the program is parsed from its header
+/// alone. The error is the first one the parser stopped at. Fails if the header itself is invalid.
+pub fn parse_program_lenient<N: Network>(string: &str) -> Result<PartialProgram<N>, Aleo2JsonError> {
+    let error = match parse_next_program::<N>(string, string) {
+        Ok((remain, program)) => {
+            let consumed = string.len() - remain.len();
+            let error = (!remain.is_empty()).then_some(Aleo2JsonError::UnconsumedInput { offset: consumed });
+            return Ok(PartialProgram { program, imports: Vec::new(), consumed, error });
+        }
+        Err(error) => error,
+    };
+
+    // Drop the last component, until the rest are valid.
+    let masked = mask_comments(string);
+    let mut end = string.len();
+    while let Some(start) = last_component_start(&masked[..end]) {
+        end = start;
+        // Parse the prefix on its own, so that the offset of an error is within the string.
+        if let Ok((remain, program)) = parse_next_program::<N>(&string[..end], &string[..end]) {
+            let consumed = end - remain.len();
+            return Ok(PartialProgram { program, imports: Vec::new(), consumed, error: Some(error) });
+        }
+    }
+
+    // None of the components is valid, so parse the header alone.
+    let Ok((remain, (imports, id))) = parse_program_header::<N>(string) else {
+        return Err(error);
+    };
+    match Program::new(id) {
+        Ok(program) => {
+            let consumed = string.len() - remain.len();
+            Ok(PartialProgram { program, imports, consumed, error: Some(error) })
+        }
+        Err(_) => Err(error),
+    }
+}
+
+/// Converts the given partial program, parsed from the given source, into JSON with the given options, as
+/// `program_source_to_json` does with the part of the source consumed by the parser.
+pub fn partial_program_to_json<N: Network>(
+    partial: &PartialProgram<N>,
+    source: &str,
+    options: &ConvertOptions,
+) -> Result<Value> {
+    let mut json = program_source_to_json(&partial.program, &source[..partial.consumed], options)?;
+    if let (false, Some(object)) = (partial.imports.is_empty(), json.as_object_mut()) {
+        let imports = partial.imports.iter().map(|import| (import.program_id().to_key(), import.to_json()));
+        object.insert("imports".to_string(), Value::Object(imports.collect()));
+    }
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::CurrentNetwork;
+
+    use std::str::FromStr;
+
+    const PROGRAM: &str = r"
+import credits.aleo;
+program lenient.aleo;
+
+struct point:
+    x as u8;
+
+function main:
+    input r0 as u8.public;
+    add r0 r0 into r1;
+
+function broken:
+    input r0 as u8.public;
+    frobnicate r0;
+";
+
+    #[test]
+    fn test_parse_program_lenient() {
+        // The program is parsed up to the invalid instruction, where the parser stopped.
+        let partial = parse_program_lenient::<CurrentNetwork>(PROGRAM).unwrap();
+        let functions = partial.program.functions().keys().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(functions, ["main", "broken"]);
+        assert!(partial.program.contains_import(&ProgramID::from_str("credits.aleo").unwrap()));
+        assert_eq!(partial.consumed, PROGRAM.find("frobnicate").unwrap());
+        assert_eq!(partial.error.as_ref().and_then(Aleo2JsonError::offset), Some(partial.consumed));
+        assert_eq!(partial.error.as_ref().map(Aleo2JsonError::kind), Some("unconsumed_input"));
+
+        // A valid program is parsed entirely, without an error.
+        let valid = &PROGRAM[..PROGRAM.find("function broken").unwrap()];
+        let partial = parse_program_lenient::<CurrentNetwork>(valid).unwrap();
+        assert_eq!(partial.consumed, valid.len());
+        assert!(partial.error.is_none());
+
+        // A component failing the whole parse is dropped, along with those after it.
+        let duplicate = format!("{valid}function main:\n    input r0 as u8.public;\n");
+        let partial = parse_program_lenient::<CurrentNetwork>(&duplicate).unwrap();
+        assert_eq!(partial.program.functions().len(), 1);
+        assert_eq!(partial.consumed, valid.len());
+        assert!(partial.error.is_some());
+
+        // A program without a valid component is parsed from its header alone.
+        let source = "import credits.aleo;\nprogram lenient.aleo;\n\nstruct point:\n    x as ;\n";
+        let partial = parse_program_lenient::<CurrentNetwork>(source).unwrap();
+        assert!(partial.program.structs().is_empty());
+        assert_eq!(partial.consumed, source.find("\n\nstruct").unwrap());
+        assert!(partial.error.is_some());
+        let json = partial_program_to_json(&partial, source, &ConvertOptions::new()).unwrap();
+        assert_eq!(json["id"]["name"], "lenient");
+        assert_eq!(json["imports"]["credits.aleo"]["type"], "Import");
+
+        // An invalid header fails.
+        assert!(parse_program_lenient::<CurrentNetwork>("program lenient;\n\nstruct point:\n    x as u8;\n").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::{json, Value};
+
+/// Keeps only the first `limit` functions of the given JSON, in declaration order,
+/// and records whether the functions were truncated along with their total count.
+pub fn limit_functions(json: &mut Value, limit: usize) {
+    let Some(functions) = json.get_mut("functions").and_then(Value::as_object_mut) else {
+        return;
+    };
+    let total = functions.len();
+    if total > limit {
+        *functions = std::mem::take(functions).into_iter().take(limit).collect();
+    }
+    json["truncated"] = json!(total > limit);
+    json["total_functions"] = json!(total);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    const PROGRAM: &str = r"
+program sample.aleo;
+
+function first:
+    input r0 as u8.public;
+
+function second:
+    input r0 as u8.public;
+
+function third:
+    input r0 as u8.public;
+";
+
+    #[test]
+    fn test_limit_functions() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+
+        let mut json = program.to_json();
+        limit_functions(&mut json, 2);
+        assert_eq!(json["functions"].as_object().unwrap().keys().collect::<Vec<_>>(), ["first", "second"]);
+        assert_eq!(json["truncated"], true);
+        assert_eq!(json["total_functions"], 3);
+
+        let mut json = program.to_json();
+        limit_functions(&mut json, 3);
+        assert_eq!(json["functions"].as_object().unwrap().len(), 3);
+        assert_eq!(json["truncated"], false);
+        assert_eq!(json["total_functions"], 3);
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{
+    raw_operands::{instruction_statements, mask_comments},
+    NodeVisitor,
+};
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// A visitor adding the 1-based line of each function and closure instruction in the source under `"line"`,
+/// i.e. `"line": 12`, as a lighter alternative to the source text of its operands.
+/// An instruction that cannot be matched with the source is left unchanged.
+pub struct InstructionLines {
+    /// The line of each instruction of each function and closure, by name.
+    lines: HashMap<String, Vec<usize>>,
+}
+
+impl InstructionLines {
+    /// Initializes a visitor adding the line of each instruction from the given program source.
+    pub fn new(source: &str) -> Self {
+        let masked = mask_comments(source);
+        let lines = instruction_statements(&masked).into_iter().map(|(name, statements)| {
+            let lines = statements.into_iter().map(|range| {
+                // The statement starts after the whitespace before it.
+                let statement = &masked[range.clone()];
+                let start = range.start + statement.len() - statement.trim_start().len();
+                source[..start].matches('\n').count() + 1
+            });
+            (name, lines.collect())
+        });
+        Self { lines: lines.collect() }
+    }
+}
+
+impl NodeVisitor for InstructionLines {
+    fn visit_instruction(&mut self, function: &str, index: usize, json: &mut Value) {
+        if let Some(line) = self.lines.get(function).and_then(|lines| lines.get(index)) {
+            json["line"] = json!(line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, ConvertOptions};
+
+    const SOURCE: &str = r"program lines.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1; // add r9 r9 into r9;
+    output r1 as u64;
+
+function main:
+    input r0 as u64.private;
+    /* add r9 r9 into r9;
+       add r9 r9 into r9; */
+    call twice r0 into r1;
+    add r1
+        r1 into r2; mul r2 r2 into r3;
+";
+
+    #[test]
+    fn test_instruction_lines() {
+        let json = convert_program(SOURCE, &ConvertOptions::new().with_lines(true)).unwrap();
+        let lines = |section: &str, name: &str| -> Vec<Value> {
+            let instructions = json[section][name]["instructions"].as_array().unwrap();
+            instructions.iter().map(|instruction| instruction["line"].clone()).collect()
+        };
+        assert_eq!(lines("closures", "twice"), [json!(5)]);
+        assert_eq!(lines("functions", "main"), [json!(12), json!(13), json!(14)]);
+
+        // Without the option, no instruction has a line.
+        let json = convert_program(SOURCE, &ConvertOptions::new()).unwrap();
+        assert!(json["functions"]["main"]["instructions"][0].get("line").is_none());
+    }
+}
@@ -0,0 +1,518 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{
+    network::{
+        prelude::{Double, One, SizeInBytes, ToBytes},
+        Network,
+    },
+    types::{Field, Group, Scalar},
+};
+
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+/// Returns `true` if the given JSON node is a literal, i.e. `{ "type": "Literal", ... }`.
+pub(crate) fn is_literal(node: &Value) -> bool {
+    node.get("type").and_then(Value::as_str) == Some("Literal")
+}
+
+/// Returns the type and the unsuffixed value of the given literal node, i.e. `("u64", "42")` for `42u64`.
+pub(crate) fn literal_parts(node: &Value) -> Option<(String, String)> {
+    // Retrieve the literal type, i.e. `U64` becomes `u64`.
+    let literal_type = node.get("vtype")?.as_str()?.to_lowercase();
+    // Retrieve the literal as it is written in the program, i.e. `42u64`.
+    let string = node.get("str")?.as_str()?;
+    // Strip the type suffix from the literal.
+    let value = match literal_type.as_str() {
+        "string" => string.trim_matches('"'),
+        _ => string.strip_suffix(literal_type.as_str()).unwrap_or(string),
+    };
+    Some((literal_type, value.to_string()))
+}
+
+/// Replaces each literal node in the given JSON with its value and its type, i.e. `{ "value": "42", "type": "u64" }`.
+pub fn split_literals(json: &mut Value) {
+    if is_literal(json) {
+        if let Some((literal_type, value)) = literal_parts(json) {
+            *json = json!({ "value": value, "type": literal_type });
+        }
+        return;
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(split_literals),
+        Value::Array(array) => array.iter_mut().for_each(split_literals),
+        _ => (),
+    }
+}
+
+/// Returns the given literal value as a native JSON boolean or number, if it is a boolean, or an integer of
+/// at most 32 bits (i.e. `u8` to `u32`, and `i8` to `i32`). Any wider integer (i.e. `u64` or `i128`), field,
+/// group, or scalar is `None`, as it may exceed the 53 bits of precision of a JSON number in JavaScript.
+fn native_value(literal_type: &str, value: &str) -> Option<Value> {
+    match literal_type {
+        "boolean" => value.parse::<bool>().ok().map(Value::Bool),
+        "u8" | "u16" | "u32" | "i8" | "i16" | "i32" => value.parse::<i64>().ok().map(Value::from),
+        _ => None,
+    }
+}
+
+/// Replaces the value of each boolean and 32-bit or narrower integer literal in the given JSON with a native JSON
+/// boolean or number, i.e. `true` or `42`. Wider integers, fields, groups, and scalars are left as strings.
+/// This applies to both literal nodes and split literals, i.e. `{ "value": 42, "type": "u8" }`.
+pub fn native_literals(json: &mut Value) {
+    if is_literal(json) {
+        if let Some((literal_type, value)) = literal_parts(json) {
+            if let Some(value) = native_value(&literal_type, &value) {
+                json["value"] = value;
+            }
+        }
+        return;
+    }
+    if let Some(object) = json.as_object_mut() {
+        // Handle a split literal, i.e. `{ "value": "42", "type": "u8" }`.
+        if object.len() == 2 {
+            let literal_type = object.get("type").and_then(Value::as_str).unwrap_or_default();
+            let value = object.get("value").and_then(Value::as_str).and_then(|value| native_value(literal_type, value));
+            if let Some(value) = value {
+                object.insert("value".to_string(), value);
+                return;
+            }
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(native_literals),
+        Value::Array(array) => array.iter_mut().for_each(native_literals),
+        _ => (),
+    }
+}
+
+/// Returns the hex form of the given decimal field element, i.e. `0x2a` for `42`.
+fn field_to_hex<N: Network>(decimal: &str) -> Option<String> {
+    let field = Field::<N>::from_str(&format!("{decimal}field")).ok()?;
+    // Encode the little-endian bytes of the field element as big-endian hex.
+    let hex = field.to_bytes_le().ok()?.iter().rev().map(|byte| format!("{byte:02x}")).collect::<String>();
+    match hex.trim_start_matches('0') {
+        "" => Some("0x0".to_string()),
+        hex => Some(format!("0x{hex}")),
+    }
+}
+
+/// Returns the decimal and hex forms of the given field or group value, i.e. `{ "dec": "42", "hex": "0x2a" }`.
+/// A group value is represented by its x-coordinate.
+pub(crate) fn dec_and_hex<N: Network>(decimal: &str) -> Option<Value> {
+    Some(json!({ "dec": decimal, "hex": field_to_hex::<N>(decimal)? }))
+}
+
+/// Replaces the value of each field and group literal in the given JSON with its decimal and hex forms.
+/// This applies to both literal nodes and split literals, i.e. `{ "value": { "dec": "42", "hex": "0x2a" }, "type": "field" }`.
+pub fn hex_fields<N: Network>(json: &mut Value) {
+    if is_literal(json) {
+        if let Some((literal_type, value)) = literal_parts(json) {
+            if literal_type == "field" || literal_type == "group" {
+                if let Some(value) = dec_and_hex::<N>(&value) {
+                    json["value"] = value;
+                }
+            }
+        }
+        return;
+    }
+    if let Some(object) = json.as_object_mut() {
+        // Handle a split literal, i.e. `{ "value": "42", "type": "field" }`.
+        if object.len() == 2 && matches!(object.get("type").and_then(Value::as_str), Some("field" | "group")) {
+            if let Some(value) = object.get("value").and_then(Value::as_str).and_then(dec_and_hex::<N>) {
+                object.insert("value".to_string(), value);
+                return;
+            }
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(hex_fields::<N>),
+        Value::Array(array) => array.iter_mut().for_each(hex_fields::<N>),
+        _ => (),
+    }
+}
+
+/// Returns the given decimal field or scalar value as big-endian hex, zero-padded to the size in bytes of its type,
+/// i.e. `0x000000000000000000000000000000000000000000000000000000000000002a` for `42field`.
+fn fixed_width_hex<N: Network>(literal_type: &str, decimal: &str) -> Option<String> {
+    let literal = format!("{decimal}{literal_type}");
+    let (mut bytes, width) = match literal_type {
+        "field" => (Field::<N>::from_str(&literal).ok()?.to_bytes_le().ok()?, Field::<N>::size_in_bytes()),
+        "scalar" => (Scalar::<N>::from_str(&literal).ok()?.to_bytes_le().ok()?, Scalar::<N>::size_in_bytes()),
+        _ => return None,
+    };
+    bytes.resize(width, 0);
+    Some(format!("0x{}", bytes.iter().rev().map(|byte| format!("{byte:02x}")).collect::<String>()))
+}
+
+/// Replaces the value of each field and scalar literal in the given JSON with its zero-padded, fixed-width hex form,
+/// as in `fixed_width_hex`, so that the values align, and compare byte by byte. This applies to both literal nodes
+/// and split literals, i.e. `{ "value": "0x00...2a", "type": "field" }`.
+pub fn fixed_width_fields<N: Network>(json: &mut Value) {
+    if is_literal(json) {
+        if let Some((literal_type, value)) = literal_parts(json) {
+            if let Some(value) = fixed_width_hex::<N>(&literal_type, &value) {
+                json["value"] = Value::String(value);
+            }
+        }
+        return;
+    }
+    if let Some(object) = json.as_object_mut() {
+        // Handle a split literal, i.e. `{ "value": "42", "type": "field" }`.
+        if object.len() == 2 {
+            let literal_type = object.get("type").and_then(Value::as_str).unwrap_or_default();
+            let value = object.get("value").and_then(Value::as_str);
+            if let Some(value) = value.and_then(|value| fixed_width_hex::<N>(literal_type, value)) {
+                object.insert("value".to_string(), Value::String(value));
+                return;
+            }
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(fixed_width_fields::<N>),
+        Value::Array(array) => array.iter_mut().for_each(fixed_width_fields::<N>),
+        _ => (),
+    }
+}
+
+/// Returns the affine coordinates of the given group value, in decimal, i.e. `{ "x": "0", "y": "1" }` for `0group`.
+/// The value of a group literal is its x-coordinate, from which its y-coordinate is recovered.
+fn group_to_affine<N: Network>(value: &str) -> Option<Value> {
+    let group = Group::<N>::from_str(&format!("{value}group")).ok()?;
+    let decimal = |field: Field<N>| field.to_string().strip_suffix("field").map(str::to_string);
+    Some(json!({ "x": decimal(group.to_x_coordinate())?, "y": decimal(group.to_y_coordinate())? }))
+}
+
+/// Replaces the value of each group literal in the given JSON with its affine coordinates, i.e. `{ "x": .., "y": .. }`.
+/// This applies to both literal nodes and split literals, i.e. `{ "value": { "x": "0", "y": "1" }, "type": "group" }`.
+pub fn expand_groups<N: Network>(json: &mut Value) {
+    if is_literal(json) {
+        if let Some((literal_type, value)) = literal_parts(json) {
+            if literal_type == "group" {
+                if let Some(value) = group_to_affine::<N>(&value) {
+                    json["value"] = value;
+                }
+            }
+        }
+        return;
+    }
+    if let Some(object) = json.as_object_mut() {
+        // Handle a split literal, i.e. `{ "value": "0", "type": "group" }`.
+        if object.len() == 2 && object.get("type").and_then(Value::as_str) == Some("group") {
+            if let Some(value) = object.get("value").and_then(Value::as_str).and_then(group_to_affine::<N>) {
+                object.insert("value".to_string(), value);
+                return;
+            }
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(expand_groups::<N>),
+        Value::Array(array) => array.iter_mut().for_each(expand_groups::<N>),
+        _ => (),
+    }
+}
+
+/// Returns the Montgomery form of the given decimal field element, in decimal, i.e. `x * R mod p` for `R = 2^256`,
+/// as it is stored in the limbs of the field element.
+fn field_to_montgomery<N: Network>(decimal: &str) -> Option<String> {
+    let field = Field::<N>::from_str(&format!("{decimal}field")).ok()?;
+    let r = (0..256).fold(Field::<N>::one(), |r, _| r.double());
+    (field * r).to_string().strip_suffix("field").map(str::to_string)
+}
+
+/// Replaces the value of each field and group literal in the given JSON with its Montgomery form, in decimal.
+/// A group value is represented by its x-coordinate. This applies to both literal nodes and split literals.
+pub fn montgomery_fields<N: Network>(json: &mut Value) {
+    if is_literal(json) {
+        if let Some((literal_type, value)) = literal_parts(json) {
+            if literal_type == "field" || literal_type == "group" {
+                if let Some(montgomery) = field_to_montgomery::<N>(&value) {
+                    json["value"][literal_type] = Value::String(montgomery);
+                }
+            }
+        }
+        return;
+    }
+    if let Some(object) = json.as_object_mut() {
+        // Handle a split literal, i.e. `{ "value": "42", "type": "field" }`.
+        if object.len() == 2 && matches!(object.get("type").and_then(Value::as_str), Some("field" | "group")) {
+            if let Some(value) = object.get("value").and_then(Value::as_str).and_then(field_to_montgomery::<N>) {
+                object.insert("value".to_string(), Value::String(value));
+                return;
+            }
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(montgomery_fields::<N>),
+        Value::Array(array) => array.iter_mut().for_each(montgomery_fields::<N>),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    const ADDRESS: &str = "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9";
+
+    #[test]
+    fn test_split_literals() {
+        let program = parse_program::<CurrentNetwork>(&format!(
+            r"
+program literals.aleo;
+
+function main:
+    input r0 as u64.private;
+    add r0 42u64 into r1;
+    mul 2group 3scalar into r2;
+    add 1field 2field into r3;
+    is.eq {ADDRESS} self.caller into r4;
+    assert.eq r4 true;
+"
+        ))
+        .unwrap();
+
+        let mut json = program.to_json();
+        split_literals(&mut json);
+
+        let operand = |index: usize, operand: usize| {
+            json["functions"]["main"]["instructions"][index]["value"]["operands"][operand]["value"].clone()
+        };
+        assert_eq!(operand(0, 1), json!({ "value": "42", "type": "u64" }));
+        assert_eq!(operand(1, 0), json!({ "value": "2", "type": "group" }));
+        assert_eq!(operand(1, 1), json!({ "value": "3", "type": "scalar" }));
+        assert_eq!(operand(2, 0), json!({ "value": "1", "type": "field" }));
+        assert_eq!(operand(3, 0), json!({ "value": ADDRESS, "type": "address" }));
+        assert_eq!(operand(4, 1), json!({ "value": "true", "type": "boolean" }));
+        // Registers are left untouched.
+        assert_eq!(operand(0, 0)["type"], "Register");
+    }
+
+    #[test]
+    fn test_hex_fields() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program hex.aleo;
+
+function main:
+    input r0 as field.private;
+    add r0 42field into r1;
+    mul 2group 3scalar into r2;
+    add 0u64 255u64 into r3;
+",
+        )
+        .unwrap();
+
+        let operand = |json: &Value, index: usize, operand: usize| {
+            json["functions"]["main"]["instructions"][index]["value"]["operands"][operand]["value"].clone()
+        };
+
+        // Check literal nodes.
+        let mut json = program.to_json();
+        hex_fields::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 0, 1)["value"], json!({ "dec": "42", "hex": "0x2a" }));
+        assert_eq!(operand(&json, 1, 0)["value"]["dec"], "2");
+        assert!(operand(&json, 1, 0)["value"]["hex"].as_str().unwrap().starts_with("0x"));
+        // Scalars and integers are left untouched.
+        assert!(operand(&json, 1, 1)["value"].get("dec").is_none());
+        assert!(operand(&json, 2, 1)["value"].get("dec").is_none());
+
+        // Check split literals.
+        let mut json = program.to_json();
+        split_literals(&mut json);
+        hex_fields::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 0, 1), json!({ "value": { "dec": "42", "hex": "0x2a" }, "type": "field" }));
+        assert_eq!(operand(&json, 2, 0), json!({ "value": "0", "type": "u64" }));
+    }
+
+    #[test]
+    fn test_fixed_width_fields() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program fixed.aleo;
+
+function main:
+    input r0 as field.private;
+    add r0 42field into r1;
+    mul 2group 3scalar into r2;
+    add 0u64 255u64 into r3;
+",
+        )
+        .unwrap();
+
+        let operand = |json: &Value, index: usize, operand: usize| {
+            json["functions"]["main"]["instructions"][index]["value"]["operands"][operand]["value"].clone()
+        };
+        let padded = |hex: &str| format!("0x{hex:0>64}");
+
+        // Check literal nodes.
+        let mut json = program.to_json();
+        fixed_width_fields::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 0, 1)["value"], padded("2a"));
+        assert_eq!(operand(&json, 1, 1)["value"], padded("3"));
+        // Groups and integers are left untouched.
+        assert_eq!(operand(&json, 1, 0), operand(&program.to_json(), 1, 0));
+        assert_eq!(operand(&json, 2, 1), operand(&program.to_json(), 2, 1));
+
+        // Check split literals.
+        let mut json = program.to_json();
+        split_literals(&mut json);
+        fixed_width_fields::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 0, 1), json!({ "value": padded("2a"), "type": "field" }));
+        assert_eq!(operand(&json, 1, 1), json!({ "value": padded("3"), "type": "scalar" }));
+        assert_eq!(operand(&json, 2, 0), json!({ "value": "0", "type": "u64" }));
+    }
+
+    #[test]
+    fn test_native_literals() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program native.aleo;
+
+function main:
+    input r0 as u8.private;
+    add r0 42u8 into r1;
+    sub -7i32 1i32 into r2;
+    add 9007199254740993u64 1u64 into r3;
+    add 1field 2field into r4;
+    assert.eq true true;
+",
+        )
+        .unwrap();
+
+        let operand = |json: &Value, index: usize, operand: usize| {
+            json["functions"]["main"]["instructions"][index]["value"]["operands"][operand]["value"].clone()
+        };
+
+        // Check literal nodes.
+        let mut json = program.to_json();
+        native_literals(&mut json);
+        assert_eq!(operand(&json, 0, 1)["value"], json!(42));
+        assert_eq!(operand(&json, 1, 0)["value"], json!(-7));
+        assert_eq!(operand(&json, 4, 0)["value"], json!(true));
+        // Wider integers and fields are left untouched.
+        assert_eq!(operand(&json, 2, 0)["value"]["integer"], "9007199254740993");
+        assert_eq!(operand(&json, 3, 0)["value"]["type"], "Field");
+
+        // Check split literals.
+        let mut json = program.to_json();
+        split_literals(&mut json);
+        native_literals(&mut json);
+        assert_eq!(operand(&json, 0, 1), json!({ "value": 42, "type": "u8" }));
+        assert_eq!(operand(&json, 2, 0), json!({ "value": "9007199254740993", "type": "u64" }));
+        assert_eq!(operand(&json, 3, 0), json!({ "value": "1", "type": "field" }));
+        assert_eq!(operand(&json, 4, 1), json!({ "value": true, "type": "boolean" }));
+    }
+
+    #[test]
+    fn test_expand_groups() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program groups.aleo;
+
+function main:
+    input r0 as group.private;
+    add r0 0group into r1;
+    add r0 2group into r2;
+    add 1field 2field into r3;
+",
+        )
+        .unwrap();
+
+        let operand = |json: &Value, index: usize, operand: usize| {
+            json["functions"]["main"]["instructions"][index]["value"]["operands"][operand]["value"].clone()
+        };
+
+        // Check literal nodes.
+        let mut json = program.to_json();
+        expand_groups::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 0, 1)["value"], json!({ "x": "0", "y": "1" }));
+        let point = operand(&json, 1, 1)["value"].clone();
+        assert_eq!(point["x"], "2");
+        let (x, y) = (point["x"].as_str().unwrap(), point["y"].as_str().unwrap());
+        let group = Group::<CurrentNetwork>::from_xy_coordinates(
+            Field::from_str(&format!("{x}field")).unwrap(),
+            Field::from_str(&format!("{y}field")).unwrap(),
+        );
+        assert_eq!(group.to_string(), "2group");
+        // Fields are left untouched.
+        assert_eq!(operand(&json, 2, 0), operand(&program.to_json(), 2, 0));
+
+        // Check split literals.
+        let mut json = program.to_json();
+        split_literals(&mut json);
+        expand_groups::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 0, 1), json!({ "value": { "x": "0", "y": "1" }, "type": "group" }));
+        assert_eq!(operand(&json, 2, 0), json!({ "value": "1", "type": "field" }));
+    }
+
+    #[test]
+    fn test_montgomery_fields() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program montgomery.aleo;
+
+function main:
+    input r0 as field.private;
+    add r0 0field into r1;
+    add r0 1field into r2;
+    add 0u64 1u64 into r3;
+",
+        )
+        .unwrap();
+
+        let operand = |json: &Value, index: usize| {
+            json["functions"]["main"]["instructions"][index]["value"]["operands"][1]["value"].clone()
+        };
+        let one = field_to_montgomery::<CurrentNetwork>("1").unwrap();
+        assert_ne!(one, "1");
+
+        // Check literal nodes.
+        let mut json = program.to_json();
+        montgomery_fields::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 0)["value"]["field"], "0");
+        assert_eq!(operand(&json, 1)["value"]["field"], one);
+        // Integers are left untouched.
+        assert_eq!(operand(&json, 2), operand(&program.to_json(), 2));
+
+        // Check split literals.
+        let mut json = program.to_json();
+        split_literals(&mut json);
+        montgomery_fields::<CurrentNetwork>(&mut json);
+        assert_eq!(operand(&json, 1), json!({ "value": one, "type": "field" }));
+        assert_eq!(operand(&json, 2), json!({ "value": "1", "type": "u64" }));
+    }
+
+    #[test]
+    fn test_field_to_montgomery() {
+        // The Montgomery form of `x` is `x * R`, so it is linear.
+        let montgomery = |decimal: &str| {
+            let montgomery = field_to_montgomery::<CurrentNetwork>(decimal).unwrap();
+            Field::<CurrentNetwork>::from_str(&format!("{montgomery}field")).unwrap()
+        };
+        assert_eq!(montgomery("1").double(), montgomery("2"));
+        assert_eq!(field_to_montgomery::<CurrentNetwork>("0").unwrap(), "0");
+        assert!(field_to_montgomery::<CurrentNetwork>("not a field").is_none());
+    }
+
+    #[test]
+    fn test_field_to_hex() {
+        assert_eq!(field_to_hex::<CurrentNetwork>("0").unwrap(), "0x0");
+        assert_eq!(field_to_hex::<CurrentNetwork>("255").unwrap(), "0xff");
+        assert_eq!(field_to_hex::<CurrentNetwork>("4096").unwrap(), "0x1000");
+        assert!(field_to_hex::<CurrentNetwork>("not a field").is_none());
+    }
+}
@@ -12,43 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkvm::{console::network::Testnet3, prelude::Parser, synthesizer::Program};
-type CurrentNetwork = Testnet3;
+use snarkvm::aleo2json::{Aleo2Json, ExitStatus};
 
-fn parse(rawp: &str) -> Program<CurrentNetwork> {
-    match Program::<CurrentNetwork>::parse(rawp) {
-        Ok((remain, program)) => {
-            if remain.is_empty() {
-                // println!("Good");
-                program
-            } else {
-                panic!("Parser did not consume all of the string: '{remain}'");
-            }
-        }
-        Err(e) => {
-            panic!("Parser error: {}", e);
-        }
+use clap::{CommandFactory, FromArgMatches};
+use std::process::ExitCode;
+
+/// Prints the given argument error, or the help or the version, returning its exit code.
+fn exit_with(error: clap::Error) -> ExitCode {
+    let _ = error.print();
+    match error.use_stderr() {
+        true => ExitCode::from(ExitStatus::Usage.code()),
+        false => ExitCode::SUCCESS,
     }
 }
 
-fn main() {
-    let path = std::env::args().nth(1).expect("No path provided");
-    let path = std::path::Path::new(&path);
-
-    // read
-    // let file_name = path.file_name().unwrap().to_str().unwrap();
-    // let file = std::fs::File::open(&path).expect("Could not open file");
-    let rawp = std::fs::read_to_string(path).expect("Read error");
-
-    // println!("Read:\n{rawp}");
-
-    let program = parse(&rawp);
-    // println!("Program:\n{program}");
-
-    // let j = serde_json::to_string(&program).expect("Json error");
-    // println!("Json:\n{j}");
-
-    let jj = program.to_json();
-    println!("{jj}\n");
-    // println!("Json:\n{jj}");
+fn main() -> ExitCode {
+    // Parse the given arguments, keeping their matches to tell which were given.
+    let matches = match Aleo2Json::command().try_get_matches() {
+        Ok(matches) => matches,
+        Err(error) => return exit_with(error),
+    };
+    let mut cli = match Aleo2Json::from_arg_matches(&matches) {
+        Ok(cli) => cli,
+        Err(error) => return exit_with(error),
+    };
+    // Apply the config file to the flags that were not given, and convert the program.
+    match cli.apply_config(&matches).and_then(|()| cli.run()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(ExitStatus::of(&error).code())
+        }
+    }
 }
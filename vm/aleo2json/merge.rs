@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::{Map, Value};
+
+/// Returns the instruction of the given UID in the given program JSON, i.e. `mint#0`, if any.
+fn instruction_mut<'a>(json: &'a mut Value, uid: &str) -> Option<&'a mut Value> {
+    let (function, _) = uid.rsplit_once('#')?;
+    for section in ["closures", "functions"] {
+        let instructions = json.get(section).and_then(|functions| functions.get(function));
+        let Some(instructions) = instructions.and_then(|function| function["instructions"].as_array()) else {
+            continue;
+        };
+        if let Some(index) = instructions.iter().position(|instruction| instruction["uid"] == uid) {
+            return json[section][function]["instructions"].get_mut(index);
+        }
+    }
+    None
+}
+
+/// Attaches each of the given annotations to its node in the given program JSON under `"annotations"`, i.e.
+/// `"annotations": { "note": "checked" }`. Each annotation is keyed by the UID of an instruction, i.e. `mint#0`, or
+/// by the JSON pointer of a node (RFC 6901), i.e. `/functions/mint`, as given by `--with-pointers`.
+/// Returns the keys matching no object in the JSON, in order.
+pub fn merge_annotations(json: &mut Value, annotations: &Map<String, Value>) -> Vec<String> {
+    let mut unmatched = Vec::new();
+    for (key, annotation) in annotations {
+        let node = match key.starts_with('/') {
+            true => json.pointer_mut(key),
+            false => instruction_mut(json, key),
+        };
+        match node.and_then(Value::as_object_mut) {
+            Some(node) => {
+                node.insert("annotations".to_string(), annotation.clone());
+            }
+            None => unmatched.push(key.clone()),
+        }
+    }
+    unmatched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, ConvertOptions};
+
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_annotations() {
+        let source = r"
+program annotated.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function main:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    mul r1 r1 into r2;
+    output r2 as u64.public;
+";
+        let mut json = convert_program(source, &ConvertOptions::new()).unwrap();
+        let annotations = json!({
+            "main#1": { "note": "may overflow" },
+            "twice#0": "checked",
+            "/functions/main": { "owner": "alice" },
+            "main#7": "stale",
+            "/functions/missing": "stale",
+            "/id/name": "not an object",
+        });
+        let unmatched = merge_annotations(&mut json, annotations.as_object().unwrap());
+        assert_eq!(unmatched, ["main#7", "/functions/missing", "/id/name"]);
+
+        assert_eq!(json["functions"]["main"]["instructions"][1]["annotations"], json!({ "note": "may overflow" }));
+        assert!(json["functions"]["main"]["instructions"][0].get("annotations").is_none());
+        assert_eq!(json["closures"]["twice"]["instructions"][0]["annotations"], "checked");
+        assert_eq!(json["functions"]["main"]["annotations"], json!({ "owner": "alice" }));
+    }
+}
@@ -12,5 +12,181 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub mod aleo2json;
-pub use aleo2json::*;
\ No newline at end of file
+//! Converts Aleo programs, executions, and transitions into JSON.
+//!
+//! This module is enabled by the `aleo2json` feature, which only depends on the program and block types,
+//! and not on the full `synthesizer` feature (i.e. the process and the VM). To build it on its own, run
+//! `cargo build --no-default-features --features aleo2json --bin aleo2json`.
+//! Every conversion is available in this build, as none of them executes or type-checks a program.
+//!
+//! Every number in the output (i.e. a count, an offset, or a size) is written in plain ASCII digits, without any
+//! thousands separator, whatever the locale of the host, as neither the formatting of Rust nor `serde_json` depends
+//! on it. The output of a program is the same on every machine.
+
+mod abi;
+pub use abi::*;
+
+mod accessors;
+pub use accessors::*;
+
+mod assertions;
+pub use assertions::*;
+
+mod batch;
+pub use batch::*;
+
+mod canonical;
+pub use canonical::*;
+
+mod cast_fields;
+pub use cast_fields::*;
+
+mod cfg;
+pub use cfg::*;
+
+mod cli;
+pub use cli::*;
+
+mod compare;
+pub use compare::*;
+
+mod config;
+pub use config::*;
+
+mod constants;
+pub use constants::*;
+
+mod control_flow;
+pub use control_flow::*;
+
+mod convert;
+pub use convert::*;
+
+mod cost;
+pub use cost::*;
+
+pub mod dataflow;
+pub use dataflow::*;
+
+mod depth;
+pub use depth::*;
+
+mod dependencies;
+pub use dependencies::*;
+
+mod desugar;
+pub use desugar::*;
+
+pub mod diagnostics;
+pub use diagnostics::{error_to_json, render_diagnostic, ColorChoice, ErrorFormat};
+
+mod effects;
+pub use effects::*;
+
+mod errors;
+pub use errors::*;
+
+mod execution;
+pub use execution::*;
+
+mod exit;
+pub use exit::*;
+
+mod futures;
+pub use futures::*;
+
+mod hashes;
+pub use hashes::*;
+
+mod imports;
+pub use imports::*;
+
+mod layout;
+pub use layout::*;
+
+mod lenient;
+pub use lenient::*;
+
+mod limit;
+pub use limit::*;
+
+mod lines;
+pub use lines::*;
+
+mod merge;
+pub use merge::*;
+
+mod expand;
+pub use expand::*;
+
+mod flatten;
+pub use flatten::*;
+
+mod format;
+pub use format::*;
+
+mod operands;
+pub use operands::*;
+
+mod options;
+pub use options::*;
+
+mod order;
+pub use order::*;
+
+pub mod opcodes;
+pub use opcodes::{add_arity, add_crypto_variants, explain_opcode, tag_opcodes, OpcodeInfo};
+
+pub mod literal;
+pub use literal::{expand_groups, fixed_width_fields, hex_fields, montgomery_fields, native_literals, split_literals};
+
+mod pointers;
+pub use pointers::*;
+
+mod profile;
+pub use profile::*;
+
+mod random;
+pub use random::*;
+
+mod raw_operands;
+pub use raw_operands::*;
+
+mod record_types;
+pub use record_types::*;
+
+mod redact;
+pub use redact::*;
+
+mod register_types;
+pub use register_types::*;
+
+mod sections;
+pub use sections::*;
+
+mod serve;
+pub use serve::*;
+
+mod stats;
+pub use stats::*;
+
+mod template;
+pub use template::*;
+
+mod typescript;
+pub use typescript::*;
+
+mod validate;
+pub use validate::*;
+
+mod value;
+pub use value::*;
+
+mod visitor;
+pub use visitor::*;
+
+mod watch;
+pub use watch::*;
+
+#[cfg(test)]
+pub(crate) type CurrentNetwork = crate::console::network::Testnet3;
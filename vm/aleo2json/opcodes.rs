@@ -0,0 +1,357 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::Aleo2JsonError;
+
+use serde_json::{json, Value};
+use std::fmt::{self, Display, Formatter};
+
+/// The description of an instruction or command opcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OpcodeInfo {
+    /// The opcode, i.e. `add`.
+    pub opcode: &'static str,
+    /// The operand signature, i.e. `add <first> <second> into <destination>`.
+    pub signature: &'static str,
+    /// A short description of the opcode.
+    pub description: &'static str,
+}
+
+impl OpcodeInfo {
+    /// Initializes a new opcode description.
+    const fn new(opcode: &'static str, signature: &'static str, description: &'static str) -> Self {
+        Self { opcode, signature, description }
+    }
+
+    /// Returns the expected number of operands, as a minimum and, unless variadic, a maximum, i.e. `(2, Some(2))`.
+    /// The operands are the placeholders before `into`, except for the function of `call` and `async`.
+    pub fn arity(&self) -> (usize, Option<usize>) {
+        let operands = self.signature.split(" into ").next().unwrap_or_default();
+        let (mut min, mut variadic) = (0, false);
+        for placeholder in operands.split_whitespace().skip(1) {
+            match placeholder {
+                "<function>" => (),
+                _ if placeholder.ends_with('*') => variadic = true,
+                _ if placeholder.ends_with('+') => (min, variadic) = (min + 1, true),
+                _ => min += 1,
+            }
+        }
+        (min, (!variadic).then_some(min))
+    }
+}
+
+impl Display for OpcodeInfo {
+    /// Prints the opcode description, i.e. `add` followed by its signature and description.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}\n\n    {}\n\n{}", self.opcode, self.signature, self.description)
+    }
+}
+
+/// The descriptions of every instruction and finalize command opcode.
+/// Keep this table in sync with the opcodes emitted in the JSON.
+pub const OPCODES: &[OpcodeInfo] = &[
+    OpcodeInfo::new("abs", "abs <first> into <destination>", "Computes the absolute value of `first`, checking for overflow/underflow, and storing the outcome in `destination`."),
+    OpcodeInfo::new("abs.w", "abs.w <first> into <destination>", "Computes the absolute value of `first`, wrapping around at the boundary of the type, and storing the outcome in `destination`."),
+    OpcodeInfo::new("add", "add <first> <second> into <destination>", "Adds `first` with `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("add.w", "add.w <first> <second> into <destination>", "Adds `first` with `second`, wrapping around at the boundary of the type, and storing the outcome in `destination`."),
+    OpcodeInfo::new("and", "and <first> <second> into <destination>", "Performs a bitwise `and` on `first` and `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("div", "div <first> <second> into <destination>", "Divides `first` by `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("div.w", "div.w <first> <second> into <destination>", "Divides `first` by `second`, wrapping around at the boundary of the type, storing the outcome in `destination`."),
+    OpcodeInfo::new("double", "double <first> into <destination>", "Doubles `first`, storing the outcome in `destination`."),
+    OpcodeInfo::new("gt", "gt <first> <second> into <destination>", "Computes whether `first` is greater than `second` as a boolean, storing the outcome in `destination`."),
+    OpcodeInfo::new("gte", "gte <first> <second> into <destination>", "Computes whether `first` is greater than or equal to `second` as a boolean, storing the outcome in `destination`."),
+    OpcodeInfo::new("inv", "inv <first> into <destination>", "Computes the multiplicative inverse of `first`, storing the outcome in `destination`."),
+    OpcodeInfo::new("lt", "lt <first> <second> into <destination>", "Computes whether `first` is less than `second` as a boolean, storing the outcome in `destination`."),
+    OpcodeInfo::new("lte", "lte <first> <second> into <destination>", "Computes whether `first` is less than or equal to `second` as a boolean, storing the outcome in `destination`."),
+    OpcodeInfo::new("mod", "mod <first> <second> into <destination>", "Computes the result of `first` mod `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("mul", "mul <first> <second> into <destination>", "Multiplies `first` and `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("mul.w", "mul.w <first> <second> into <destination>", "Multiplies `first` and `second`, wrapping around at the boundary of the type, storing the outcome in `destination`."),
+    OpcodeInfo::new("nand", "nand <first> <second> into <destination>", "Returns `false` if `first` and `second` are `true`, storing the outcome in `destination`."),
+    OpcodeInfo::new("neg", "neg <first> into <destination>", "Negates `first`, storing the outcome in `destination`."),
+    OpcodeInfo::new("nor", "nor <first> <second> into <destination>", "Returns `true` if neither `first` nor `second` is `true`, storing the outcome in `destination`."),
+    OpcodeInfo::new("not", "not <first> into <destination>", "Flips each bit in the representation of `first`, storing the outcome in `destination`."),
+    OpcodeInfo::new("or", "or <first> <second> into <destination>", "Performs a bitwise `or` on `first` and `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("pow", "pow <first> <second> into <destination>", "Raises `first` to the power of `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("pow.w", "pow.w <first> <second> into <destination>", "Raises `first` to the power of `second`, wrapping around at the boundary of the type, storing the outcome in `destination`."),
+    OpcodeInfo::new("rem", "rem <first> <second> into <destination>", "Divides `first` by `second`, storing the remainder in `destination`."),
+    OpcodeInfo::new("rem.w", "rem.w <first> <second> into <destination>", "Divides `first` by `second`, wrapping around at the boundary of the type, storing the remainder in `destination`."),
+    OpcodeInfo::new("shl", "shl <first> <second> into <destination>", "Shifts `first` left by `second` bits, storing the outcome in `destination`."),
+    OpcodeInfo::new("shl.w", "shl.w <first> <second> into <destination>", "Shifts `first` left by `second` bits, continuing past the boundary of the type, storing the outcome in `destination`."),
+    OpcodeInfo::new("shr", "shr <first> <second> into <destination>", "Shifts `first` right by `second` bits, storing the outcome in `destination`."),
+    OpcodeInfo::new("shr.w", "shr.w <first> <second> into <destination>", "Shifts `first` right by `second` bits, continuing past the boundary of the type, storing the outcome in `destination`."),
+    OpcodeInfo::new("square", "square <first> into <destination>", "Squares `first`, storing the outcome in `destination`."),
+    OpcodeInfo::new("sqrt", "sqrt <first> into <destination>", "Computes the square root of `first`, storing the outcome in `destination`."),
+    OpcodeInfo::new("sub", "sub <first> <second> into <destination>", "Computes `first - second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("sub.w", "sub.w <first> <second> into <destination>", "Computes `first - second`, wrapping around at the boundary of the type, and storing the outcome in `destination`."),
+    OpcodeInfo::new("xor", "xor <first> <second> into <destination>", "Performs a bitwise `xor` on `first` and `second`, storing the outcome in `destination`."),
+    OpcodeInfo::new("ternary", "ternary <condition> <first> <second> into <destination>", "Selects `first`, if `condition` is true, otherwise selects `second`, storing the result in `destination`."),
+    OpcodeInfo::new("assert.eq", "assert.eq <first> <second>", "Asserts `first` and `second` are equal to each other."),
+    OpcodeInfo::new("assert.neq", "assert.neq <first> <second>", "Asserts `first` and `second` are **not** equal to each other."),
+    OpcodeInfo::new("is.eq", "is.eq <first> <second> into <destination>", "Computes whether `first` equals `second` as a boolean, storing the outcome in `destination`."),
+    OpcodeInfo::new("is.neq", "is.neq <first> <second> into <destination>", "Computes whether `first` does **not** equal `second` as a boolean, storing the outcome in `destination`."),
+    OpcodeInfo::new("call", "call <function> <operands>* into <destinations>*", "Calls the given function or closure on the operands, storing its outputs in the destinations."),
+    OpcodeInfo::new("async", "async <function> <operands>* into <destination>", "Invokes the finalize logic of the given function on the operands, storing the future in `destination`."),
+    OpcodeInfo::new("cast", "cast <operands>+ into <destination> as <type>", "Casts the operands into the declared type, i.e. a literal, struct, or record, storing the outcome in `destination`."),
+    OpcodeInfo::new("cast.lossy", "cast.lossy <first> into <destination> as <type>", "Casts `first` into the declared literal type, truncating if necessary, and storing the outcome in `destination`."),
+    OpcodeInfo::new("commit.bhp256", "commit.bhp256 <value> <randomizer> into <destination> as <type>", "Commits to `value` with the scalar `randomizer` using the BHP256 collision-resistant function, which processes inputs in 256-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("commit.bhp512", "commit.bhp512 <value> <randomizer> into <destination> as <type>", "Commits to `value` with the scalar `randomizer` using the BHP512 collision-resistant function, which processes inputs in 512-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("commit.bhp768", "commit.bhp768 <value> <randomizer> into <destination> as <type>", "Commits to `value` with the scalar `randomizer` using the BHP768 collision-resistant function, which processes inputs in 768-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("commit.bhp1024", "commit.bhp1024 <value> <randomizer> into <destination> as <type>", "Commits to `value` with the scalar `randomizer` using the BHP1024 collision-resistant function, which processes inputs in 1024-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("commit.ped64", "commit.ped64 <value> <randomizer> into <destination> as <type>", "Commits to `value` with the scalar `randomizer` using the Pedersen64 collision-resistant function, which processes inputs in 64-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("commit.ped128", "commit.ped128 <value> <randomizer> into <destination> as <type>", "Commits to `value` with the scalar `randomizer` using the Pedersen128 collision-resistant function, which processes inputs in 128-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.bhp256", "hash.bhp256 <value> into <destination> as <type>", "Hashes `value` using the BHP256 collision-resistant hash function, which processes inputs in 256-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.bhp512", "hash.bhp512 <value> into <destination> as <type>", "Hashes `value` using the BHP512 collision-resistant hash function, which processes inputs in 512-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.bhp768", "hash.bhp768 <value> into <destination> as <type>", "Hashes `value` using the BHP768 collision-resistant hash function, which processes inputs in 768-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.bhp1024", "hash.bhp1024 <value> into <destination> as <type>", "Hashes `value` using the BHP1024 collision-resistant hash function, which processes inputs in 1024-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.keccak256", "hash.keccak256 <value> into <destination> as <type>", "Hashes `value` using the Keccak256 hash function, which outputs a 256-bit digest, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.keccak384", "hash.keccak384 <value> into <destination> as <type>", "Hashes `value` using the Keccak384 hash function, which outputs a 384-bit digest, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.keccak512", "hash.keccak512 <value> into <destination> as <type>", "Hashes `value` using the Keccak512 hash function, which outputs a 512-bit digest, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.ped64", "hash.ped64 <value> into <destination> as <type>", "Hashes `value` using the Pedersen64 collision-resistant hash function, which processes inputs in 64-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.ped128", "hash.ped128 <value> into <destination> as <type>", "Hashes `value` using the Pedersen128 collision-resistant hash function, which processes inputs in 128-bit chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.psd2", "hash.psd2 <value> into <destination> as <type>", "Hashes `value` using the Poseidon2 hash function, which processes inputs in 2-field chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.psd4", "hash.psd4 <value> into <destination> as <type>", "Hashes `value` using the Poseidon4 hash function, which processes inputs in 4-field chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.psd8", "hash.psd8 <value> into <destination> as <type>", "Hashes `value` using the Poseidon8 hash function, which processes inputs in 8-field chunks, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.sha3_256", "hash.sha3_256 <value> into <destination> as <type>", "Hashes `value` using the SHA3-256 hash function, which outputs a 256-bit digest, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.sha3_384", "hash.sha3_384 <value> into <destination> as <type>", "Hashes `value` using the SHA3-384 hash function, which outputs a 384-bit digest, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash.sha3_512", "hash.sha3_512 <value> into <destination> as <type>", "Hashes `value` using the SHA3-512 hash function, which outputs a 512-bit digest, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash_many.psd2", "hash_many.psd2 <value> <num_outputs> into <destination> as <type>", "Hashes `value` into `num_outputs` field elements using the Poseidon2 hash function, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash_many.psd4", "hash_many.psd4 <value> <num_outputs> into <destination> as <type>", "Hashes `value` into `num_outputs` field elements using the Poseidon4 hash function, storing the outcome in `destination`."),
+    OpcodeInfo::new("hash_many.psd8", "hash_many.psd8 <value> <num_outputs> into <destination> as <type>", "Hashes `value` into `num_outputs` field elements using the Poseidon8 hash function, storing the outcome in `destination`."),
+    OpcodeInfo::new("sign.verify", "sign.verify <signature> <address> <message> into <destination>", "Verifies `signature` by `address` on `message`, storing the outcome as a boolean in `destination`."),
+    OpcodeInfo::new("await", "await <future>", "Awaits the given future, running its finalize logic."),
+    OpcodeInfo::new("branch.eq", "branch.eq <first> <second> to <position>", "Jumps to `position` if `first` equals `second`."),
+    OpcodeInfo::new("branch.neq", "branch.neq <first> <second> to <position>", "Jumps to `position` if `first` does **not** equal `second`."),
+    OpcodeInfo::new("contains", "contains <mapping>[<key>] into <destination>", "Checks whether `key` is in `mapping`, storing the outcome as a boolean in `destination`."),
+    OpcodeInfo::new("get", "get <mapping>[<key>] into <destination>", "Reads the value of `key` in `mapping`, storing it in `destination`. Fails if `key` is not in `mapping`."),
+    OpcodeInfo::new("get.or_use", "get.or_use <mapping>[<key>] <default> into <destination>", "Reads the value of `key` in `mapping`, or `default` if it is absent, storing it in `destination`."),
+    OpcodeInfo::new("position", "position <name>", "Marks a position that branch commands can jump to."),
+    OpcodeInfo::new("rand.chacha", "rand.chacha <operands>* into <destination> as <type>", "Samples a random value of the declared type, seeded by the operands, storing it in `destination`."),
+    OpcodeInfo::new("remove", "remove <mapping>[<key>]", "Removes `key` from `mapping`."),
+    OpcodeInfo::new("set", "set <value> into <mapping>[<key>]", "Writes `value` to `key` in `mapping`."),
+];
+
+/// The built-in references to the execution context that an operand can be, and a short description of each.
+/// Each is emitted as an operand of kind `builtin`, named as it is printed, i.e. `"name": "self.caller"`, as it is an
+/// implicit input of the function. Keep this table in sync with the built-in operands of the parser.
+pub const BUILTINS: &[(&str, &str)] = &[
+    ("self.signer", "The address of the signer of the transaction."),
+    ("self.caller", "The address of the caller of the function, i.e. the calling program, or else the signer."),
+    ("block.height", "The height of the block in which the finalize logic runs."),
+];
+
+/// Returns the description of the given opcode, if it exists.
+pub fn explain_opcode(opcode: &str) -> Option<&'static OpcodeInfo> {
+    OPCODES.iter().find(|info| info.opcode == opcode)
+}
+
+/// Returns the opcode of the given instruction or command, i.e. `add` for `add r0 r1 into r2;`.
+pub(crate) fn opcode_of(statement: &str) -> &str {
+    statement.split_whitespace().next().unwrap_or_default().trim_end_matches(';')
+}
+
+/// Adds the number of operands of each instruction in the given JSON under `"arity"`, i.e. `"arity": 2`.
+pub fn add_arity(json: &mut Value) {
+    if json.get("type").and_then(Value::as_str) == Some("Instruction") {
+        if let Some(arity) = json["value"]["operands"].as_array().map(Vec::len) {
+            json["arity"] = json!(arity);
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(add_arity),
+        Value::Array(array) => array.iter_mut().for_each(add_arity),
+        _ => (),
+    }
+}
+
+/// Adds the operation and the algorithm of each `hash`, `hash_many`, and `commit` instruction in the given JSON under
+/// `"crypto"`, i.e. `"crypto": { "op": "hash", "algorithm": "psd8", "output_type": "field" }` for
+/// `hash.psd8 r0 into r1 as field`. The output type is the one the instruction is cast to, if any.
+pub fn add_crypto_variants(json: &mut Value) {
+    if json.get("type").and_then(Value::as_str) == Some("Instruction") {
+        if let Some(instruction) = json.get("str").and_then(Value::as_str) {
+            let opcode = opcode_of(instruction);
+            if let Some((op @ ("hash" | "hash_many" | "commit"), algorithm)) = opcode.split_once('.') {
+                let output_type = instruction.rsplit_once(" as ").map(|(_, type_)| type_.trim_end_matches(';'));
+                json["crypto"] = json!({ "op": op, "algorithm": algorithm, "output_type": output_type });
+            }
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(add_crypto_variants),
+        Value::Array(array) => array.iter_mut().for_each(add_crypto_variants),
+        _ => (),
+    }
+}
+
+/// Tags each instruction and command in the given JSON with its opcode, i.e. `"opcode": "add"`.
+/// An opcode missing from [`OPCODES`] is tagged as `"unknown:<opcode>"`, or is an error if `deny_unknown` is set.
+pub fn tag_opcodes(json: &mut Value, deny_unknown: bool) -> Result<(), Aleo2JsonError> {
+    if matches!(json.get("type").and_then(Value::as_str), Some("Instruction" | "Command")) {
+        if let Some(instruction) = json.get("str").and_then(Value::as_str).map(str::to_string) {
+            let opcode = opcode_of(&instruction);
+            json["opcode"] = match explain_opcode(opcode) {
+                Some(info) => json!(info.opcode),
+                None if deny_unknown => {
+                    return Err(Aleo2JsonError::UnknownOpcode { opcode: opcode.to_string(), instruction });
+                }
+                None => json!(format!("unknown:{opcode}")),
+            };
+        }
+    }
+    match json {
+        Value::Object(object) => object.values_mut().try_for_each(|value| tag_opcodes(value, deny_unknown)),
+        Value::Array(array) => array.iter_mut().try_for_each(|value| tag_opcodes(value, deny_unknown)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::CurrentNetwork;
+
+    use snarkvm_synthesizer_program::{Instruction, Operand};
+
+    use std::str::FromStr;
+
+    #[test]
+    fn test_every_instruction_is_explained() {
+        for opcode in Instruction::<CurrentNetwork>::OPCODES {
+            assert!(explain_opcode(opcode).is_some(), "Missing an explanation for '{}'", **opcode);
+        }
+    }
+
+    #[test]
+    fn test_builtins() {
+        for (name, _) in BUILTINS {
+            let json = Operand::<CurrentNetwork>::from_str(name).unwrap().to_json();
+            assert_eq!(json["kind"], "builtin", "'{name}' is not a built-in operand");
+            assert_eq!(json["name"], *name);
+        }
+        // A program ID is an identifier, rather than a built-in.
+        let json = Operand::<CurrentNetwork>::from_str("token.aleo").unwrap().to_json();
+        assert_eq!(json["kind"], "identifier");
+        assert!(json.get("name").is_none());
+    }
+
+    #[test]
+    fn test_explain_opcode() {
+        let info = explain_opcode("add").unwrap();
+        assert_eq!(info.signature, "add <first> <second> into <destination>");
+        assert!(info.to_string().starts_with("add\n\n    add <first> <second> into <destination>\n\n"));
+
+        assert!(explain_opcode("get.or_use").is_some());
+        assert!(explain_opcode("foo").is_none());
+    }
+
+    #[test]
+    fn test_arity() {
+        let arity = |opcode: &str| explain_opcode(opcode).unwrap().arity();
+        assert_eq!(arity("add"), (2, Some(2)));
+        assert_eq!(arity("neg"), (1, Some(1)));
+        assert_eq!(arity("ternary"), (3, Some(3)));
+        assert_eq!(arity("assert.eq"), (2, Some(2)));
+        assert_eq!(arity("hash.bhp256"), (1, Some(1)));
+        assert_eq!(arity("call"), (0, None));
+        assert_eq!(arity("cast"), (1, None));
+    }
+
+    #[test]
+    fn test_add_arity() {
+        let program = crate::aleo2json::parse_program::<CurrentNetwork>(
+            "program arity.aleo;\n\nfunction main:\n    input r0 as u8.public;\n    ternary true r0 r0 into r1;\n",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_arity(&mut json);
+        assert_eq!(json["functions"]["main"]["instructions"][0]["arity"], 3);
+    }
+
+    #[test]
+    fn test_add_crypto_variants() {
+        let program = crate::aleo2json::parse_program::<CurrentNetwork>(
+            r"
+program crypto.aleo;
+
+function main:
+    input r0 as field.public;
+    input r1 as scalar.public;
+    hash.psd8 r0 into r2 as field;
+    commit.bhp256 r0 r1 into r3 as address;
+    add r0 r2 into r4;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_crypto_variants(&mut json);
+        let instructions = &json["functions"]["main"]["instructions"];
+        assert_eq!(instructions[0]["crypto"], json!({ "op": "hash", "algorithm": "psd8", "output_type": "field" }));
+        assert_eq!(
+            instructions[1]["crypto"],
+            json!({ "op": "commit", "algorithm": "bhp256", "output_type": "address" })
+        );
+        assert!(instructions[2].get("crypto").is_none());
+    }
+
+    #[test]
+    fn test_tag_opcodes() {
+        let program = crate::aleo2json::parse_program::<CurrentNetwork>(
+            r"
+program tags.aleo;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+function main:
+    input r0 as u64.public;
+    add r0 1u64 into r1;
+    async main self.caller r1 into r2;
+    output r2 as tags.aleo/main.future;
+
+finalize main:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use balances[r0] 0u64 into r2;
+    set r1 into balances[r0];
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        tag_opcodes(&mut json, true).unwrap();
+        let function = &json["functions"]["main"];
+        assert_eq!(function["instructions"][0]["opcode"], "add");
+        assert_eq!(function["instructions"][1]["opcode"], "async");
+        assert_eq!(function["finalize_logic"]["commands"][0]["opcode"], "get.or_use");
+        assert_eq!(function["finalize_logic"]["commands"][1]["opcode"], "set");
+    }
+
+    #[test]
+    fn test_tag_unknown_opcodes() {
+        let unknown = json!({ "type": "Instruction", "str": "frobnicate r0 into r1;" });
+
+        // In the lenient mode, the unknown opcode is tagged.
+        let mut json = json!([unknown]);
+        tag_opcodes(&mut json, false).unwrap();
+        assert_eq!(json[0]["opcode"], "unknown:frobnicate");
+
+        // In the strict mode, the unknown opcode is an error.
+        let mut json = json!([unknown]);
+        let error = tag_opcodes(&mut json, true).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown opcode 'frobnicate' in 'frobnicate r0 into r1;'");
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::Value;
+
+/// Replaces each operand in the given JSON, i.e. `{ "type": "Operand", "kind": "register", ..., "str": "r0" }`, with
+/// its string, i.e. `"r0"`, as printed in the program. This includes the operands of instructions, of finalize
+/// commands, and of outputs. As the annotations of an operand are dropped with it, this is done last.
+pub fn operands_to_strings(json: &mut Value) {
+    if json["type"] == "Operand" {
+        if let Some(string) = json["str"].as_str() {
+            *json = Value::String(string.to_string());
+        }
+        return;
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(operands_to_strings),
+        Value::Array(array) => array.iter_mut().for_each(operands_to_strings),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aleo2json::{convert_program, ConvertOptions, OperandFormat};
+
+    use serde_json::json;
+
+    #[test]
+    fn test_operands_to_strings() {
+        let source = r"
+program operands.aleo;
+
+struct wallet:
+    amount as u64;
+
+function main:
+    input r0 as wallet.private;
+    add r0.amount 1u64 into r1;
+    async main r1 into r2;
+    output r2 as operands.aleo/main.future;
+
+finalize main:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+";
+        let options = ConvertOptions::new().operand_format(OperandFormat::Strings);
+        let json = convert_program(source, &options).unwrap();
+        let main = &json["functions"]["main"];
+        assert_eq!(main["instructions"][0]["value"]["operands"], json!(["r0.amount", "1u64"]));
+        assert_eq!(main["outputs"][0]["operand"], "r2");
+        assert_eq!(main["finalize_logic"]["commands"][0]["value"]["value"]["operands"], json!(["r0", "r0"]));
+
+        // The operands are objects by default.
+        let json = convert_program(source, &ConvertOptions::new()).unwrap();
+        assert_eq!(json["functions"]["main"]["instructions"][0]["value"]["operands"][0]["type"], "Operand");
+    }
+}
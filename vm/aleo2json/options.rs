@@ -0,0 +1,516 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{
+    escape_non_ascii,
+    to_json_string,
+    to_string_by_threshold,
+    to_string_pretty_arrays_inline,
+    write_chunked,
+    OutputFormat,
+};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+/// The network to parse the input for.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum NetworkName {
+    /// The Aleo Testnet3 network.
+    #[default]
+    Testnet3,
+}
+
+impl NetworkName {
+    /// Returns the name of the network, as it is given to `--network`, i.e. `testnet3`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Testnet3 => "testnet3",
+        }
+    }
+}
+
+/// The representation of field elements, and of group x-coordinates, in the output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum FieldRepr {
+    /// The canonical integer, i.e. `1`.
+    #[default]
+    Canonical,
+    /// The raw Montgomery form, as stored in the limbs of the field element, i.e. `1 * 2^256 mod p`.
+    Montgomery,
+}
+
+/// The representation of operands in the output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OperandFormat {
+    /// A tagged object, i.e. `{ "type": "Operand", "kind": "register", ..., "str": "r0" }`.
+    #[default]
+    Objects,
+    /// The operand as printed in the program, i.e. `"r0"`.
+    Strings,
+}
+
+/// The encoding of an input file.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum InputEncoding {
+    /// UTF-8, failing on an invalid sequence.
+    #[default]
+    Utf8,
+    /// Latin-1 (ISO-8859-1), where each byte is the character of the same code point.
+    Latin1,
+}
+
+/// The options for converting an input into JSON, i.e.
+/// `ConvertOptions::new().network(NetworkName::Testnet3).pretty(true).split_literals(true)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConvertOptions {
+    /// The network to parse the input for.
+    pub(crate) network: NetworkName,
+    /// The serialization format of the output.
+    pub(crate) format: OutputFormat,
+    /// Whether to indent the output.
+    pub(crate) pretty: bool,
+    /// Whether to indent the JSON output, rendering arrays of scalars on a single line.
+    pub(crate) pretty_arrays_inline: bool,
+    /// The length of the compact JSON output under which it is indented instead, if any.
+    pub(crate) pretty_threshold: Option<usize>,
+    /// Whether to emit the dataflow of the program, instead of the program.
+    pub(crate) dataflow: bool,
+    /// Whether to emit the control-flow graph of each finalize logic, instead of the program.
+    pub(crate) cfg: bool,
+    /// Whether to emit a table with one row per instruction, instead of the program.
+    pub(crate) flatten: bool,
+    /// Whether to emit each literal as its value and its type.
+    pub(crate) split_literals: bool,
+    /// Whether to emit each field and group literal in decimal and hex forms.
+    pub(crate) hex_fields: bool,
+    /// Whether to emit each boolean and 32-bit or narrower integer literal as a native JSON value.
+    pub(crate) native_literals: bool,
+    /// Whether to emit each group literal as its affine coordinates.
+    pub(crate) expand_group: bool,
+    /// Whether to emit each field and scalar literal as zero-padded, fixed-width hex.
+    pub(crate) fixed_width_fields: bool,
+    /// The representation of field elements, and of group x-coordinates.
+    pub(crate) field_repr: FieldRepr,
+    /// The maximum number of functions to emit, if any.
+    pub(crate) limit_functions: Option<usize>,
+    /// The directory to resolve imports from, if imports are to be inlined.
+    pub(crate) import_dir: Option<PathBuf>,
+    /// Whether to validate the program, emitting the violations found under `"violations"`.
+    pub(crate) validate: bool,
+    /// Whether to check that each referenced name is defined, emitting the dangling references under `"violations"`.
+    pub(crate) validate_closed: bool,
+    /// Whether to fail on any unknown opcode, instead of tagging it as unknown.
+    pub(crate) deny_unknown_opcodes: bool,
+    /// Whether to emit the statistics of the program under `"stats"`.
+    pub(crate) stats: bool,
+    /// Whether to include the statistics of each function in the statistics.
+    pub(crate) per_function: bool,
+    /// The top-level sections to emit, if not all of them.
+    pub(crate) only: Vec<String>,
+    /// The token to replace private values with, if they are to be redacted.
+    pub(crate) redact: Option<String>,
+    /// Whether to emit the hash of the program, and of each function body.
+    pub(crate) with_hashes: bool,
+    /// Whether to emit the fingerprint of the program, over its ID, its network, and its hash.
+    pub(crate) with_fingerprint: bool,
+    /// Whether to escape each non-ASCII character in the JSON output.
+    pub(crate) ascii: bool,
+    /// Whether to remove each empty top-level section.
+    pub(crate) prune_empty: bool,
+    /// The renames of the top-level sections, i.e. `functions=procedures`.
+    pub(crate) rename_keys: Vec<String>,
+    /// The regex to filter the rows of the instruction table with, if any.
+    pub(crate) grep: Option<String>,
+    /// The function to filter the rows of the instruction table with, if any.
+    pub(crate) grep_function: Option<String>,
+    /// Whether to emit each instruction on a line of its own, keyed by its UID, instead of the program.
+    pub(crate) jsonl_index: bool,
+    /// Whether to bundle the canonical source, its hash, and its JSON into one object.
+    pub(crate) canonicalize: bool,
+    /// Whether to mark each instruction operating only on constants with `"const"`.
+    pub(crate) annotate_const: bool,
+    /// The maximum nesting depth of a type or a value, if any.
+    pub(crate) max_depth: Option<usize>,
+    /// Whether to inline the structs referenced by the key and the value of each mapping.
+    pub(crate) expand_types: bool,
+    /// Whether to normalize the program before computing its hashes.
+    pub(crate) normalize_before_hash: bool,
+    /// Whether to emit the estimated proving cost of each function under `"cost_estimate"`.
+    pub(crate) with_cost: bool,
+    /// Whether to emit the length of the serialization of each function, and of the program, under `"bytes_len"`.
+    pub(crate) with_byte_sizes: bool,
+    /// Whether to emit the number of field elements encoding each entry of a record under `"encoded_fields"`.
+    pub(crate) with_encoding: bool,
+    /// Whether to emit the bit offset and width of each struct member and record entry under `"layout"`.
+    pub(crate) with_layout: bool,
+    /// Whether to emit the source text of each operand under `"raw"`.
+    pub(crate) with_raw_operands: bool,
+    /// Whether to rewrite each instruction in its canonical form before converting the program.
+    pub(crate) desugar: bool,
+    /// The representation of operands.
+    pub(crate) operand_format: OperandFormat,
+    /// Whether to emit the source line of each instruction under `"line"`.
+    pub(crate) with_lines: bool,
+    /// Whether to emit the JSON pointer of each significant node under `"_ptr"`.
+    pub(crate) with_pointers: bool,
+    /// Whether to emit the version of the structure of the output under `"schema_version"`.
+    pub(crate) with_meta: bool,
+}
+
+impl ConvertOptions {
+    /// Initializes the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the network to parse the input for.
+    pub fn network(mut self, network: NetworkName) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Sets the serialization format of the output.
+    pub fn format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets whether to indent the output.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Sets whether to indent the JSON output, rendering arrays of scalars on a single line.
+    pub fn pretty_arrays_inline(mut self, pretty_arrays_inline: bool) -> Self {
+        self.pretty_arrays_inline = pretty_arrays_inline;
+        self
+    }
+
+    /// Sets the length in bytes of the compact JSON output under which it is indented instead, i.e. to indent small
+    /// outputs only. The compact output is serialized first, to measure it. Only applies to JSON.
+    pub fn pretty_threshold(mut self, pretty_threshold: Option<usize>) -> Self {
+        self.pretty_threshold = pretty_threshold;
+        self
+    }
+
+    /// Sets whether to emit the dataflow of the program, instead of the program.
+    pub fn dataflow(mut self, dataflow: bool) -> Self {
+        self.dataflow = dataflow;
+        self
+    }
+
+    /// Sets whether to emit the control-flow graph of the finalize logic of each function, instead of the program,
+    /// with a node per basic block, and an edge per branch and fallthrough.
+    pub fn cfg(mut self, cfg: bool) -> Self {
+        self.cfg = cfg;
+        self
+    }
+
+    /// Sets whether to emit a table with one row per instruction, instead of the program.
+    pub fn flatten(mut self, flatten: bool) -> Self {
+        self.flatten = flatten;
+        self
+    }
+
+    /// Sets whether to emit each literal as its value and its type.
+    pub fn split_literals(mut self, split_literals: bool) -> Self {
+        self.split_literals = split_literals;
+        self
+    }
+
+    /// Sets whether to emit each field and group literal in decimal and hex forms.
+    pub fn hex_fields(mut self, hex_fields: bool) -> Self {
+        self.hex_fields = hex_fields;
+        self
+    }
+
+    /// Sets whether to emit each group literal as its affine coordinates, i.e. `{ "x": "0", "y": "1" }`,
+    /// instead of its x-coordinate.
+    pub fn expand_group(mut self, expand_group: bool) -> Self {
+        self.expand_group = expand_group;
+        self
+    }
+
+    /// Sets whether to emit each field and scalar literal as big-endian hex, zero-padded to the size in bytes of its
+    /// type, i.e. `0x00...2a`, instead of the shortest decimal string, so that outputs compare byte by byte.
+    pub fn fixed_width_fields(mut self, fixed_width_fields: bool) -> Self {
+        self.fixed_width_fields = fixed_width_fields;
+        self
+    }
+
+    /// Sets the representation of field elements, and of group x-coordinates, i.e. `FieldRepr::Montgomery`.
+    pub fn field_repr(mut self, field_repr: FieldRepr) -> Self {
+        self.field_repr = field_repr;
+        self
+    }
+
+    /// Sets whether to emit each boolean and 32-bit or narrower integer literal as a native JSON boolean or number.
+    /// Wider integers, fields, groups, and scalars stay strings, as they may not fit in a JSON number.
+    pub fn native_literals(mut self, native_literals: bool) -> Self {
+        self.native_literals = native_literals;
+        self
+    }
+
+    /// Sets the maximum number of functions to emit, if any.
+    pub fn limit_functions(mut self, limit_functions: Option<usize>) -> Self {
+        self.limit_functions = limit_functions;
+        self
+    }
+
+    /// Sets the directory to resolve imports from, if imports are to be inlined under `"resolved_imports"`.
+    pub fn import_dir(mut self, import_dir: Option<PathBuf>) -> Self {
+        self.import_dir = import_dir;
+        self
+    }
+
+    /// Sets whether to validate the program, emitting the violations found under `"violations"`.
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Sets whether to check that each name referenced by the program, i.e. a struct, a record, a closure, a
+    /// function, or a mapping, is defined in it or in one of its declared imports, emitting the dangling references
+    /// under `"violations"`, along with those of `validate`, if set.
+    pub fn validate_closed(mut self, validate_closed: bool) -> Self {
+        self.validate_closed = validate_closed;
+        self
+    }
+
+    /// Sets whether to fail on any unknown opcode, instead of tagging it as `"opcode": "unknown:<opcode>"`.
+    pub fn deny_unknown_opcodes(mut self, deny_unknown_opcodes: bool) -> Self {
+        self.deny_unknown_opcodes = deny_unknown_opcodes;
+        self
+    }
+
+    /// Sets whether to emit the statistics of the program under `"stats"`.
+    pub fn stats(mut self, stats: bool) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Sets whether to include the statistics of each function in the statistics.
+    pub fn per_function(mut self, per_function: bool) -> Self {
+        self.per_function = per_function;
+        self
+    }
+
+    /// Sets the top-level sections to emit, always retaining `"id"`. If empty, all sections are emitted.
+    pub fn only(mut self, only: Vec<String>) -> Self {
+        self.only = only;
+        self
+    }
+
+    /// Sets whether to remove each top-level section that is an empty array or object, i.e. `"structs": {}`.
+    /// By default every section is emitted, so that its key is always present.
+    pub fn prune_empty(mut self, prune_empty: bool) -> Self {
+        self.prune_empty = prune_empty;
+        self
+    }
+
+    /// Sets the token to replace private values with, if they are to be redacted, i.e. `Some("<redacted>")`.
+    pub fn redact(mut self, redact: Option<String>) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// Sets whether to emit the hash of the program under `"program_hash"`, and of each function under `"body_hash"`.
+    pub fn with_hashes(mut self, with_hashes: bool) -> Self {
+        self.with_hashes = with_hashes;
+        self
+    }
+
+    /// Sets whether to emit the fingerprint of the program under `"fingerprint"`, as computed by `fingerprint`.
+    pub fn with_fingerprint(mut self, with_fingerprint: bool) -> Self {
+        self.with_fingerprint = with_fingerprint;
+        self
+    }
+
+    /// Sets whether to escape each non-ASCII character in the JSON output as `\uXXXX`.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Sets the renames of the top-level sections, i.e. `functions=procedures`. Applied after `only`.
+    pub fn rename_keys(mut self, rename_keys: Vec<String>) -> Self {
+        self.rename_keys = rename_keys;
+        self
+    }
+
+    /// Sets the regex to filter the rows of the instruction table with, if any, emitting the table.
+    pub fn grep(mut self, grep: Option<String>) -> Self {
+        self.grep = grep;
+        self
+    }
+
+    /// Sets the function to filter the rows of the instruction table with, if any.
+    pub fn grep_function(mut self, grep_function: Option<String>) -> Self {
+        self.grep_function = grep_function;
+        self
+    }
+
+    /// Sets whether to emit each instruction on a line of its own, keyed by its UID (i.e. `{ "mint#0": { ... } }`),
+    /// instead of the program. Each line is a JSON object of its own, whatever the format.
+    pub fn jsonl_index(mut self, jsonl_index: bool) -> Self {
+        self.jsonl_index = jsonl_index;
+        self
+    }
+
+    /// Sets whether to emit `{ "source": ..., "json": ..., "hash": "sha256:..." }`, where the source is the canonical
+    /// form of the program, and the hash is over that source.
+    pub fn canonicalize(mut self, canonicalize: bool) -> Self {
+        self.canonicalize = canonicalize;
+        self
+    }
+
+    /// Sets whether to mark each instruction whose operands are all constant inputs, literals, or registers computed
+    /// from them, with `"const": true`, and every other instruction with `"const": false`.
+    pub fn annotate_const(mut self, annotate_const: bool) -> Self {
+        self.annotate_const = annotate_const;
+        self
+    }
+
+    /// Sets the maximum nesting depth of a type or a value, if any, i.e. `Some(DEFAULT_MAX_DEPTH)`.
+    /// A program with a deeper struct, record, or mapping type fails to convert, reporting its deepest path.
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets whether to inline the structs referenced by the key and the value of each mapping under `"expanded"`,
+    /// i.e. `"expanded": { "x": "field", "y": "field" }`.
+    pub fn expand_types(mut self, expand_types: bool) -> Self {
+        self.expand_types = expand_types;
+        self
+    }
+
+    /// Sets whether to normalize the program before computing its hashes with `with_hashes`, i.e. to renumber
+    /// the registers of each function and closure in order of first use, and to sort its definitions, so that
+    /// semantically identical programs have the same `"program_hash"`.
+    pub fn normalize_before_hash(mut self, normalize_before_hash: bool) -> Self {
+        self.normalize_before_hash = normalize_before_hash;
+        self
+    }
+
+    /// Sets whether to emit the estimated proving cost of each function and closure under `"cost_estimate"`,
+    /// as the sum of the weights in `OPCODE_WEIGHTS` of its instructions.
+    pub fn with_cost(mut self, with_cost: bool) -> Self {
+        self.with_cost = with_cost;
+        self
+    }
+
+    /// Sets whether to emit the length in bytes of the `ToBytes` serialization of each function and closure, and of
+    /// the whole program, under `"bytes_len"`, i.e. the space each takes on chain.
+    pub fn with_byte_sizes(mut self, with_byte_sizes: bool) -> Self {
+        self.with_byte_sizes = with_byte_sizes;
+        self
+    }
+
+    /// Sets whether to emit the number of field elements encoding each data entry of a record under
+    /// `"encoded_fields"`, i.e. the number of field elements of its ciphertext, if it is private.
+    pub fn with_encoding(mut self, with_encoding: bool) -> Self {
+        self.with_encoding = with_encoding;
+        self
+    }
+
+    /// Sets whether to emit the bit offset and the bit width of each struct member and record entry in their
+    /// `to_bits_le` encoding under `"layout"`, i.e. `"layout": { "x": { "offset": 34, "width": 34 } }`.
+    pub fn with_layout(mut self, with_layout: bool) -> Self {
+        self.with_layout = with_layout;
+        self
+    }
+
+    /// Sets whether to emit the source text of each operand of each function and closure instruction under `"raw"`,
+    /// i.e. `"raw": "r0.amount"`. Only applies when converting from a program source.
+    pub fn with_raw_operands(mut self, with_raw_operands: bool) -> Self {
+        self.with_raw_operands = with_raw_operands;
+        self
+    }
+
+    /// Sets whether to rewrite each instruction in its canonical, fully explicit form before converting the program,
+    /// i.e. `gt r0 r1 into r2` as `lt r1 r0 into r2`, as in `desugar_program`, so that equivalent spellings of a
+    /// program have the same output, including their hashes.
+    pub fn desugar(mut self, desugar: bool) -> Self {
+        self.desugar = desugar;
+        self
+    }
+
+    /// Sets the representation of operands, i.e. `OperandFormat::Strings` for `"r0"`. The annotations of an operand,
+    /// i.e. its `"access"` path, its `"raw"` source text, and the forms of its literal, need `OperandFormat::Objects`.
+    pub fn operand_format(mut self, operand_format: OperandFormat) -> Self {
+        self.operand_format = operand_format;
+        self
+    }
+
+    /// Sets whether to emit the 1-based source line of each function and closure instruction under `"line"`,
+    /// i.e. `"line": 12`. Only applies when converting from a program source.
+    pub fn with_lines(mut self, with_lines: bool) -> Self {
+        self.with_lines = with_lines;
+        self
+    }
+
+    /// Sets whether to emit the JSON pointer of each definition, function, closure, and instruction to itself under
+    /// `"_ptr"`, i.e. `"_ptr": "/functions/main/instructions/0"`.
+    pub fn with_pointers(mut self, with_pointers: bool) -> Self {
+        self.with_pointers = with_pointers;
+        self
+    }
+
+    /// Sets whether to emit the version of the structure of the output under `"schema_version"`, i.e. `1`.
+    pub fn with_meta(mut self, with_meta: bool) -> Self {
+        self.with_meta = with_meta;
+        self
+    }
+
+    /// Serializes the given JSON with these options.
+    pub fn serialize(&self, json: &serde_json::Value) -> Result<String> {
+        // Each line of the instruction index stands on its own, so that the output can be streamed line by line.
+        if let (true, Some(entries)) = (self.jsonl_index, json.as_array()) {
+            let lines = entries.iter().map(|entry| to_json_string(entry, false)).collect::<Vec<_>>();
+            return Ok(lines.join("\n"));
+        }
+        let output = match (self.format, self.pretty_threshold) {
+            (OutputFormat::Json, _) if self.pretty_arrays_inline => to_string_pretty_arrays_inline(json)?,
+            (OutputFormat::Json, Some(threshold)) => to_string_by_threshold(json, threshold)?,
+            (format, _) => format.serialize(json, self.pretty)?,
+        };
+        match self.ascii && self.format == OutputFormat::Json {
+            true => Ok(escape_non_ascii(&output)),
+            false => Ok(output),
+        }
+    }
+
+    /// Serializes the given JSON with these options to the given writer, serializing the functions of a program
+    /// `chunk_size` at a time, with the same output as [`Self::serialize`]. Any other format than plain or indented
+    /// JSON is serialized as a whole.
+    pub fn serialize_chunked<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        json: serde_json::Value,
+        chunk_size: usize,
+    ) -> Result<()> {
+        if self.format != OutputFormat::Json || self.pretty_arrays_inline {
+            writer.write_all(self.serialize(&json)?.as_bytes())?;
+            return Ok(writer.flush()?);
+        }
+        match self.ascii {
+            true => write_chunked(writer, json, self.pretty, chunk_size, |chunk| escape_non_ascii(&chunk)),
+            false => write_chunked(writer, json, self.pretty, chunk_size, |chunk| chunk),
+        }
+    }
+}
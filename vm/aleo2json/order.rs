@@ -0,0 +1,70 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::network::Network;
+use snarkvm_synthesizer_program::Program;
+
+/// Returns the names of the closures and functions of the given program, in declaration order, i.e.
+/// `["twice", "main", "helper"]`, so that the order survives a consumer that does not keep the order of the keys of
+/// `"closures"` and `"functions"`. As the program holds its closures apart from its functions, their interleaving is
+/// recovered from the printed program, which prints each definition in declaration order, without indentation.
+pub fn declaration_order<N: Network>(program: &Program<N>) -> Vec<String> {
+    let string = program.to_string();
+    let headers = string.lines().filter_map(|line| match line.split_once(' ')? {
+        ("closure" | "function", name) => Some(name.trim_end_matches(':').to_string()),
+        _ => None,
+    });
+    headers.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, parse_program, ConvertOptions, CurrentNetwork};
+
+    use serde_json::json;
+
+    const PROGRAM: &str = r"
+program order.aleo;
+
+closure twice:
+    input r0 as u8;
+    add r0 r0 into r1;
+    output r1 as u8;
+
+function main:
+    input r0 as u8.public;
+    call twice r0 into r1;
+    output r1 as u8.public;
+
+closure triple:
+    input r0 as u8;
+    mul r0 3u8 into r1;
+    output r1 as u8;
+
+function other:
+    input r0 as u8.public;
+    call triple r0 into r1;
+    output r1 as u8.public;
+";
+
+    #[test]
+    fn test_declaration_order() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        assert_eq!(declaration_order(&program), ["twice", "main", "triple", "other"]);
+
+        let json = convert_program(PROGRAM, &ConvertOptions::new()).unwrap();
+        assert_eq!(json["order"], json!(["twice", "main", "triple", "other"]));
+    }
+}
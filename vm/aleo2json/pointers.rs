@@ -0,0 +1,119 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::NodeVisitor;
+
+use serde_json::{json, Value};
+
+/// Escapes the given reference token of a JSON pointer, as in RFC 6901, i.e. `a/b` as `a~1b`.
+fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// A visitor adding the JSON pointer of each definition, function, closure, and instruction to itself under `"_ptr"`,
+/// i.e. `"_ptr": "/functions/main/instructions/0"`, so that external annotations can reference each node.
+/// The pointers are into the program JSON, before any top-level section is renamed.
+#[derive(Default)]
+pub struct Pointers {
+    /// The section of the function or closure being visited, i.e. `functions`.
+    section: String,
+}
+
+impl NodeVisitor for Pointers {
+    fn visit_definition(&mut self, section: &str, name: &str, json: &mut Value) {
+        json["_ptr"] = json!(format!("/{section}/{}", escape_token(name)));
+    }
+
+    fn visit_function(&mut self, section: &str, name: &str, json: &mut Value) {
+        self.section = section.to_string();
+        json["_ptr"] = json!(format!("/{section}/{}", escape_token(name)));
+    }
+
+    fn visit_instruction(&mut self, function: &str, index: usize, json: &mut Value) {
+        json["_ptr"] = json!(format!("/{}/{}/instructions/{index}", self.section, escape_token(function)));
+    }
+}
+
+/// Appends the given JSON pointer, and the pointer of each node under it in the given JSON, to the given pointers.
+fn collect_pointers(json: &Value, pointer: &mut String, pointers: &mut Vec<String>) {
+    pointers.push(pointer.clone());
+    let mut descend = |token: &str, child: &Value| {
+        let length = pointer.len();
+        pointer.push('/');
+        pointer.push_str(&escape_token(token));
+        collect_pointers(child, pointer, pointers);
+        pointer.truncate(length);
+    };
+    match json {
+        Value::Object(object) => object.iter().for_each(|(key, child)| descend(key, child)),
+        Value::Array(array) => array.iter().enumerate().for_each(|(index, child)| descend(&index.to_string(), child)),
+        _ => (),
+    }
+}
+
+/// Returns the JSON pointer of each node of the given JSON, in document order, i.e. `["", "/id", "/id/name", ...]`,
+/// starting with the empty pointer of the JSON itself, and including each object key and each array index.
+pub fn json_pointers(json: &Value) -> Vec<String> {
+    let mut pointers = Vec::new();
+    collect_pointers(json, &mut String::new(), &mut pointers);
+    pointers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, ConvertOptions};
+
+    #[test]
+    fn test_pointers() {
+        let string = r"
+program pointers.aleo;
+
+struct point:
+    x as field;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function main:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    add r1 r1 into r2;
+";
+        let json = convert_program(string, &ConvertOptions::new().with_pointers(true)).unwrap();
+        // Each pointer resolves to the node carrying it.
+        for pointer in ["/structs/point", "/closures/twice", "/functions/main/instructions/1"] {
+            assert_eq!(json.pointer(pointer).unwrap()["_ptr"], pointer);
+        }
+        assert_eq!(json["closures"]["twice"]["instructions"][0]["_ptr"], "/closures/twice/instructions/0");
+
+        // Without the option, no node has a pointer.
+        let json = convert_program(string, &ConvertOptions::new()).unwrap();
+        assert!(json["functions"]["main"].get("_ptr").is_none());
+
+        // Each pointer of the list resolves to a node, and every node is listed.
+        let pointers = json_pointers(&json);
+        assert!(pointers.iter().all(|pointer| json.pointer(pointer).is_some()));
+        assert!(pointers.contains(&"/functions/main/instructions/1".to_string()));
+        let pointers = json_pointers(&json!({ "a/b": [1, { "c": null }] }));
+        assert_eq!(pointers, ["", "/a~1b", "/a~1b/0", "/a~1b/1", "/a~1b/1/c"]);
+    }
+
+    #[test]
+    fn test_escape_token() {
+        assert_eq!(escape_token("a/b~c"), "a~1b~0c");
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{path::Path, time::Duration};
+
+/// The number of slowest files reported by `--profile`, after converting a directory.
+pub const PROFILE_SLOWEST: usize = 5;
+
+/// A phase of the conversion of an input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Parsing the input.
+    Parse,
+    /// Building the JSON of the parsed input.
+    Build,
+    /// Serializing the JSON.
+    Serialize,
+}
+
+impl Phase {
+    /// The phases, in the order they run in.
+    const ALL: [Self; 3] = [Self::Parse, Self::Build, Self::Serialize];
+
+    /// Returns the name of the phase, i.e. `parse`.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Build => "build",
+            Self::Serialize => "serialize",
+        }
+    }
+}
+
+/// The time spent in each phase of the conversion, in total and on each file, reported to stderr by `--profile`.
+#[derive(Debug, Default)]
+pub struct Profile {
+    /// The total time spent in each phase, in the order of `Phase::ALL`.
+    totals: [Duration; 3],
+    /// The time spent on each finished file, across all phases.
+    files: Vec<(String, Duration)>,
+    /// The time spent on the current file so far.
+    current: Duration,
+}
+
+impl Profile {
+    /// Records the given time spent in the given phase, on the current file.
+    pub fn record(&mut self, phase: Phase, elapsed: Duration) {
+        self.totals[phase as usize] += elapsed;
+        self.current += elapsed;
+    }
+
+    /// Finishes the current file, at the given path.
+    pub fn finish_file(&mut self, path: &Path) {
+        self.files.push((path.display().to_string(), std::mem::take(&mut self.current)));
+    }
+
+    /// Returns the report of the profile, i.e. `profile: parse 12.500 ms, build 3.100 ms, serialize 0.800 ms`,
+    /// followed by the slowest files, if more than one file was converted.
+    pub fn report(&self) -> String {
+        let phases = Phase::ALL.map(|phase| format!("{} {}", phase.name(), millis(self.totals[phase as usize])));
+        let mut report = format!("profile: {}\n", phases.join(", "));
+        if self.files.len() > 1 {
+            let mut files = self.files.iter().collect::<Vec<_>>();
+            files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            report.push_str("profile: slowest files\n");
+            for (path, elapsed) in files.into_iter().take(PROFILE_SLOWEST) {
+                report.push_str(&format!("  {}  {path}\n", millis(*elapsed)));
+            }
+        }
+        report
+    }
+}
+
+/// Formats the given duration in milliseconds, i.e. `12.500 ms`.
+fn millis(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let mut profile = Profile::default();
+        profile.record(Phase::Parse, Duration::from_millis(2));
+        profile.record(Phase::Build, Duration::from_millis(1));
+        profile.finish_file(Path::new("a.aleo"));
+        assert_eq!(profile.report(), "profile: parse 2.000 ms, build 1.000 ms, serialize 0.000 ms\n");
+
+        profile.record(Phase::Parse, Duration::from_millis(5));
+        profile.finish_file(Path::new("b.aleo"));
+        profile.record(Phase::Serialize, Duration::from_micros(500));
+        assert_eq!(
+            profile.report(),
+            "profile: parse 7.000 ms, build 1.000 ms, serialize 0.500 ms\n\
+             profile: slowest files\n  5.000 ms  b.aleo\n  3.000 ms  a.aleo\n"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::network::Network;
+use snarkvm_synthesizer_program::{Command, Program};
+
+use serde_json::{json, Value};
+
+/// Adds the source of randomness of each `rand.*` command of each finalize logic under `"random"`, i.e.
+/// `"random": { "op": "rand", "algorithm": "chacha", "seeds": ["r0"], "output_type": "u64" }` for
+/// `rand.chacha r0 into r1 as u64`, as `"crypto"` does for the hash family, so that each nondeterministic command
+/// can be found without parsing its opcode.
+pub fn add_random_sources<N: Network>(json: &mut Value, program: &Program<N>) {
+    for (name, function) in program.functions() {
+        let Some(finalize) = function.finalize_logic() else {
+            continue;
+        };
+        let Some(commands) = json.pointer_mut(&format!("/functions/{name}/finalize_logic/commands")) else {
+            continue;
+        };
+        for (index, command) in finalize.commands().iter().enumerate() {
+            let random = match command {
+                Command::RandChaCha(rand_chacha) => json!({
+                    "op": "rand",
+                    "algorithm": "chacha",
+                    "seeds": rand_chacha.operands().iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    "output_type": rand_chacha.destination_type().to_string(),
+                }),
+                _ => continue,
+            };
+            commands[index]["random"] = random;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_random_sources() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program lottery.aleo;
+
+mapping winners:
+    key as u8.public;
+    value as address.public;
+
+function draw:
+    input r0 as u64.public;
+    async draw r0 into r1;
+    output r1 as lottery.aleo/draw.future;
+
+finalize draw:
+    input r0 as u64.public;
+    rand.chacha into r1 as u8;
+    rand.chacha r0 into r2 as address;
+    set r2 into winners[r1];
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_random_sources(&mut json, &program);
+        let commands = &json["functions"]["draw"]["finalize_logic"]["commands"];
+        assert_eq!(
+            commands[0]["random"],
+            json!({ "op": "rand", "algorithm": "chacha", "seeds": [], "output_type": "u8" })
+        );
+        assert_eq!(
+            commands[1]["random"],
+            json!({ "op": "rand", "algorithm": "chacha", "seeds": ["r0"], "output_type": "address" })
+        );
+        assert!(commands[2].get("random").is_none());
+    }
+}
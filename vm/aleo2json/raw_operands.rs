@@ -0,0 +1,189 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::NodeVisitor;
+
+use serde_json::{json, Value};
+use std::{collections::HashMap, ops::Range};
+
+/// Returns the given source with each comment replaced by spaces, so that every other byte keeps its offset.
+pub(crate) fn mask_comments(source: &str) -> String {
+    let mut masked = source.as_bytes().to_vec();
+    let mut index = 0;
+    while index < masked.len() {
+        let end = match &masked[index..] {
+            [b'/', b'/', ..] => {
+                let line = masked[index..].iter().position(|byte| *byte == b'\n');
+                line.map_or(masked.len(), |end| index + end)
+            }
+            [b'/', b'*', ..] => {
+                let body = masked[index + 2..].windows(2).position(|window| window == b"*/");
+                body.map_or(masked.len(), |end| index + 2 + end + 2)
+            }
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+        // Keep the line breaks of a block comment, as they do not affect the offsets.
+        masked[index..end].iter_mut().filter(|byte| **byte != b'\n').for_each(|byte| *byte = b' ');
+        index = end;
+    }
+    // Each comment is replaced as a whole, so the multi-byte characters outside of comments are intact.
+    String::from_utf8_lossy(&masked).into_owned()
+}
+
+/// Returns the byte ranges of the operands of the given instruction, i.e. `r0` and `1u64` in `add r0 1u64 into r1`,
+/// given the offset of the instruction in the source. The function of a `call` or an `async` is not an operand.
+fn operand_ranges(instruction: &str, offset: usize) -> Vec<Range<usize>> {
+    let mut words = instruction.split_whitespace();
+    let skip = match words.next() {
+        Some("call" | "async") => 1,
+        _ => 0,
+    };
+    words
+        .skip(skip)
+        .take_while(|word| *word != "into")
+        .map(|word| {
+            let start = offset + (word.as_ptr() as usize - instruction.as_ptr() as usize);
+            start..start + word.len()
+        })
+        .collect()
+}
+
+/// Returns the byte ranges of each instruction of each function and closure in the given source with its comments
+/// masked, by name, each including the whitespace before it. A statement ends at a `;`, and a header (i.e.
+/// `function main:`) at a `:`, outside of an array type.
+pub(crate) fn instruction_statements(masked: &str) -> HashMap<String, Vec<Range<usize>>> {
+    let (mut statements, mut current, mut depth, mut start) = (HashMap::new(), None, 0usize, 0);
+    for (offset, byte) in masked.bytes().enumerate() {
+        match byte {
+            b'[' => depth += 1,
+            b']' => depth = depth.saturating_sub(1),
+            b':' | b';' if depth == 0 => {
+                let statement = &masked[start..offset];
+                let mut words = statement.split_whitespace();
+                match (byte, words.next(), words.next()) {
+                    (b':', Some("function" | "closure"), Some(name)) => current = Some(name.to_string()),
+                    // The commands of a finalize, and the entries of a definition, are not instructions.
+                    (b':', ..) => current = None,
+                    (_, None | Some("input" | "output"), _) => (),
+                    _ => {
+                        if let Some(name) = &current {
+                            statements.entry(name.clone()).or_insert_with(Vec::new).push(start..offset);
+                        }
+                    }
+                }
+                start = offset + 1;
+            }
+            _ => (),
+        }
+    }
+    statements
+}
+
+/// Returns the byte ranges of the operands of each instruction of each function and closure in the given source,
+/// by name.
+fn instruction_operands(source: &str) -> HashMap<String, Vec<Vec<Range<usize>>>> {
+    let masked = mask_comments(source);
+    let statements = instruction_statements(&masked).into_iter().map(|(name, statements)| {
+        let operands = statements.into_iter().map(|range| operand_ranges(&masked[range.clone()], range.start));
+        (name, operands.collect())
+    });
+    statements.collect()
+}
+
+/// A visitor adding the source text of each operand of each function and closure instruction under `"raw"`,
+/// i.e. `"raw": "r0.amount"`, as it is written in the source, including any non-canonical formatting.
+/// An instruction whose operands cannot be matched with the source is left unchanged.
+pub struct RawOperands<'a> {
+    /// The source of the program.
+    source: &'a str,
+    /// The byte ranges of the operands of each instruction of each function and closure, by name.
+    operands: HashMap<String, Vec<Vec<Range<usize>>>>,
+}
+
+impl<'a> RawOperands<'a> {
+    /// Initializes a visitor adding the source text of each operand from the given program source.
+    pub fn new(source: &'a str) -> Self {
+        Self { source, operands: instruction_operands(source) }
+    }
+}
+
+impl NodeVisitor for RawOperands<'_> {
+    fn visit_instruction(&mut self, function: &str, index: usize, json: &mut Value) {
+        let Some(ranges) = self.operands.get(function).and_then(|instructions| instructions.get(index)) else {
+            return;
+        };
+        let Some(operands) = json["value"].get_mut("operands").and_then(Value::as_array_mut) else {
+            return;
+        };
+        if operands.len() == ranges.len() {
+            for (operand, range) in operands.iter_mut().zip(ranges) {
+                operand["raw"] = json!(&self.source[range.clone()]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, ConvertOptions};
+
+    const SOURCE: &str = r"
+program raw.aleo;
+
+struct wallet:
+    balances as [u64; 2u32];
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1; // add r9 r9 into r9;
+    output r1 as u64;
+
+function main:
+    input r0 as wallet.private;
+    /* add r9 r9 into r9; */
+    add   r0.balances[0u32]    r0.balances[1u32] into r1;
+    call twice r1 into r2;
+    cast r2 r2 into r3 as [u64; 2u32];
+    assert.eq r3[0u32] 0u64;
+";
+
+    #[test]
+    fn test_raw_operands() {
+        let json = convert_program(SOURCE, &ConvertOptions::new().with_raw_operands(true)).unwrap();
+        let raw = |section: &str, name: &str| -> Value {
+            let instructions = json[section][name]["instructions"].as_array().unwrap();
+            let operands = instructions.iter().map(|instruction| instruction["value"]["operands"].as_array().unwrap());
+            operands.map(|operands| operands.iter().map(|operand| operand["raw"].clone()).collect::<Value>()).collect()
+        };
+        assert_eq!(raw("closures", "twice"), json!([["r0", "r0"]]));
+        assert_eq!(
+            raw("functions", "main"),
+            json!([["r0.balances[0u32]", "r0.balances[1u32]"], ["r1"], ["r2", "r2"], ["r3[0u32]", "0u64"]])
+        );
+
+        // Without the option, no operand has a source text.
+        let json = convert_program(SOURCE, &ConvertOptions::new()).unwrap();
+        assert!(json["functions"]["main"]["instructions"][0]["value"]["operands"][0].get("raw").is_none());
+    }
+
+    #[test]
+    fn test_mask_comments() {
+        let masked = mask_comments("a // b\nc /* d\ne */ f");
+        assert_eq!(masked, "a     \nc     \n     f");
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{network::Network, program::EntryType};
+use snarkvm_synthesizer_program::Program;
+
+use serde_json::{json, Value};
+
+/// Returns the catalog of the record types declared in the given program, in declaration order, i.e.
+/// `[{ "name": "token", "owner": "private", "entries": [{ "name": "amount", "type": "u64", "visibility": ... }] }]`.
+/// Unlike a struct member, each record entry has its own visibility, which determines how it is encoded.
+pub fn record_types<N: Network>(program: &Program<N>) -> Value {
+    let records = program.records().iter().map(|(name, record)| {
+        let owner = match record.owner().is_public() {
+            true => "public",
+            false => "private",
+        };
+        let entries = record
+            .entries()
+            .iter()
+            .map(|(name, entry)| {
+                let (plaintext_type, visibility) = match entry {
+                    EntryType::Constant(plaintext_type) => (plaintext_type, "constant"),
+                    EntryType::Public(plaintext_type) => (plaintext_type, "public"),
+                    EntryType::Private(plaintext_type) => (plaintext_type, "private"),
+                };
+                json!({ "name": name.to_string(), "type": plaintext_type.to_string(), "visibility": visibility })
+            })
+            .collect::<Vec<_>>();
+        json!({ "name": name.to_string(), "owner": owner, "entries": entries })
+    });
+    Value::Array(records.collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_record_types() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program records.aleo;
+
+struct point:
+    x as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+    kind as u8.constant;
+    origin as point.public;
+
+record ticket:
+    owner as address.public;
+    id as field.public;
+
+function main:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            record_types(&program),
+            json!([
+                {
+                    "name": "token",
+                    "owner": "private",
+                    "entries": [
+                        { "name": "amount", "type": "u64", "visibility": "private" },
+                        { "name": "kind", "type": "u8", "visibility": "constant" },
+                        { "name": "origin", "type": "point", "visibility": "public" },
+                    ],
+                },
+                {
+                    "name": "ticket",
+                    "owner": "public",
+                    "entries": [{ "name": "id", "type": "field", "visibility": "public" }],
+                },
+            ])
+        );
+    }
+}
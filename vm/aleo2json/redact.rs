@@ -0,0 +1,65 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::Value;
+
+/// The default token to replace private values with.
+pub const DEFAULT_REDACT_TOKEN: &str = "<redacted>";
+
+/// Returns `true` if the given node is a private value, i.e. a record entry
+/// `{ "value": ..., "visibility": "private" }`, or a transition input `{ "type": "private", "id": ..., "value": ... }`.
+fn is_private_value(node: &Value) -> bool {
+    node.get("value").is_some() && (node["visibility"] == "private" || node["type"] == "private")
+}
+
+/// Replaces the value of each private value in the given JSON with the given token, keeping its visibility,
+/// i.e. `{ "value": "<redacted>", "visibility": "private" }`. Public and constant values are left untouched.
+pub fn redact_private(json: &mut Value, token: &str) {
+    if is_private_value(json) {
+        json["value"] = Value::String(token.to_string());
+        return;
+    }
+    match json {
+        Value::Object(object) => object.values_mut().for_each(|value| redact_private(value, token)),
+        Value::Array(array) => array.iter_mut().for_each(|value| redact_private(value, token)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_private() {
+        let mut json = json!({
+            "type": "Record",
+            "owner": { "value": "aleo1...", "visibility": "private" },
+            "data": {
+                "amount": { "value": "5u64", "visibility": "private" },
+                "memo": { "value": "1field", "visibility": "public" },
+                "kind": { "value": "0u8", "visibility": "constant" },
+            },
+            "inputs": [{ "type": "private", "id": "1field", "value": "ciphertext1..." }],
+        });
+        redact_private(&mut json, "***");
+        assert_eq!(json["owner"], json!({ "value": "***", "visibility": "private" }));
+        assert_eq!(json["data"]["amount"]["value"], "***");
+        assert_eq!(json["data"]["memo"]["value"], "1field");
+        assert_eq!(json["data"]["kind"]["value"], "0u8");
+        assert_eq!(json["inputs"][0], json!({ "type": "private", "id": "1field", "value": "***" }));
+    }
+}
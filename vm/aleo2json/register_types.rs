@@ -0,0 +1,301 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{
+    network::Network,
+    program::{PlaintextType, ProgramID, Register, RegisterType, ValueType},
+};
+use snarkvm_synthesizer_program::{CastType, Instruction, InstructionTrait, Operand, Program};
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The opcodes whose destination is a boolean.
+const BOOLEAN_OPCODES: &[&str] = &["is.eq", "is.neq", "gt", "gte", "lt", "lte", "sign.verify"];
+
+/// The opcodes whose destination has the type of their first operand.
+const FIRST_OPERAND_OPCODES: &[&str] = &[
+    "abs", "abs.w", "add", "add.w", "and", "div", "div.w", "double", "inv", "max", "min", "mod", "mul", "mul.w", "nand",
+    "neg", "nor", "not", "or", "pow", "pow.w", "rem", "rem.w", "shl", "shl.w", "shr", "shr.w", "square", "sqrt", "sub",
+    "sub.w", "xor",
+];
+
+/// Returns the register type of the given value type, i.e. `u64` for `u64.public`.
+fn value_type_to_string<N: Network>(value_type: &ValueType<N>) -> String {
+    match value_type {
+        ValueType::Constant(plaintext_type)
+        | ValueType::Public(plaintext_type)
+        | ValueType::Private(plaintext_type) => plaintext_type.to_string(),
+        ValueType::Record(name) => format!("{name}.record"),
+        ValueType::ExternalRecord(locator) => format!("{locator}.record"),
+        ValueType::Future(locator) => format!("{locator}.future"),
+    }
+}
+
+/// Returns the type of the given operand in the given register types, if it is known.
+fn operand_type<N: Network>(operand: &Operand<N>, types: &HashMap<Register<N>, String>) -> Option<String> {
+    match operand {
+        Operand::Literal(literal) => Some(literal.to_type().to_string()),
+        Operand::Register(register) => types.get(register).cloned(),
+        Operand::Signer | Operand::Caller => Some("address".to_string()),
+        Operand::BlockHeight => Some("u32".to_string()),
+        Operand::ProgramID(_) => None,
+    }
+}
+
+/// Returns the type of the destination of the given instruction in the given register types, if it can be inferred.
+/// The type is inferred from the declared type of a `cast`, `hash`, or `commit`, the scheduled finalize of an `async`,
+/// or the type of the operands of an arithmetic, logical, or comparison operation.
+fn destination_type<N: Network>(
+    program_id: &ProgramID<N>,
+    instruction: &Instruction<N>,
+    types: &HashMap<Register<N>, String>,
+) -> Option<String> {
+    let opcode = instruction.opcode().to_string();
+    let cast_type = |cast_type: &CastType<N>| match cast_type {
+        CastType::GroupXCoordinate | CastType::GroupYCoordinate => "field".to_string(),
+        cast_type => cast_type.to_string(),
+    };
+    match instruction {
+        Instruction::Cast(cast) => Some(cast_type(cast.cast_type())),
+        Instruction::CastLossy(cast) => Some(cast_type(cast.cast_type())),
+        Instruction::Async(async_) => Some(format!("{program_id}/{}.future", async_.function_name())),
+        // A hash or a commitment declares the type of its destination, i.e. `hash.bhp256 r0 into r1 as field`.
+        _ if opcode.starts_with("hash") || opcode.starts_with("commit") => {
+            instruction.to_string().trim_end_matches(';').rsplit_once(" as ").map(|(_, type_)| type_.to_string())
+        }
+        _ if BOOLEAN_OPCODES.contains(&opcode.as_str()) => Some("boolean".to_string()),
+        _ if FIRST_OPERAND_OPCODES.contains(&opcode.as_str()) => operand_type(instruction.operands().first()?, types),
+        // The branches of a ternary have the same type.
+        _ if opcode == "ternary" => operand_type(instruction.operands().get(1)?, types),
+        _ => None,
+    }
+}
+
+/// Adds the type of the destination register of each instruction under `"destination_type"`, i.e.
+/// `"destination_type": "u64"`, or `null` if it cannot be inferred (i.e. of a `call`, or of an accessor operand).
+/// The types of the registers are seeded with the declared types of the inputs of each function and closure.
+pub fn add_destination_types<N: Network>(json: &mut Value, program: &Program<N>) {
+    let functions = program.functions().iter().map(|(name, function)| {
+        let inputs =
+            function.inputs().iter().map(|input| (input.register().clone(), value_type_to_string(input.value_type())));
+        ("functions", name, inputs.collect::<HashMap<_, _>>(), function.instructions())
+    });
+    let closures = program.closures().iter().map(|(name, closure)| {
+        let inputs = closure.inputs().iter().map(|input| (input.register().clone(), input.register_type().to_string()));
+        ("closures", name, inputs.collect::<HashMap<_, _>>(), closure.instructions())
+    });
+    for (section, name, mut types, instructions) in functions.chain(closures) {
+        let Some(function_json) = json.get_mut(section).and_then(|section| section.get_mut(name.to_string())) else {
+            continue;
+        };
+        for (index, instruction) in instructions.iter().enumerate() {
+            let destinations = instruction.destinations();
+            // An instruction with several destinations (i.e. a `call`) has no single type.
+            let type_ = match destinations.as_slice() {
+                [destination] => destination_type(program.id(), instruction, &types).map(|type_| (destination, type_)),
+                _ => None,
+            };
+            if let Some((destination, type_)) = &type_ {
+                types.insert((*destination).clone(), type_.clone());
+            }
+            function_json["instructions"][index]["destination_type"] = json!(type_.map(|(_, type_)| type_));
+        }
+    }
+}
+
+/// Returns the program defining the given plaintext type, i.e. `"self"` for a struct, or `None` for a literal type,
+/// which is built in. An array is defined where its elements are.
+fn plaintext_defined_in<N: Network>(plaintext_type: &PlaintextType<N>) -> Option<String> {
+    match plaintext_type {
+        PlaintextType::Literal(_) => None,
+        PlaintextType::Struct(_) => Some("self".to_string()),
+        PlaintextType::Array(array_type) => plaintext_defined_in(array_type.base_element_type()),
+    }
+}
+
+/// Returns the given program, or `"self"` if it is the program of the given ID.
+fn program_or_self<N: Network>(program_id: &ProgramID<N>, defined_in: &ProgramID<N>) -> String {
+    match defined_in == program_id {
+        true => "self".to_string(),
+        false => defined_in.to_string(),
+    }
+}
+
+/// Returns the program defining the given register type of a closure, as `plaintext_defined_in` does.
+fn register_type_defined_in<N: Network>(program_id: &ProgramID<N>, register_type: &RegisterType<N>) -> Option<String> {
+    match register_type {
+        RegisterType::Plaintext(plaintext_type) => plaintext_defined_in(plaintext_type),
+        RegisterType::Record(_) => Some("self".to_string()),
+        RegisterType::ExternalRecord(locator) | RegisterType::Future(locator) => {
+            Some(program_or_self(program_id, locator.program_id()))
+        }
+    }
+}
+
+/// Returns the program defining the given value type of a function, as `plaintext_defined_in` does.
+fn value_type_defined_in<N: Network>(program_id: &ProgramID<N>, value_type: &ValueType<N>) -> Option<String> {
+    match value_type {
+        ValueType::Constant(plaintext_type)
+        | ValueType::Public(plaintext_type)
+        | ValueType::Private(plaintext_type) => plaintext_defined_in(plaintext_type),
+        ValueType::Record(_) => Some("self".to_string()),
+        ValueType::ExternalRecord(locator) | ValueType::Future(locator) => {
+            Some(program_or_self(program_id, locator.program_id()))
+        }
+    }
+}
+
+/// Adds the program defining the type of each input and output of each function and closure under `"defined_in"`,
+/// i.e. `"defined_in": "credits.aleo"` for `credits.aleo/credits.record`, and `"defined_in": "self"` for a struct,
+/// a record, or a future of the program itself. A literal type is built in, so it is defined in `null`.
+pub fn add_type_provenance<N: Network>(json: &mut Value, program: &Program<N>) {
+    let program_id = program.id();
+    let closures = program.closures().iter().map(|(name, closure)| {
+        let inputs = closure.inputs().iter().map(|input| register_type_defined_in(program_id, input.register_type()));
+        let outputs =
+            closure.outputs().iter().map(|output| register_type_defined_in(program_id, output.register_type()));
+        ("closures", name, inputs.collect::<Vec<_>>(), outputs.collect::<Vec<_>>())
+    });
+    let functions = program.functions().iter().map(|(name, function)| {
+        let inputs = function.inputs().iter().map(|input| value_type_defined_in(program_id, input.value_type()));
+        let outputs = function.outputs().iter().map(|output| value_type_defined_in(program_id, output.value_type()));
+        ("functions", name, inputs.collect::<Vec<_>>(), outputs.collect::<Vec<_>>())
+    });
+    for (section, name, inputs, outputs) in closures.chain(functions) {
+        let Some(function_json) = json.get_mut(section).and_then(|section| section.get_mut(name.to_string())) else {
+            continue;
+        };
+        for (key, defined_in) in [("inputs", inputs), ("outputs", outputs)] {
+            for (index, defined_in) in defined_in.into_iter().enumerate() {
+                function_json[key][index]["defined_in"] = json!(defined_in);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_add_destination_types() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program types.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+closure twice:
+    input r0 as u8;
+    add r0 r0 into r1;
+    output r1 as u8;
+
+function main:
+    input r0 as u64.public;
+    input r1 as point.private;
+    add r0 1u64 into r2;
+    cast 1field 2field into r3 as point;
+    is.eq r2 r0 into r4;
+    hash.bhp256 r0 into r5 as field;
+    add r1.x r1.y into r6;
+    call twice 1u8 into r7;
+    ternary r4 r2 r0 into r8;
+    async main r2 into r9;
+    output r9 as types.aleo/main.future;
+
+finalize main:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_destination_types(&mut json, &program);
+        let types = |section: &str, name: &str| {
+            let instructions = json[section][name]["instructions"].as_array().unwrap();
+            instructions.iter().map(|instruction| instruction["destination_type"].clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(types("closures", "twice"), [json!("u8")]);
+        assert_eq!(types("functions", "main"), [
+            json!("u64"),
+            json!("point"),
+            json!("boolean"),
+            json!("field"),
+            // The type of an accessor, and of the output of a call, are not inferred.
+            json!(null),
+            json!(null),
+            json!("u64"),
+            json!("types.aleo/main.future"),
+        ]);
+    }
+
+    #[test]
+    fn test_add_type_provenance() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+
+program provenance.aleo;
+
+struct point:
+    x as u8;
+    y as u8;
+
+record coin:
+    owner as address.private;
+    amount as u64.private;
+
+closure scale:
+    input r0 as point;
+    input r1 as u8;
+    cast r0.x r0.y into r2 as point;
+    output r2 as point;
+
+function spend:
+    input r0 as credits.aleo/credits.record;
+    input r1 as [point; 2u32].private;
+    input r2 as coin.record;
+    input r3 as u64.public;
+    async spend r3 into r4;
+    output r4 as provenance.aleo/spend.future;
+
+finalize spend:
+    input r0 as u64.public;
+    gt r0 2u64 into r1;
+",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        add_type_provenance(&mut json, &program);
+        let defined_in = |section: &str, name: &str, key: &str| {
+            let values = json[section][name][key].as_array().unwrap();
+            values.iter().map(|value| value["defined_in"].clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(defined_in("closures", "scale", "inputs"), [json!("self"), json!(null)]);
+        assert_eq!(defined_in("closures", "scale", "outputs"), [json!("self")]);
+        assert_eq!(defined_in("functions", "spend", "inputs"), [
+            json!("credits.aleo"),
+            json!("self"),
+            json!("self"),
+            json!(null),
+        ]);
+        assert_eq!(defined_in("functions", "spend", "outputs"), [json!("self")]);
+    }
+}
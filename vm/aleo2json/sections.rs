@@ -0,0 +1,177 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::Aleo2JsonError;
+
+use serde_json::Value;
+
+/// The top-level sections of the program JSON that can be selected with `--only`.
+pub const SECTIONS: &[&str] = &[
+    "identifiers",
+    "imports",
+    "dependencies",
+    "mappings",
+    "structs",
+    "records",
+    "record_types",
+    "closures",
+    "functions",
+    "order",
+    "external_calls",
+    "credit_interactions",
+    "mapping_index",
+    "violations",
+    "stats",
+    "resolved_imports",
+];
+
+/// Ensures each of the given section names is one of the valid `SECTIONS`.
+pub fn check_sections(sections: &[String]) -> Result<(), Aleo2JsonError> {
+    match sections.iter().find(|section| !SECTIONS.contains(&section.as_str())) {
+        Some(section) => Err(Aleo2JsonError::UnknownSection { section: section.clone(), valid: SECTIONS.join(", ") }),
+        None => Ok(()),
+    }
+}
+
+/// Keeps only the given top-level sections of the given JSON, always retaining `"id"`.
+pub fn only_sections(json: &mut Value, sections: &[String]) -> Result<(), Aleo2JsonError> {
+    check_sections(sections)?;
+    if let Some(object) = json.as_object_mut() {
+        object.retain(|key, _| key == "id" || sections.contains(key));
+    }
+    Ok(())
+}
+
+/// Parses the given renames of top-level sections, i.e. `functions=procedures`, into pairs.
+/// Ensures each renamed key is one of the valid `SECTIONS`, and that no two keys end up with the same name.
+pub fn check_renames(renames: &[String]) -> Result<Vec<(String, String)>, Aleo2JsonError> {
+    let mut pairs = Vec::with_capacity(renames.len());
+    for rename in renames {
+        let (from, to) = match rename.split_once('=') {
+            Some((from, to)) if !from.is_empty() && !to.is_empty() => (from.to_string(), to.to_string()),
+            _ => return Err(Aleo2JsonError::InvalidRename { rename: rename.clone() }),
+        };
+        check_sections(std::slice::from_ref(&from))?;
+        pairs.push((from, to));
+    }
+
+    // The keys of the program JSON after renaming, which must remain unique.
+    let mut keys = vec!["type".to_string(), "id".to_string()];
+    for section in SECTIONS {
+        let renamed = pairs.iter().filter(|(from, _)| from == section).map(|(_, to)| to.clone()).collect::<Vec<_>>();
+        keys.extend(if renamed.is_empty() { vec![section.to_string()] } else { renamed });
+    }
+    for (from, to) in &pairs {
+        if keys.iter().filter(|key| *key == to).count() > 1 {
+            return Err(Aleo2JsonError::RenameCollision { from: from.clone(), to: to.clone() });
+        }
+    }
+    Ok(pairs)
+}
+
+/// Removes each top-level section of the given JSON that is an empty array or object, i.e. `"structs": {}`.
+/// By default every section is emitted, even if empty, so that consumers can rely on its key being present;
+/// pruning trades that guarantee for a leaner output.
+pub fn prune_empty_sections(json: &mut Value) {
+    if let Some(object) = json.as_object_mut() {
+        object.retain(|_, value| match value {
+            Value::Array(array) => !array.is_empty(),
+            Value::Object(object) => !object.is_empty(),
+            _ => true,
+        });
+    }
+}
+
+/// Renames the top-level sections of the given JSON, i.e. `functions=procedures`.
+pub fn rename_sections(json: &mut Value, renames: &[String]) -> Result<(), Aleo2JsonError> {
+    let pairs = check_renames(renames)?;
+    if let Some(object) = json.as_object_mut() {
+        // Remove every renamed section first, so that sections can be swapped.
+        let values = pairs.iter().map(|(from, _)| object.remove(from)).collect::<Vec<_>>();
+        for ((_, to), value) in pairs.into_iter().zip(values) {
+            if let Some(value) = value {
+                object.insert(to, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_only_sections() {
+        let program = parse_program::<CurrentNetwork>(
+            "program sections.aleo;\n\nmapping balances:\n    key as address.public;\n    value as u64.public;\n",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        only_sections(&mut json, &["mappings".to_string(), "structs".to_string()]).unwrap();
+        let keys = json.as_object().unwrap().keys().collect::<Vec<_>>();
+        assert_eq!(keys.len(), 3);
+        assert!(["id", "mappings", "structs"].iter().all(|key| keys.contains(&&key.to_string())));
+        assert!(json["mappings"]["balances"].is_object());
+
+        let error = only_sections(&mut json, &["mapping".to_string()]).unwrap_err();
+        assert_eq!(error.kind(), "unknown_section");
+        assert!(error.to_string().contains("identifiers, imports, dependencies, mappings"));
+    }
+
+    #[test]
+    fn test_prune_empty_sections() {
+        let program = parse_program::<CurrentNetwork>(
+            "program pruned.aleo;\n\nmapping balances:\n    key as address.public;\n    value as u64.public;\n",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        assert_eq!(json["structs"], serde_json::json!({}));
+        prune_empty_sections(&mut json);
+        assert!(json.get("structs").is_none());
+        assert!(json.get("functions").is_none());
+        assert!(json["mappings"]["balances"].is_object());
+        assert!(json["id"].is_object());
+    }
+
+    #[test]
+    fn test_rename_sections() {
+        let program = parse_program::<CurrentNetwork>(
+            "program renames.aleo;\n\nrecord token:\n    owner as address.private;\n\nfunction main:\n    input r0 as u8.public;\n",
+        )
+        .unwrap();
+
+        let mut json = program.to_json();
+        rename_sections(&mut json, &["functions=procedures".to_string()]).unwrap();
+        assert!(json.get("functions").is_none());
+        assert!(json["procedures"]["main"].is_object());
+
+        // Swapping two sections does not collide.
+        rename_sections(&mut json, &["structs=records".to_string(), "records=structs".to_string()]).unwrap();
+        assert!(json["structs"]["token"].is_object());
+        assert_eq!(json["records"], serde_json::json!({}));
+
+        let error = check_renames(&["functions".to_string()]).unwrap_err();
+        assert_eq!(error.kind(), "invalid_rename");
+        let error = check_renames(&["function=procedures".to_string()]).unwrap_err();
+        assert_eq!(error.kind(), "unknown_section");
+        let error = check_renames(&["functions=closures".to_string()]).unwrap_err();
+        assert_eq!(error.kind(), "rename_collision");
+        let error = check_renames(&["functions=id".to_string()]).unwrap_err();
+        assert_eq!(error.kind(), "rename_collision");
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{error_to_json, Aleo2JsonError};
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde_json::json;
+use std::io::{BufRead, Read, Write};
+
+/// The framing of the requests read by `--serve`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Framing {
+    /// Each request is a line holding the source as a JSON string, i.e. `"program main.aleo;\n..."`.
+    #[default]
+    Lines,
+    /// Each request is a line holding the length of the source in bytes, followed by the source itself.
+    Length,
+}
+
+/// Converts each request read from the reader, and writes one JSON response per request to the writer, i.e.
+/// `{ "ok": true, "result": { ... } }` or `{ "ok": false, "error": { ... } }`, until the end of the input.
+/// A request that fails to convert, or is malformed, is answered with an error, and the loop continues.
+pub fn serve<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    framing: Framing,
+    max_size: u64,
+    tab_width: usize,
+    mut convert: impl FnMut(&str) -> Result<serde_json::Value>,
+) -> Result<()> {
+    while let Some(request) = read_request(&mut reader, framing, max_size)? {
+        let response = match request {
+            Ok(source) => match convert(&source) {
+                Ok(json) => json!({ "ok": true, "result": json }),
+                Err(error) => error_to_json(&error, Some(&source), tab_width),
+            },
+            Err(error) => error_to_json(&error, None, tab_width),
+        };
+        // Write each response on a line of its own, and flush it, so that the client can read it right away.
+        writeln!(writer, "{response}")?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// The prompt of `--repl` for the first line of a program, and for each following line.
+const REPL_PROMPTS: (&str, &str) = ("aleo2json> ", "...> ");
+
+/// Reads a program at a time from the reader, prompting for each line, and writes its converted output, or its error,
+/// to the writer, until the end of the input. A program ends at a blank line, or at the end of the input.
+pub fn repl<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    mut convert: impl FnMut(&str) -> Result<String>,
+) -> Result<()> {
+    loop {
+        let mut source = String::new();
+        let at_end = loop {
+            let prompt = if source.is_empty() { REPL_PROMPTS.0 } else { REPL_PROMPTS.1 };
+            write!(writer, "{prompt}")?;
+            writer.flush()?;
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                break true;
+            }
+            match line.trim().is_empty() {
+                // Leading blank lines do not end a program.
+                true if source.is_empty() => continue,
+                true => break false,
+                false => source.push_str(&line),
+            }
+        };
+        if source.is_empty() {
+            // End the line of the last prompt.
+            writeln!(writer)?;
+        } else {
+            match convert(&source) {
+                Ok(output) => writeln!(writer, "{output}")?,
+                Err(error) => writeln!(writer, "{error:#}")?,
+            }
+        }
+        if at_end {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads the next request from the reader, returning `None` at the end of the input.
+/// The inner result is an error if the request is malformed, and the outer result if the input fails.
+fn read_request<R: BufRead>(reader: &mut R, framing: Framing, max_size: u64) -> Result<Option<Result<String>>> {
+    // Read the next line, skipping blank lines between requests.
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break;
+        }
+    }
+
+    match framing {
+        Framing::Lines => Ok(Some(
+            serde_json::from_str::<String>(line.trim())
+                .map_err(|error| anyhow!("Invalid request, expected the source as a JSON string: {error}")),
+        )),
+        Framing::Length => {
+            let Ok(length) = line.trim().parse::<u64>() else {
+                return Ok(Some(Err(anyhow!("Invalid frame length '{}'", line.trim()))));
+            };
+            // Skip oversized frames, so that the next request can still be read.
+            if length > max_size {
+                std::io::copy(&mut reader.take(length), &mut std::io::sink())?;
+                return Ok(Some(Err(Aleo2JsonError::InputTooLarge { size: length, max_size }.into())));
+            }
+            let mut bytes = Vec::new();
+            reader.take(length).read_to_end(&mut bytes)?;
+            if (bytes.len() as u64) < length {
+                return Ok(Some(Err(anyhow!("Truncated frame of {} bytes, expected {length} bytes", bytes.len()))));
+            }
+            Ok(Some(String::from_utf8(bytes).map_err(|error| anyhow!("Invalid frame: {error}"))))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, ConvertOptions};
+
+    const PROGRAM: &str = "program serve.aleo;\n\nfunction main:\n    input r0 as u8.private;\n";
+
+    /// Serves the given input, returning the responses.
+    fn serve_input(input: &str, framing: Framing, max_size: u64) -> Vec<serde_json::Value> {
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output, framing, max_size, 1, |source| {
+            convert_program(source, &ConvertOptions::new())
+        })
+        .unwrap();
+        String::from_utf8(output).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_serve_lines() {
+        let input = format!("{}\n\n\"program broken.aleo\"\nnot json\n{}\n", json!(PROGRAM), json!(PROGRAM));
+        let responses = serve_input(&input, Framing::Lines, 1024);
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["ok"], true);
+        assert_eq!(responses[0]["result"]["type"], "ProgramCore");
+        // Errors are answered, and do not end the loop.
+        assert_eq!(responses[1]["ok"], false);
+        assert_eq!(responses[2]["error"]["kind"], "other");
+        assert_eq!(responses[3]["ok"], true);
+    }
+
+    #[test]
+    fn test_repl() {
+        // A blank line ends a program, so that the program is pasted without the blank lines between its sections.
+        let program = PROGRAM.replace("\n\n", "\n");
+        let input = format!("\n{program}\nprogram broken.aleo\n\n{program}");
+        let mut output = Vec::new();
+        repl(input.as_bytes(), &mut output, |source| Ok(convert_program(source, &ConvertOptions::new())?.to_string()))
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        // The first program, an error, and the last program, which ends at the end of the input.
+        assert!(lines[0].starts_with("aleo2json> aleo2json> ...> ...> ...> {"));
+        assert!(lines[1].starts_with("aleo2json> ...> ") && !lines[1].ends_with('}'));
+        assert!(lines.last().unwrap().ends_with('}'));
+        assert_eq!(lines.iter().filter(|line| line.ends_with('}')).count(), 2);
+    }
+
+    #[test]
+    fn test_serve_length() {
+        let input = format!("{}\n{PROGRAM}x\n{}\n{PROGRAM}", PROGRAM.len(), PROGRAM.len());
+        let responses = serve_input(&input, Framing::Length, 1024);
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["ok"], true);
+        assert_eq!(responses[1]["error"]["message"], "Invalid frame length 'x'");
+        assert_eq!(responses[2]["ok"], true);
+
+        // Oversized frames are skipped.
+        let input = format!("{}\n{PROGRAM}{}\n{PROGRAM}", PROGRAM.len(), PROGRAM.len());
+        let responses = serve_input(&input, Framing::Length, 8);
+        assert_eq!(responses.len(), 2);
+        assert!(responses.iter().all(|response| response["error"]["kind"] == "input_too_large"));
+    }
+}
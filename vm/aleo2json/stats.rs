@@ -0,0 +1,199 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{to_json_string, DataflowEntry},
+    console::network::Network,
+};
+use snarkvm_synthesizer_program::{Function, Program};
+
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+/// The columns of the statistics of each program in `--summary-csv`, in order, after the file.
+pub const STATS_COLUMNS: &[&str] = &[
+    "mappings",
+    "structs",
+    "records",
+    "closures",
+    "functions",
+    "instructions",
+    "functions_with_finalize",
+    "finalize_commands",
+    "max_register_count",
+];
+
+/// Returns the given field of a CSV row, quoted if it contains a comma, a quote, or a line break (RFC 4180).
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+/// Returns the statistics of the given programs as CSV, with a header row, and one row per program, i.e.
+/// `file,mappings,...,error`, given the JSON of each program by file. The statistics of a program that failed to
+/// convert are empty, and its error is in the last column.
+pub fn stats_csv<'a>(programs: impl Iterator<Item = (&'a str, &'a Value)>) -> String {
+    let header = std::iter::once("file").chain(STATS_COLUMNS.iter().copied()).chain(std::iter::once("error"));
+    let mut rows = vec![header.collect::<Vec<_>>().join(",")];
+    for (file, json) in programs {
+        let metrics = STATS_COLUMNS.iter().map(|column| match &json["stats"][column] {
+            Value::Null => String::new(),
+            value => to_json_string(value, false),
+        });
+        let error = json["error"].as_str().unwrap_or_default();
+        let row = std::iter::once(file.to_string()).chain(metrics).chain(std::iter::once(error.to_string()));
+        rows.push(row.map(|field| csv_field(&field)).collect::<Vec<_>>().join(","));
+    }
+    rows.join("\n")
+}
+
+/// Returns the number of distinct registers of the given function, i.e. its inputs, and the registers its
+/// instructions read and write. A register access (i.e. `r0.owner`) counts as its register (i.e. `r0`).
+/// The registers of its finalize logic are distinct from its own, and are not counted.
+fn register_count<N: Network>(function: &Function<N>) -> usize {
+    let mut registers = function.inputs().iter().map(|input| input.register().locator()).collect::<HashSet<_>>();
+    for (index, instruction) in function.instructions().iter().enumerate() {
+        let entry = DataflowEntry::from_instruction(index, instruction);
+        registers.extend(entry.reads().iter().chain(entry.writes()).map(|register| register.locator()));
+    }
+    registers.len()
+}
+
+/// Returns the statistics of the given program, i.e. `{ "functions": 2, "instructions": 7, ... }`.
+/// The counts always cover the entire program, regardless of the options that truncate the output.
+/// If `per_function` is set, the statistics of each function are included under `"per_function"`, with its number
+/// of distinct registers under `"register_count"`, of which the maximum is `"max_register_count"`.
+pub fn program_stats<N: Network>(program: &Program<N>, per_function: bool) -> Value {
+    let closure_instructions: usize = program.closures().values().map(|closure| closure.instructions().len()).sum();
+
+    let mut function_instructions = 0;
+    let mut finalize_commands = 0;
+    let mut functions_with_finalize = 0;
+    let mut max_register_count = 0;
+    let mut j_functions = Map::new();
+    for (name, function) in program.functions() {
+        let num_commands = function.finalize_logic().map(|finalize| finalize.commands().len());
+        function_instructions += function.instructions().len();
+        finalize_commands += num_commands.unwrap_or_default();
+        functions_with_finalize += usize::from(num_commands.is_some());
+        let num_registers = register_count(function);
+        max_register_count = max_register_count.max(num_registers);
+
+        j_functions.insert(
+            name.to_string(),
+            json!({
+                "instructions": function.instructions().len(),
+                "has_finalize": num_commands.is_some(),
+                "finalize_commands": num_commands.unwrap_or_default(),
+                "register_count": num_registers,
+            }),
+        );
+    }
+
+    let mut stats = json!({
+        "mappings": program.mappings().len(),
+        "structs": program.structs().len(),
+        "records": program.records().len(),
+        "closures": program.closures().len(),
+        "functions": program.functions().len(),
+        "instructions": closure_instructions + function_instructions,
+        "functions_with_finalize": functions_with_finalize,
+        "finalize_commands": finalize_commands,
+        "max_register_count": max_register_count,
+    });
+    if per_function {
+        stats["per_function"] = Value::Object(j_functions);
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    #[test]
+    fn test_stats_csv() {
+        let stats = json!({ "stats": {
+            "mappings": 1, "structs": 0, "records": 2, "closures": 0, "functions": 3, "instructions": 9,
+            "functions_with_finalize": 1, "finalize_commands": 4, "max_register_count": 5,
+        } });
+        let error = json!({ "error": "Parser error: \"frobnicate\", at line 4" });
+        let csv = stats_csv([("a.aleo", &stats), ("dir/b,c.aleo", &error)].into_iter());
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "file,mappings,structs,records,closures,functions,instructions,functions_with_finalize,finalize_commands,\
+             max_register_count,error"
+        );
+        assert_eq!(lines[1], "a.aleo,1,0,2,0,3,9,1,4,5,");
+        assert_eq!(lines[2], r#""dir/b,c.aleo",,,,,,,,,,"Parser error: ""frobnicate"", at line 4""#);
+    }
+
+    #[test]
+    fn test_program_stats() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program stats.aleo;
+
+mapping totals:
+    key as address.public;
+    value as u64.public;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function deposit:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    async deposit self.caller r1 into r2;
+    output r2 as stats.aleo/deposit.future;
+
+finalize deposit:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use totals[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into totals[r0];
+
+function noop:
+    input r0 as u8.private;
+",
+        )
+        .unwrap();
+
+        let stats = program_stats(&program, false);
+        assert_eq!(stats["functions"], 2);
+        assert_eq!(stats["instructions"], 3);
+        assert_eq!(stats["functions_with_finalize"], 1);
+        assert_eq!(stats["finalize_commands"], 3);
+        // The registers of `deposit` are `r0` to `r2`, whatever the registers of its finalize logic.
+        assert_eq!(stats["max_register_count"], 3);
+        assert!(stats.get("per_function").is_none());
+
+        let stats = program_stats(&program, true);
+        assert_eq!(
+            stats["per_function"]["deposit"],
+            json!({ "instructions": 2, "has_finalize": true, "finalize_commands": 3, "register_count": 3 })
+        );
+        assert_eq!(
+            stats["per_function"]["noop"],
+            json!({ "instructions": 0, "has_finalize": false, "finalize_commands": 0, "register_count": 1 })
+        );
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+use std::error::Error;
+use tera::{Context, Tera};
+
+/// Returns the given Tera error, followed by each of its causes, i.e. the line and column of a syntax error.
+fn describe_error(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+/// Renders the given Tera template, named after its file, with the given JSON as its context, i.e.
+/// `{{ id.name }}` or `{% for name, function in functions %}`. Each top-level key of the JSON is a variable.
+/// A syntax error is reported with its line and column.
+pub fn render_template(name: &str, template: &str, json: &Value) -> Result<String> {
+    if !json.is_object() {
+        bail!("The template '{name}' can only be rendered against a JSON object");
+    }
+    let mut tera = Tera::default();
+    tera.add_raw_template(name, template)
+        .map_err(|error| anyhow!("Invalid template: {}", describe_error(&error)))?;
+    let context = Context::from_value(json.clone())?;
+    tera.render(name, &context).map_err(|error| anyhow!("Invalid template: {}", describe_error(&error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_template() {
+        let json = json!({ "id": { "name": "token" }, "functions": { "mint": {}, "burn": {} } });
+        let template = "# {{ id.name }}\n{% for name, _ in functions %}- {{ name }}\n{% endfor %}";
+        assert_eq!(render_template("docs.md", template, &json).unwrap(), "# token\n- mint\n- burn\n");
+
+        // A syntax error is reported with its line.
+        let error = render_template("docs.md", "# {{ id.name }}\n{% for %}", &json).unwrap_err();
+        assert!(error.to_string().contains("2:"), "{error}");
+        assert!(render_template("docs.md", "{{ missing }}", &json).is_err());
+        assert!(render_template("docs.md", "", &json!([])).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The TypeScript declarations of the JSON of a program, covering the program, its functions, and its instructions.
+/// The nested types (i.e. of a literal or a value type) are left as `unknown`.
+/// Keep these declarations in sync with the keys emitted in the JSON.
+pub const TS_TYPES: &str = r#"// The TypeScript declarations of the JSON emitted by aleo2json.
+
+export type Identifier = string;
+
+export interface ProgramID {
+  type: "ProgramID";
+  name: Identifier;
+  network: Identifier;
+}
+
+export interface ProgramCore {
+  type: "ProgramCore";
+  id: ProgramID;
+  identifiers: Record<Identifier, { type: "ProgramDefinition"; definition: string }>;
+  imports: Record<string, { type: "Import"; program_id: ProgramID }>;
+  mappings: Record<Identifier, unknown>;
+  structs: Record<Identifier, unknown>;
+  records: Record<Identifier, unknown>;
+  closures: Record<Identifier, ClosureCore>;
+  functions: Record<Identifier, FunctionCore>;
+  dependencies: string[];
+  external_calls: { from: Identifier; program: string; function: Identifier }[];
+  credit_interactions: { function: Identifier; operation: Identifier }[];
+  mapping_index: { mapping: Identifier; readers: Identifier[]; writers: Identifier[] }[];
+  record_types: RecordType[];
+  order: Identifier[];
+  network: string;
+  edition?: number;
+  schema_version?: number;
+  program_hash?: string;
+  fingerprint?: string;
+  total_functions?: number;
+  bytes_len?: number;
+  violations?: unknown[];
+  stats?: unknown;
+  resolved_imports?: Record<string, unknown>;
+  parser_consumed?: number;
+  parser_remainder?: string | null;
+  parse_error?: {
+    kind: string;
+    message: string;
+    offset: number | null;
+    line: number | null;
+    col: number | null;
+  } | null;
+}
+
+export interface RecordType {
+  name: Identifier;
+  owner: "public" | "private";
+  entries: { name: Identifier; type: string; visibility: "constant" | "public" | "private" }[];
+}
+
+export interface ClosureCore {
+  type: "ClosureCore";
+  name: Identifier;
+  is_entrypoint: false;
+  inputs: Input[];
+  instructions: Instruction[];
+  outputs: Output[];
+  callers: Identifier[];
+  unused_inputs: string[];
+  assertions: Assertion[];
+  body_hash?: string;
+  cost_estimate?: number;
+  bytes_len?: number;
+  _ptr?: string;
+}
+
+export interface FunctionCore {
+  type: "FunctionCore";
+  name: Identifier;
+  is_entrypoint: true;
+  inputs: Input[];
+  instructions: Instruction[];
+  outputs: Output[];
+  finalize_logic: FinalizeCore | null;
+  mapping_effects: Record<Identifier, "read" | "write" | "read_write">;
+  purity: "pure" | "read" | "write";
+  unused_inputs: string[];
+  assertions: Assertion[];
+  body_hash?: string;
+  cost_estimate?: number;
+  bytes_len?: number;
+  _ptr?: string;
+}
+
+export interface Assertion {
+  type: "Assertion";
+  index: number;
+  opcode: "assert.eq" | "assert.neq";
+  operands: Operand[];
+  str: string;
+}
+
+export interface FinalizeCore {
+  type: "FinalizeCore";
+  name: Identifier;
+  function: Identifier;
+  inputs: unknown[];
+  commands: Command[];
+  num_writes: number;
+  positions: Record<Identifier, number>;
+}
+
+export interface Input {
+  type: "Input";
+  register: unknown;
+  value_type: unknown;
+  visibility?: "constant" | "public" | "private" | "record" | "external_record" | "future";
+  str: string;
+  defined_in: string | null;
+}
+
+export interface Output {
+  type: "Output";
+  operand: Operand | string;
+  value_type: unknown;
+  str: string;
+  defined_in: string | null;
+  future?: { target: string; finalize: string | null; async: number | null };
+}
+
+export interface Instruction {
+  type: "Instruction";
+  vtype: string;
+  value: {
+    type: string;
+    operands?: Operand[] | string[];
+    fields?: { field: Identifier; operand: Operand | string }[];
+    [key: string]: unknown;
+  };
+  destination: string | string[] | null;
+  destination_type: string | null;
+  str: string;
+  index: number;
+  uid: string;
+  opcode: string;
+  arity: number;
+  async?: { target: string; arguments: string[]; future: string };
+  const?: boolean;
+  crypto?: { op: "hash" | "hash_many" | "commit"; algorithm: string; output_type: string | null };
+  line?: number;
+  _ptr?: string;
+}
+
+export interface Command {
+  type: "Command";
+  vtype: string;
+  value: unknown;
+  str: string;
+  opcode: string;
+  await?: { future: string; target: string | null; input: number | null };
+  random?: { op: "rand"; algorithm: "chacha"; seeds: string[]; output_type: string };
+  control_flow?: { to_position: Identifier } | { position: Identifier };
+}
+
+export interface Operand {
+  type: "Operand";
+  kind: "register" | "accessor" | "literal" | "identifier" | "builtin";
+  vtype: string;
+  value: unknown;
+  str: string;
+  name?: "self.signer" | "self.caller" | "block.height";
+  raw?: string;
+  access?: { register: string; path: (Identifier | number)[] };
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, ConvertOptions};
+
+    use serde_json::Value;
+
+    /// Returns the body of the given interface in the declarations.
+    fn interface(name: &str) -> &'static str {
+        let start = TS_TYPES.find(&format!("export interface {name} {{")).unwrap();
+        let end = start + TS_TYPES[start..].find("\n}").unwrap();
+        &TS_TYPES[start..end]
+    }
+
+    /// Ensures each key of the given JSON object is declared in the given interface.
+    fn check_keys(name: &str, json: &Value) {
+        let body = interface(name);
+        for key in json.as_object().unwrap().keys() {
+            assert!(
+                body.contains(&format!("\n  {key}: ")) || body.contains(&format!("\n  {key}?: ")),
+                "'{key}' is not declared in '{name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ts_types_match_json() {
+        let string = r"
+program typed.aleo;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function main:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    async main r1 into r2;
+    output r2 as typed.aleo/main.future;
+
+finalize main:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+";
+        let options = ConvertOptions::new().with_hashes(true).with_pointers(true);
+        let json = convert_program(string, &options).unwrap();
+        check_keys("ProgramCore", &json);
+        check_keys("ProgramID", &json["id"]);
+        check_keys("ClosureCore", &json["closures"]["twice"]);
+        let function = &json["functions"]["main"];
+        check_keys("FunctionCore", function);
+        check_keys("FinalizeCore", &function["finalize_logic"]);
+        check_keys("Input", &function["inputs"][0]);
+        check_keys("Output", &function["outputs"][0]);
+        check_keys("Instruction", &function["instructions"][0]);
+        check_keys("Command", &function["finalize_logic"]["commands"][0]);
+        check_keys("Operand", &function["instructions"][0]["value"]["operands"][0]);
+    }
+}
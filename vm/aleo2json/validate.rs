@@ -0,0 +1,702 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        opcodes::{explain_opcode, opcode_of},
+        raw_operands::mask_comments,
+        Aleo2JsonError,
+        DataflowEntry,
+    },
+    console::{
+        network::Network,
+        program::{FinalizeType, Identifier, Locator, PlaintextType, ProgramID, RegisterType, ValueType},
+        types::Address,
+    },
+};
+use snarkvm_synthesizer_program::{CallOperator, CastType, Command, Instruction, MappingLocator, Program};
+
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// The length of an address literal, i.e. the `aleo1` prefix, followed by its data and its checksum in bech32.
+const ADDRESS_LENGTH: usize = 63;
+
+/// The rule of a reference to a name defined neither in the program, nor in one of its declared imports.
+pub const DANGLING_REFERENCE: &str = "dangling-reference";
+
+/// Validates the given program, returning the violations found, i.e.
+/// `[{ "rule": "register-shadowing", "message": "...", "location": "/functions/main/instructions/0/..." }]`.
+/// Each location is a JSON pointer into the program JSON.
+pub fn validate<N: Network>(program: &Program<N>) -> Vec<Value> {
+    let mut violations = Vec::new();
+    check_cycles(program, &mut violations);
+    check_arity(program, &mut violations);
+    check_register_shadowing(program, &mut violations);
+    violations
+}
+
+/// Returns the number of violations in the given output, i.e. under `"violations"` of a program, or of each program
+/// of a directory, under `"files"`.
+pub fn count_violations(json: &Value) -> usize {
+    let files = json["files"].as_object().into_iter().flatten();
+    json["violations"].as_array().map_or(0, Vec::len) + files.map(|(_, json)| count_violations(json)).sum::<usize>()
+}
+
+/// Returns the number of violations of the given rule in the given output, as `count_violations` does.
+pub fn count_rule_violations(json: &Value, rule: &str) -> usize {
+    let violations = json["violations"].as_array().into_iter().flatten();
+    let files = json["files"].as_object().into_iter().flatten();
+    violations.filter(|violation| violation["rule"] == rule).count()
+        + files.map(|(_, json)| count_rule_violations(json, rule)).sum::<usize>()
+}
+
+/// Checks that each name referenced by the given program is defined, either in the program itself, or in one of its
+/// declared imports, returning each dangling reference found under the `"dangling-reference"` rule, i.e. an
+/// undefined struct, record, closure, function, or mapping, or a program that is not imported. A name of an import
+/// is only checked to be in an imported program, as its definitions are not known. The members of structs and
+/// records are left out, as the parser rejects undefined ones already.
+pub fn validate_closed<N: Network>(program: &Program<N>) -> Vec<Value> {
+    let mut references = DanglingReferences { program, violations: Vec::new() };
+    for (name, mapping) in program.mappings() {
+        references.plaintext_type(&format!("/mappings/{name}/key"), mapping.key().plaintext_type());
+        references.plaintext_type(&format!("/mappings/{name}/value"), mapping.value().plaintext_type());
+    }
+    for (name, closure) in program.closures() {
+        for (index, input) in closure.inputs().iter().enumerate() {
+            references.register_type(&format!("/closures/{name}/inputs/{index}"), input.register_type());
+        }
+        for (index, instruction) in closure.instructions().iter().enumerate() {
+            references.instruction(&format!("/closures/{name}/instructions/{index}"), instruction);
+        }
+        for (index, output) in closure.outputs().iter().enumerate() {
+            references.register_type(&format!("/closures/{name}/outputs/{index}"), output.register_type());
+        }
+    }
+    for (name, function) in program.functions() {
+        for (index, input) in function.inputs().iter().enumerate() {
+            references.value_type(&format!("/functions/{name}/inputs/{index}"), input.value_type());
+        }
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            references.instruction(&format!("/functions/{name}/instructions/{index}"), instruction);
+        }
+        for (index, output) in function.outputs().iter().enumerate() {
+            references.value_type(&format!("/functions/{name}/outputs/{index}"), output.value_type());
+        }
+        let Some(finalize) = function.finalize_logic() else {
+            continue;
+        };
+        for (index, input) in finalize.inputs().iter().enumerate() {
+            let location = format!("/functions/{name}/finalize_logic/inputs/{index}");
+            references.finalize_type(&location, input.finalize_type());
+        }
+        for (index, command) in finalize.commands().iter().enumerate() {
+            references.command(&format!("/functions/{name}/finalize_logic/commands/{index}"), command);
+        }
+    }
+    references.violations
+}
+
+/// Checks that no register of each function and closure is assigned twice, i.e. by an input and an instruction,
+/// or by two instructions. Each reassignment is reported with the indices of both, where an input has no index.
+fn check_register_shadowing<N: Network>(program: &Program<N>, violations: &mut Vec<Value>) {
+    let closures = program.closures().iter().map(|(name, closure)| {
+        let inputs = closure.inputs().iter().map(|input| input.register().clone()).collect::<Vec<_>>();
+        (format!("/closures/{name}"), inputs, closure.instructions())
+    });
+    let functions = program.functions().iter().map(|(name, function)| {
+        let inputs = function.inputs().iter().map(|input| input.register().clone()).collect::<Vec<_>>();
+        (format!("/functions/{name}"), inputs, function.instructions())
+    });
+    for (location, inputs, instructions) in closures.chain(functions) {
+        // The index of the instruction assigning each register, or `None` for an input.
+        let mut assigned = inputs.into_iter().map(|register| (register, None)).collect::<HashMap<_, _>>();
+        for (index, instruction) in instructions.iter().enumerate() {
+            for register in DataflowEntry::from_instruction(index, instruction).writes() {
+                let Some(first) = assigned.insert(register.clone(), Some(index)) else {
+                    continue;
+                };
+                let assigner = first.map_or("an input".to_string(), |first| format!("instruction {first}"));
+                violations.push(json!({
+                    "rule": "register-shadowing",
+                    "message": format!("Register '{register}' is assigned by {assigner}, and by instruction {index}"),
+                    "location": format!("{location}/instructions/{index}"),
+                    "register": register.to_string(),
+                    "first": first,
+                    "second": index,
+                }));
+            }
+        }
+    }
+}
+
+/// Checks that the number of operands of each instruction matches the arity of its opcode, as given by `--explain`.
+fn check_arity<N: Network>(program: &Program<N>, violations: &mut Vec<Value>) {
+    let closures =
+        program.closures().iter().map(|(name, closure)| (format!("/closures/{name}"), closure.instructions()));
+    let functions =
+        program.functions().iter().map(|(name, function)| (format!("/functions/{name}"), function.instructions()));
+    for (location, instructions) in closures.chain(functions) {
+        for (index, instruction) in instructions.iter().enumerate() {
+            let Some(info) = explain_opcode(opcode_of(&instruction.to_string())) else {
+                continue;
+            };
+            let (min, max) = info.arity();
+            let actual = instruction.operands().len();
+            if actual < min || max.is_some_and(|max| actual > max) {
+                let expected = match max {
+                    Some(max) => max.to_string(),
+                    None => format!("at least {min}"),
+                };
+                violations.push(json!({
+                    "rule": "arity",
+                    "message": format!("'{}' expects {expected} operand(s), but found {actual}", info.opcode),
+                    "location": format!("{location}/instructions/{index}"),
+                    "expected_min": min,
+                    "expected_max": max,
+                    "actual": actual,
+                }));
+            }
+        }
+    }
+}
+
+/// Returns the local closures and functions called by the given instructions, in order of first call.
+fn local_callees<N: Network>(program_id: &ProgramID<N>, instructions: &[Instruction<N>]) -> Vec<Identifier<N>> {
+    let mut callees = Vec::new();
+    for instruction in instructions {
+        let Instruction::Call(call) = instruction else {
+            continue;
+        };
+        let callee = match call.operator() {
+            CallOperator::Resource(name) => *name,
+            // A locator into this program is a local call as well.
+            CallOperator::Locator(locator) if locator.program_id() == program_id => *locator.resource(),
+            CallOperator::Locator(_) => continue,
+        };
+        if !callees.contains(&callee) {
+            callees.push(callee);
+        }
+    }
+    callees
+}
+
+/// Checks the call graph of the given program for cycles, i.e. a closure that calls itself.
+/// Each cycle is reported once, with its path, i.e. `"cycle": ["a", "b", "a"]`.
+fn check_cycles<N: Network>(program: &Program<N>, violations: &mut Vec<Value>) {
+    // The callees and the location of each closure and function, in declaration order.
+    let mut graph = IndexMap::new();
+    for (name, closure) in program.closures() {
+        graph.insert(*name, (local_callees(program.id(), closure.instructions()), format!("/closures/{name}")));
+    }
+    for (name, function) in program.functions() {
+        graph.insert(*name, (local_callees(program.id(), function.instructions()), format!("/functions/{name}")));
+    }
+
+    // Walks the graph depth-first, reporting each edge back onto the current path as a cycle.
+    fn visit<N: Network>(
+        name: Identifier<N>,
+        graph: &IndexMap<Identifier<N>, (Vec<Identifier<N>>, String)>,
+        path: &mut Vec<Identifier<N>>,
+        visited: &mut Vec<Identifier<N>>,
+        violations: &mut Vec<Value>,
+    ) {
+        visited.push(name);
+        path.push(name);
+        for callee in graph.get(&name).map(|(callees, _)| callees.as_slice()).unwrap_or_default() {
+            if let Some(start) = path.iter().position(|step| step == callee) {
+                let cycle = path[start..].iter().chain([callee]).map(|step| step.to_string()).collect::<Vec<_>>();
+                violations.push(json!({
+                    "rule": "cycle",
+                    "message": format!("Found a call cycle: {}", cycle.join(" -> ")),
+                    "location": graph[callee].1,
+                    "cycle": cycle,
+                }));
+            } else if !visited.contains(callee) {
+                visit(*callee, graph, path, visited, violations);
+            }
+        }
+        path.pop();
+    }
+
+    let mut visited = Vec::new();
+    for name in graph.keys() {
+        if !visited.contains(name) {
+            visit(*name, &graph, &mut Vec::new(), &mut visited, violations);
+        }
+    }
+}
+
+/// The references to names defined neither in a program, nor in one of its declared imports, as violations.
+struct DanglingReferences<'a, N: Network> {
+    /// The program defining the names.
+    program: &'a Program<N>,
+    /// The dangling references found so far.
+    violations: Vec<Value>,
+}
+
+impl<N: Network> DanglingReferences<'_, N> {
+    /// Reports the given reference to a name of the given kind, i.e. `struct`, at the given location, unless it is
+    /// defined.
+    fn check(&mut self, location: &str, kind: &str, name: impl Display, defined: bool) {
+        if !defined {
+            let problem = match kind {
+                "program" => "is not imported",
+                _ => "is not defined",
+            };
+            self.violations.push(json!({
+                "rule": DANGLING_REFERENCE,
+                "message": format!("The {kind} '{name}' {problem}"),
+                "location": location,
+                "kind": kind,
+                "reference": name.to_string(),
+            }));
+        }
+    }
+
+    /// Checks the given locator, i.e. `token.aleo/mint`, whose resource is of the given kind. The resource of a
+    /// locator into the program itself must be defined, as given by `defined`, and the program of any other imported.
+    fn locator(
+        &mut self,
+        location: &str,
+        kind: &str,
+        locator: &Locator<N>,
+        defined: fn(&Program<N>, &Identifier<N>) -> bool,
+    ) {
+        let (program, program_id) = (self.program, locator.program_id());
+        match program_id == program.id() {
+            true => self.check(location, kind, locator, defined(program, locator.resource())),
+            false => self.check(location, "program", program_id, program.contains_import(program_id)),
+        }
+    }
+
+    /// Checks the struct of the given plaintext type, or of its elements, if any.
+    fn plaintext_type(&mut self, location: &str, plaintext_type: &PlaintextType<N>) {
+        match plaintext_type {
+            PlaintextType::Literal(_) => (),
+            PlaintextType::Struct(name) => self.check(location, "struct", name, self.program.contains_struct(name)),
+            PlaintextType::Array(array_type) => self.plaintext_type(location, array_type.base_element_type()),
+        }
+    }
+
+    /// Checks the struct, the record, or the function of the future of the given register type of a closure.
+    fn register_type(&mut self, location: &str, register_type: &RegisterType<N>) {
+        match register_type {
+            RegisterType::Plaintext(plaintext_type) => self.plaintext_type(location, plaintext_type),
+            RegisterType::Record(name) => self.check(location, "record", name, self.program.contains_record(name)),
+            RegisterType::ExternalRecord(locator) => {
+                self.locator(location, "record", locator, Program::contains_record)
+            }
+            RegisterType::Future(locator) => self.locator(location, "function", locator, Program::contains_function),
+        }
+    }
+
+    /// Checks the struct, the record, or the function of the future of the given value type of a function.
+    fn value_type(&mut self, location: &str, value_type: &ValueType<N>) {
+        match value_type {
+            ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type) => self.plaintext_type(location, plaintext_type),
+            ValueType::Record(name) => self.check(location, "record", name, self.program.contains_record(name)),
+            ValueType::ExternalRecord(locator) => self.locator(location, "record", locator, Program::contains_record),
+            ValueType::Future(locator) => self.locator(location, "function", locator, Program::contains_function),
+        }
+    }
+
+    /// Checks the struct, or the function of the future of the given input type of a finalize.
+    fn finalize_type(&mut self, location: &str, finalize_type: &FinalizeType<N>) {
+        match finalize_type {
+            FinalizeType::Plaintext(plaintext_type) => self.plaintext_type(location, plaintext_type),
+            FinalizeType::Future(locator) => self.locator(location, "function", locator, Program::contains_function),
+        }
+    }
+
+    /// Checks the callee of a `call`, the function of an `async`, and the destination type of a `cast`.
+    fn instruction(&mut self, location: &str, instruction: &Instruction<N>) {
+        let program = self.program;
+        let cast_type = match instruction {
+            Instruction::Call(call) => {
+                return match call.operator() {
+                    CallOperator::Resource(name) => {
+                        let defined = program.contains_closure(name) || program.contains_function(name);
+                        self.check(location, "closure or function", name, defined)
+                    }
+                    CallOperator::Locator(locator) => {
+                        self.locator(location, "function", locator, Program::contains_function)
+                    }
+                };
+            }
+            Instruction::Async(async_) => {
+                let name = async_.function_name();
+                return self.check(location, "function", name, program.contains_function(name));
+            }
+            Instruction::Cast(cast) => cast.cast_type(),
+            Instruction::CastLossy(cast) => cast.cast_type(),
+            _ => return,
+        };
+        match cast_type {
+            CastType::GroupXCoordinate | CastType::GroupYCoordinate => (),
+            CastType::Plaintext(plaintext_type) => self.plaintext_type(location, plaintext_type),
+            CastType::Record(name) => self.check(location, "record", name, program.contains_record(name)),
+            CastType::ExternalRecord(locator) => self.locator(location, "record", locator, Program::contains_record),
+        }
+    }
+
+    /// Checks the mapping of a finalize command, or else the instruction it is.
+    fn command(&mut self, location: &str, command: &Command<N>) {
+        let program = self.program;
+        let mapping = match command {
+            Command::Instruction(instruction) => return self.instruction(location, instruction),
+            Command::Contains(contains) => contains.mapping_name(),
+            Command::Remove(remove) => remove.mapping_name(),
+            Command::Set(set) => set.mapping_name(),
+            Command::Get(get) => match get.mapping() {
+                MappingLocator::Resource(name) => name,
+                MappingLocator::Locator(locator) => {
+                    return self.locator(location, "mapping", locator, Program::contains_mapping);
+                }
+            },
+            Command::GetOrUse(get_or_use) => match get_or_use.mapping() {
+                MappingLocator::Resource(name) => name,
+                MappingLocator::Locator(locator) => {
+                    return self.locator(location, "mapping", locator, Program::contains_mapping);
+                }
+            },
+            _ => return,
+        };
+        self.check(location, "mapping", mapping, program.contains_mapping(mapping));
+    }
+}
+
+/// Returns each word of the given source outside of its comments, with its offset, i.e. the `aleo1...` of an address
+/// literal, or the `5u8` of an integer literal.
+fn source_words(source: &str) -> Vec<(usize, &str)> {
+    let masked = mask_comments(source);
+    let bytes = masked.as_bytes();
+    let is_word = |byte: &u8| byte.is_ascii_alphanumeric() || *byte == b'_';
+    let mut words = Vec::new();
+    let mut start = 0;
+    while let Some(length) = bytes[start..].iter().position(is_word) {
+        start += length;
+        let end = bytes[start..].iter().position(|byte| !is_word(byte)).map_or(bytes.len(), |length| start + length);
+        // Each word is delimited by ASCII bytes, so its offsets are on character boundaries of the source.
+        words.push((start, &source[start..end]));
+        start = end;
+    }
+    words
+}
+
+/// Validates the literals of the given source, before it is parsed, as the parser rejects a program with an invalid
+/// literal at the start of its component, rather than at the literal. Fails at the first invalid literal, i.e. an
+/// address with an invalid bech32 checksum, or an integer out of the range of its type, as `300u8`, with its offset
+/// in the source.
+pub fn validate_source<N: Network>(source: &str) -> Result<(), Aleo2JsonError> {
+    for (offset, word) in source_words(source) {
+        // Only a word of the length of an address is one, as an identifier may also start with `aleo1`.
+        if word.len() == ADDRESS_LENGTH && word.starts_with("aleo1") {
+            if let Err(error) = Address::<N>::from_str(word) {
+                let message = error.to_string();
+                return Err(Aleo2JsonError::InvalidLiteral { offset, literal: word.to_string(), message });
+            }
+        }
+        // The sign of a negative integer precedes its word.
+        let negative = source[..offset].ends_with('-');
+        if let Some(message) = check_integer_range(word, negative) {
+            let (offset, literal) = match negative {
+                true => (offset - 1, format!("-{word}")),
+                false => (offset, word.to_string()),
+            };
+            return Err(Aleo2JsonError::InvalidLiteral { offset, literal, message });
+        }
+    }
+    Ok(())
+}
+
+/// Returns why the given word is out of the range of its integer type, if it is an integer literal, i.e.
+/// `out of the range of u8, from 0 to 255` for `300u8`, or `None` if it is within it, or is not an integer literal.
+fn check_integer_range(word: &str, negative: bool) -> Option<String> {
+    if !word.starts_with(|character: char| character.is_ascii_digit()) {
+        return None;
+    }
+    let (digits, literal_type) = word.split_at(word.find(['i', 'u'])?);
+    let (min, max) = integer_bounds(literal_type)?;
+    let digits = digits.replace('_', "");
+    if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    // A literal too large for the widest type is out of the range of any type.
+    let in_range = match negative {
+        true => format!("-{digits}").parse::<i128>().is_ok_and(|value| value >= min),
+        false => digits.parse::<u128>().is_ok_and(|value| value <= max),
+    };
+    (!in_range).then(|| format!("out of the range of {literal_type}, from {min} to {max}"))
+}
+
+/// Returns the minimum and the maximum of the integer type of the given name, i.e. `(0, 255)` for `u8`, from its
+/// bit width, or `None` if it is not an integer type.
+fn integer_bounds(literal_type: &str) -> Option<(i128, u128)> {
+    let (signed, bits) = match literal_type.split_at(1) {
+        ("i", bits) => (true, bits.parse::<u32>().ok()?),
+        ("u", bits) => (false, bits.parse::<u32>().ok()?),
+        _ => return None,
+    };
+    if !matches!(bits, 8 | 16 | 32 | 64 | 128) {
+        return None;
+    }
+    // Shift the bounds of the widest type down to the bit width.
+    match signed {
+        true => Some((i128::MIN >> (128 - bits), (i128::MAX >> (128 - bits)) as u128)),
+        false => Some((0, u128::MAX >> (128 - bits))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    const ADDRESS: &str = "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9";
+
+    #[test]
+    fn test_validate() {
+        let program = parse_program::<CurrentNetwork>(&format!(
+            r"
+program addresses.aleo;
+
+function main:
+    input r0 as address.private;
+    is.eq {ADDRESS} r0 into r1;
+    assert.eq r1 true;
+"
+        ))
+        .unwrap();
+        assert!(validate(&program).is_empty());
+    }
+
+    #[test]
+    fn test_count_violations() {
+        let violation = json!({ "rule": "register-shadowing" });
+        assert_eq!(count_violations(&json!({ "violations": [violation, violation] })), 2);
+        assert_eq!(count_violations(&json!({ "violations": [] })), 0);
+        assert_eq!(count_violations(&json!({ "id": "main.aleo" })), 0);
+
+        // Each program of a directory is counted, and a failed one has none.
+        let files = json!({
+            "files": { "a.aleo": { "violations": [violation] }, "b.aleo": { "error": "...", "violations": null } },
+            "summary": { "succeeded": 1, "failed": 1, "skipped": 0 },
+        });
+        assert_eq!(count_violations(&files), 1);
+
+        // Only the violations of the given rule are counted, in each program of a directory as well.
+        let dangling = json!({ "rule": DANGLING_REFERENCE });
+        let json = json!({ "violations": [violation, dangling], "files": { "a.aleo": { "violations": [dangling] } } });
+        assert_eq!(count_rule_violations(&json, DANGLING_REFERENCE), 2);
+        assert_eq!(count_rule_violations(&json, "cycle"), 0);
+    }
+
+    #[test]
+    fn test_validate_closed() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+program dangling.aleo;
+
+struct point:
+    x as u8;
+
+mapping counts:
+    key as point.public;
+    value as total.public;
+
+closure twice:
+    input r0 as u8;
+    call missing r0 into r1;
+    output r1 as u8;
+
+function main:
+    input r0 as credits.aleo/credits.record;
+    input r1 as token.aleo/token.record;
+    input r2 as u8.public;
+    call twice r2 into r3;
+    cast r2 into r4 as [vector; 2u32];
+    output r4 as [vector; 2u32].private;
+
+function update:
+    input r0 as u8.public;
+    async update r0 into r1;
+    output r1 as dangling.aleo/update.future;
+
+finalize update:
+    input r0 as u8.public;
+    cast r0 into r1 as point;
+    get.or_use counts[r1] 0u8 into r2;
+    contains totals[r1] into r3;
+    get token.aleo/supply[r0] into r4;
+",
+        )
+        .unwrap();
+        let violations = validate_closed(&program);
+        let locations = violations.iter().map(|violation| violation["location"].as_str().unwrap()).collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            [
+                "/mappings/counts/value",
+                "/closures/twice/instructions/0",
+                "/functions/main/inputs/1",
+                "/functions/main/instructions/1",
+                "/functions/main/outputs/0",
+                "/functions/update/finalize_logic/commands/2",
+                "/functions/update/finalize_logic/commands/3",
+            ]
+        );
+        assert!(violations.iter().all(|violation| violation["rule"] == DANGLING_REFERENCE));
+        assert_eq!(violations[0]["message"], "The struct 'total' is not defined");
+        assert_eq!(violations[1]["kind"], "closure or function");
+        assert_eq!(violations[1]["reference"], "missing");
+        assert_eq!(violations[2]["message"], "The program 'token.aleo' is not imported");
+        assert_eq!(violations[5]["kind"], "mapping");
+
+        // A program referencing only its own definitions, and those of its imports, is closed.
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+program closed.aleo;
+
+function main:
+    input r0 as credits.aleo/credits.record;
+    call credits.aleo/transfer_private r0 r0.owner 1u64 into r1 r2;
+    output r1 as credits.aleo/credits.record;
+",
+        )
+        .unwrap();
+        assert!(validate_closed(&program).is_empty());
+    }
+
+    #[test]
+    fn test_check_cycles() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program cycles.aleo;
+
+closure even:
+    input r0 as u8;
+    call odd r0 into r1;
+    output r1 as u8;
+
+closure odd:
+    input r0 as u8;
+    call even r0 into r1;
+    output r1 as u8;
+
+closure leaf:
+    input r0 as u8;
+    add r0 r0 into r1;
+    output r1 as u8;
+
+function main:
+    input r0 as u8.private;
+    call even r0 into r1;
+    call leaf r1 into r2;
+    output r2 as u8.private;
+",
+        )
+        .unwrap();
+
+        let mut violations = Vec::new();
+        check_cycles(&program, &mut violations);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0]["rule"], "cycle");
+        assert_eq!(violations[0]["cycle"], json!(["even", "odd", "even"]));
+        assert_eq!(violations[0]["location"], "/closures/even");
+        assert_eq!(violations[0]["message"], "Found a call cycle: even -> odd -> even");
+    }
+
+    #[test]
+    fn test_check_register_shadowing() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program shadowing.aleo;
+
+function main:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    mul r1 r1 into r1;
+    sub r1 r1 into r0;
+    output r1 as u8.private;
+",
+        )
+        .unwrap();
+
+        let mut violations = Vec::new();
+        check_register_shadowing(&program, &mut violations);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0]["rule"], "register-shadowing");
+        assert_eq!(violations[0]["location"], "/functions/main/instructions/1");
+        assert_eq!(violations[0]["message"], "Register 'r1' is assigned by instruction 0, and by instruction 1");
+        assert_eq!((violations[0]["first"].clone(), violations[0]["second"].clone()), (json!(0), json!(1)));
+        assert_eq!(violations[1]["message"], "Register 'r0' is assigned by an input, and by instruction 2");
+        assert_eq!(violations[1]["first"], Value::Null);
+    }
+
+    #[test]
+    fn test_validate_source() {
+        // Corrupt the checksum of an address literal, after a valid one, and one in a comment.
+        let corrupted = format!("{}8", &ADDRESS[..ADDRESS.len() - 1]);
+        let source = format!(
+            r"
+program addresses.aleo;
+
+function main:
+    // {corrupted}
+    is.eq {ADDRESS} {corrupted} into r0;
+"
+        );
+        let error = validate_source::<CurrentNetwork>(&source).unwrap_err();
+        assert!(matches!(&error, Aleo2JsonError::InvalidLiteral { literal, .. } if *literal == corrupted));
+        assert_eq!(error.offset(), source.rfind(&corrupted));
+        assert_eq!(error.kind(), "invalid_literal");
+
+        assert!(parse_program::<CurrentNetwork>(&source).is_err());
+        assert!(validate_source::<CurrentNetwork>(&source.replace(&corrupted, ADDRESS)).is_ok());
+    }
+
+    #[test]
+    fn test_check_integer_range() {
+        assert_eq!(check_integer_range("300u8", false).unwrap(), "out of the range of u8, from 0 to 255");
+        assert_eq!(check_integer_range("255u8", false), None);
+        assert!(check_integer_range("129i8", true).is_some());
+        assert_eq!(check_integer_range("128i8", true), None);
+        assert!(check_integer_range("1u64", true).is_some());
+        assert_eq!(check_integer_range("340282366920938463463374607431768211455u128", false), None);
+        assert!(check_integer_range("170141183460469231731687303715884105728i128", false).is_some());
+        assert_eq!(check_integer_range("1_000u16", false), None);
+        // Neither a field literal, nor an identifier, nor a register is an integer literal.
+        assert_eq!(check_integer_range("300field", false), None);
+        assert_eq!(check_integer_range("u8", false), None);
+        assert_eq!(check_integer_range("r300", false), None);
+        assert_eq!(integer_bounds("i16"), Some((-32768, 32767)));
+        assert_eq!(integer_bounds("field"), None);
+
+        // The source is checked at the literal, with its sign.
+        let source = "program ranges.aleo;
+
+function main:
+    add 1u8 -129i8 into r0;
+";
+        let error = validate_source::<CurrentNetwork>(source).unwrap_err();
+        assert!(matches!(&error, Aleo2JsonError::InvalidLiteral { literal, .. } if literal == "-129i8"));
+        assert_eq!(error.offset(), source.find("-129i8"));
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{
+    network::Network,
+    program::{Identifier, LiteralType, Plaintext, PlaintextType},
+};
+use snarkvm_synthesizer_program::Program;
+
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+/// The definition of some plaintext members, and the type of each member.
+type Members<N> = (&'static str, Identifier<N>, IndexMap<Identifier<N>, PlaintextType<N>>);
+
+/// Returns whether the given plaintext is of the given type, resolving each struct in the given program.
+fn conforms<N: Network>(plaintext: &Plaintext<N>, plaintext_type: &PlaintextType<N>, program: &Program<N>) -> bool {
+    match (plaintext, plaintext_type) {
+        (Plaintext::Literal(literal, _), PlaintextType::Literal(literal_type)) => literal.to_type() == *literal_type,
+        (Plaintext::Struct(members, _), PlaintextType::Struct(name)) => {
+            let Ok(struct_) = program.get_struct(name) else {
+                return false;
+            };
+            // The members of a struct are in declaration order.
+            members.len() == struct_.members().len()
+                && members.iter().zip(struct_.members()).all(|((name, member), (expected, member_type))| {
+                    name == expected && conforms(member, member_type, program)
+                })
+        }
+        (Plaintext::Array(elements, _), PlaintextType::Array(array_type)) => {
+            elements.len() == **array_type.length() as usize
+                && elements.iter().all(|element| conforms(element, array_type.next_element_type(), program))
+        }
+        _ => false,
+    }
+}
+
+/// Returns the definition of the given members in the given program, i.e. `("struct", "point")`, and the type of
+/// each member, given the type expected of them, if any. A struct matches its members in order, and a record matches
+/// its owner and its entries in any order, as its entries are written with their visibility otherwise.
+fn resolve_members<N: Network>(
+    members: &IndexMap<Identifier<N>, Plaintext<N>>,
+    expected: Option<&PlaintextType<N>>,
+    program: &Program<N>,
+) -> Option<Members<N>> {
+    let plaintext = Plaintext::Struct(members.clone(), Default::default());
+    // The expected struct takes precedence over any other struct of the same members.
+    let candidates = expected.into_iter().filter_map(|expected| match expected {
+        PlaintextType::Struct(name) => program.get_struct(name).ok(),
+        _ => None,
+    });
+    let struct_ = candidates.chain(program.structs().values()).find(|struct_| {
+        conforms(&plaintext, &PlaintextType::Struct(*struct_.name()), program)
+    });
+    if let Some(struct_) = struct_ {
+        return Some(("struct", *struct_.name(), struct_.members().clone()));
+    }
+    program.records().values().find_map(|record| {
+        let owner = Identifier::from_str("owner").ok()?;
+        let mut types = IndexMap::from([(owner, PlaintextType::Literal(LiteralType::Address))]);
+        types.extend(record.entries().iter().map(|(name, entry)| (*name, entry.plaintext_type().clone())));
+        let matches = members.len() == types.len()
+            && members.iter().all(|(name, member)| {
+                types.get(name).is_some_and(|member_type| conforms(member, member_type, program))
+            });
+        matches.then(|| ("record", *record.name(), types))
+    })
+}
+
+/// Returns the given plaintext value as JSON, with the type of each value under `"plaintext_type"`, i.e.
+/// `"plaintext_type": "[u64; 2u32]"`. Each literal is typed by itself. Without the given program, a struct is
+/// untyped (`null`), and an array is typed by its elements if they are all of the same type. With the program, each
+/// struct is resolved to the struct of the program whose members it matches, and a struct matching the owner and
+/// the entries of a record instead, to the record under `"record"`, so that each of their members is typed.
+pub fn value_to_json<N: Network>(plaintext: &Plaintext<N>, program: Option<&Program<N>>) -> Value {
+    plaintext_to_json(plaintext, None, program)
+}
+
+/// Returns the given plaintext as JSON, given the type expected of it, if any, as `value_to_json` does.
+fn plaintext_to_json<N: Network>(
+    plaintext: &Plaintext<N>,
+    expected: Option<&PlaintextType<N>>,
+    program: Option<&Program<N>>,
+) -> Value {
+    match plaintext {
+        Plaintext::Literal(literal, _) => {
+            let mut json = literal.to_json();
+            json["plaintext_type"] = json!(literal.to_type().to_string());
+            json
+        }
+        Plaintext::Struct(members, _) => {
+            let resolved = program.and_then(|program| resolve_members(members, expected, program));
+            let member_type = |name: &Identifier<N>| resolved.as_ref().and_then(|(_, _, types)| types.get(name));
+            let json_members = members.iter().map(|(name, member)| {
+                (name.to_string(), plaintext_to_json(member, member_type(name), program))
+            });
+            let (plaintext_type, record) = match &resolved {
+                Some(("record", name, _)) => (None, Some(name.to_string())),
+                Some((_, name, _)) => (Some(name.to_string()), None),
+                None => (None, None),
+            };
+            let mut json = json!({
+                "type": "Struct",
+                "plaintext_type": plaintext_type,
+                "members": Value::Object(json_members.collect::<Map<_, _>>()),
+                "str": plaintext.to_string(),
+            });
+            if let Some(record) = record {
+                json["record"] = json!(record);
+            }
+            json
+        }
+        Plaintext::Array(elements, _) => {
+            let element_type = match expected {
+                Some(PlaintextType::Array(array_type)) => Some(array_type.next_element_type()),
+                _ => None,
+            };
+            let elements = elements.iter().map(|element| plaintext_to_json(element, element_type, program));
+            let elements = elements.collect::<Vec<_>>();
+            // An array is of the expected type if it conforms to it, or else of the type of its elements.
+            let conforming =
+                expected.filter(|expected| program.is_some_and(|program| conforms(plaintext, expected, program)));
+            let plaintext_type = match conforming {
+                Some(expected) => json!(expected.to_string()),
+                None => match elements.first().map(|element| &element["plaintext_type"]) {
+                    Some(Value::String(element_type))
+                        if elements.iter().all(|element| element["plaintext_type"] == *element_type) =>
+                    {
+                        json!(format!("[{element_type}; {}u32]", elements.len()))
+                    }
+                    _ => Value::Null,
+                },
+            };
+            json!({
+                "type": "Array",
+                "plaintext_type": plaintext_type,
+                "elements": elements,
+                "str": plaintext.to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{parse_program, CurrentNetwork};
+
+    const PROGRAM: &str = r"
+program typed.aleo;
+
+struct point:
+    x as u8;
+    y as u8;
+
+struct path:
+    points as [point; 2u32];
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function main:
+    input r0 as u8.public;
+";
+
+    const OWNER: &str = "aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah";
+
+    #[test]
+    fn test_value_to_json() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let value = |string: &str| Plaintext::<CurrentNetwork>::from_str(string).unwrap();
+
+        // Without a context, only the literals, and the arrays of literals, are typed.
+        let path = value("{ points: [{ x: 1u8, y: 2u8 }, { x: 3u8, y: 4u8 }] }");
+        let json = value_to_json(&path, None);
+        assert_eq!(json["type"], "Struct");
+        assert!(json["plaintext_type"].is_null());
+        assert!(json["members"]["points"]["plaintext_type"].is_null());
+        assert_eq!(json["members"]["points"]["elements"][0]["members"]["x"]["plaintext_type"], "u8");
+        assert_eq!(value_to_json(&value("[1u8, 2u8]"), None)["plaintext_type"], "[u8; 2u32]");
+        assert!(value_to_json(&value("[1u8, 2u16]"), None)["plaintext_type"].is_null());
+
+        // With a context, each struct is resolved, and so is each array of structs.
+        let json = value_to_json(&path, Some(&program));
+        assert_eq!(json["plaintext_type"], "path");
+        assert_eq!(json["members"]["points"]["plaintext_type"], "[point; 2u32]");
+        assert_eq!(json["members"]["points"]["elements"][1]["plaintext_type"], "point");
+
+        // A struct of the owner and the entries of a record is resolved to the record, in any order.
+        let json = value_to_json(&value(&format!("{{ amount: 5u64, owner: {OWNER} }}")), Some(&program));
+        assert_eq!(json["record"], "token");
+        assert!(json["plaintext_type"].is_null());
+        assert_eq!(json["members"]["amount"]["plaintext_type"], "u64");
+
+        // A struct matching no definition is untyped.
+        let json = value_to_json(&value("{ x: 1u8, y: 2u16 }"), Some(&program));
+        assert!(json["plaintext_type"].is_null());
+        assert!(json.get("record").is_none());
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::redact_private;
+
+use serde_json::Value;
+
+/// The sections of a program whose entries are definitions, i.e. `{ "structs": { "point": { ... } } }`.
+const DEFINITION_SECTIONS: [&str; 3] = ["mappings", "structs", "records"];
+
+/// The sections of a program whose entries are functions, i.e. `{ "functions": { "main": { ... } } }`.
+const FUNCTION_SECTIONS: [&str; 2] = ["closures", "functions"];
+
+/// A transform of the JSON of a program, invoked on each of its nodes by `walk_program`.
+/// Each method may mutate or annotate the node it is given, and does nothing by default.
+pub trait NodeVisitor {
+    /// Visits the program, after each of its nodes.
+    fn visit_program(&mut self, _json: &mut Value) {}
+
+    /// Visits the given mapping, struct, or record, i.e. `("structs", "point")`.
+    fn visit_definition(&mut self, _section: &str, _name: &str, _json: &mut Value) {}
+
+    /// Visits the given function or closure, i.e. `("functions", "main")`, before its instructions.
+    fn visit_function(&mut self, _section: &str, _name: &str, _json: &mut Value) {}
+
+    /// Visits the instruction at the given index in the given function or closure.
+    fn visit_instruction(&mut self, _function: &str, _index: usize, _json: &mut Value) {}
+}
+
+/// Walks the JSON of a program with the given visitor: each definition, then each closure and function followed by
+/// its instructions, in declaration order, and finally the program itself. Any other JSON is only visited as a program.
+pub fn walk_program(json: &mut Value, visitor: &mut dyn NodeVisitor) {
+    for section in DEFINITION_SECTIONS {
+        if let Some(definitions) = json.get_mut(section).and_then(Value::as_object_mut) {
+            definitions.iter_mut().for_each(|(name, definition)| visitor.visit_definition(section, name, definition));
+        }
+    }
+    for section in FUNCTION_SECTIONS {
+        let Some(functions) = json.get_mut(section).and_then(Value::as_object_mut) else {
+            continue;
+        };
+        for (name, function) in functions.iter_mut() {
+            visitor.visit_function(section, name, function);
+            if let Some(instructions) = function.get_mut("instructions").and_then(Value::as_array_mut) {
+                for (index, instruction) in instructions.iter_mut().enumerate() {
+                    visitor.visit_instruction(name, index, instruction);
+                }
+            }
+        }
+    }
+    visitor.visit_program(json);
+}
+
+/// A visitor replacing each private value with the given token, as `redact_private` does.
+pub struct Redactor {
+    /// The token to replace private values with.
+    token: String,
+}
+
+impl Redactor {
+    /// Initializes a visitor replacing each private value with the given token.
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+}
+
+impl NodeVisitor for Redactor {
+    fn visit_program(&mut self, json: &mut Value) {
+        redact_private(json, &self.token);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{convert_program, parse_program, program_to_json_with, ConvertOptions, CurrentNetwork};
+
+    use serde_json::json;
+
+    /// Drops the `"str"` of each instruction, and counts the instructions of each function.
+    #[derive(Default)]
+    struct Annotator {
+        visited: Vec<String>,
+    }
+
+    impl NodeVisitor for Annotator {
+        fn visit_definition(&mut self, section: &str, name: &str, _json: &mut Value) {
+            self.visited.push(format!("{section}/{name}"));
+        }
+
+        fn visit_function(&mut self, section: &str, name: &str, json: &mut Value) {
+            self.visited.push(format!("{section}/{name}"));
+            json["num_instructions"] = json!(json["instructions"].as_array().map_or(0, Vec::len));
+        }
+
+        fn visit_instruction(&mut self, _function: &str, _index: usize, json: &mut Value) {
+            json.as_object_mut().unwrap().remove("str");
+        }
+
+        fn visit_program(&mut self, json: &mut Value) {
+            json["visited"] = json!(self.visited);
+        }
+    }
+
+    #[test]
+    fn test_walk_program() {
+        let string = r"
+program visited.aleo;
+
+struct point:
+    x as field;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function main:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    add r1 r1 into r2;
+";
+        let program = parse_program::<CurrentNetwork>(string).unwrap();
+        let json =
+            program_to_json_with(&program, &ConvertOptions::new(), &mut [&mut Annotator::default()]).unwrap();
+        assert_eq!(json["visited"], json!(["structs/point", "closures/twice", "functions/main"]));
+        assert_eq!(json["functions"]["main"]["num_instructions"], 2);
+        assert!(json["functions"]["main"]["instructions"][0].get("str").is_none());
+
+        // Without visitors, the conversion is unchanged.
+        let json = program_to_json_with(&program, &ConvertOptions::new(), &mut []).unwrap();
+        assert_eq!(json, convert_program(string, &ConvertOptions::new()).unwrap());
+    }
+
+    #[test]
+    fn test_redactor() {
+        let mut json = json!({ "data": { "amount": { "value": "5u64", "visibility": "private" } } });
+        walk_program(&mut json, &mut Redactor::new("***".to_string()));
+        assert_eq!(json["data"]["amount"]["value"], "***");
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use notify::{RecursiveMode, Watcher};
+use std::{path::Path, sync::mpsc, time::Duration};
+
+/// The delay used to coalesce the burst of events emitted by a single save.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Calls `on_change` once on startup, and again each time the file at the given path is modified.
+/// This function only returns if the watcher fails.
+pub fn watch_file(path: &Path, mut on_change: impl FnMut()) -> Result<()> {
+    // Editors often save by replacing the file, so watch the parent directory and filter by file name.
+    let path = path.canonicalize()?;
+    let directory = path.parent().ok_or_else(|| anyhow!("'{}' has no parent directory", path.display()))?;
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+    on_change();
+    loop {
+        let event = receiver.recv()??;
+        let is_save = event.kind.is_modify() || event.kind.is_create();
+        if !is_save || !event.paths.contains(&path) {
+            continue;
+        }
+        // Drain the remaining events of this save.
+        std::thread::sleep(DEBOUNCE);
+        while receiver.try_recv().is_ok() {}
+
+        on_change();
+    }
+}
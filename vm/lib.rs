@@ -16,13 +16,18 @@
 #![allow(clippy::module_inception)]
 #![cfg_attr(test, allow(clippy::assertions_on_result_states))]
 
-#[cfg(feature = "cli")]
+#[cfg(any(feature = "cli", feature = "aleo2json"))]
 #[macro_use]
 extern crate thiserror;
 
+#[cfg(feature = "aleo2json")]
+pub mod aleo2json;
+// The `file` and `package` modules need the dependencies of the `cli` feature.
 #[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "cli")]
 pub mod file;
+#[cfg(feature = "cli")]
 pub mod package;
 
 #[cfg(feature = "algorithms")]